@@ -0,0 +1,197 @@
+use std::path::PathBuf;
+
+use clap::{ArgGroup, Parser, Subcommand, ValueEnum};
+use clap_complete::Shell;
+
+/// Control and inspect Tapo devices from the command line.
+///
+/// The Tapo account credentials are read from the `--username`/`--password` flags,
+/// the `TAPO_USERNAME`/`TAPO_PASSWORD` environment variables or the configuration file,
+/// in this order.
+#[derive(Debug, Parser)]
+#[command(name = "tapo", version)]
+pub struct Cli {
+    /// Tapo account email.
+    #[arg(long, env = "TAPO_USERNAME", global = true)]
+    pub username: Option<String>,
+    /// Tapo account password.
+    #[arg(long, env = "TAPO_PASSWORD", global = true, hide_env_values = true)]
+    pub password: Option<String>,
+    /// Path to the configuration file [default: `$XDG_CONFIG_HOME/tapo/config.toml`].
+    #[arg(long, env = "TAPO_CONFIG", global = true)]
+    pub config: Option<PathBuf>,
+    /// Print the output as JSON.
+    #[arg(long, global = true)]
+    pub json: bool,
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Discover the devices on the network.
+    Discover {
+        /// Network target for the discovery, e.g. `192.168.1.255` [default: `255.255.255.255`].
+        #[arg(long, env = "TAPO_DISCOVERY_TARGET")]
+        target: Option<String>,
+        /// Discovery timeout in seconds.
+        #[arg(long, default_value_t = 5)]
+        timeout: u64,
+    },
+    /// Print the device info.
+    Info {
+        /// IP address of the device.
+        ip: String,
+    },
+    /// Turn the device on.
+    On {
+        /// IP address of the device.
+        ip: String,
+    },
+    /// Turn the device off.
+    Off {
+        /// IP address of the device.
+        ip: String,
+    },
+    /// Set the brightness of a light.
+    Brightness {
+        /// IP address of the device.
+        ip: String,
+        /// Brightness, between 1 and 100.
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=100))]
+        brightness: u8,
+    },
+    /// Set the color of a color light.
+    #[command(group(ArgGroup::new("target").required(true)))]
+    Color {
+        /// IP address of the device.
+        ip: String,
+        /// Preset color as defined in the Google Home app, e.g. `WarmWhite` or `Crimson`.
+        #[arg(group = "target")]
+        name: Option<String>,
+        /// Hue, between 0 and 360.
+        #[arg(
+            long,
+            group = "target",
+            requires = "saturation",
+            value_parser = clap::value_parser!(u16).range(0..=360),
+        )]
+        hue: Option<u16>,
+        /// Saturation, between 1 and 100.
+        #[arg(long, requires = "hue", value_parser = clap::value_parser!(u8).range(1..=100))]
+        saturation: Option<u8>,
+        /// Color temperature in Kelvin, between 2500 and 6500.
+        #[arg(long, group = "target", value_parser = clap::value_parser!(u16).range(2500..=6500))]
+        temperature: Option<u16>,
+    },
+    /// Set a lighting effect on an RGBIC light strip.
+    Effect {
+        /// IP address of the device.
+        ip: String,
+        /// Lighting effect preset, e.g. `Aurora` or `Rainbow`.
+        name: String,
+    },
+    /// Print the current power and the energy usage of an energy monitoring plug.
+    Energy {
+        /// IP address of the device.
+        ip: String,
+    },
+    /// Manage the countdown timer of a plug.
+    Timer {
+        /// IP address of the device.
+        ip: String,
+        #[command(subcommand)]
+        action: TimerAction,
+    },
+    /// Save a JPEG snapshot from a camera.
+    Snapshot {
+        /// IP address of the device.
+        ip: String,
+        /// Path the snapshot is written to.
+        #[arg(long, short, default_value = "snapshot.jpg")]
+        output: PathBuf,
+        /// Camera account username, as set in the Tapo app.
+        #[arg(long, env = "TAPO_CAMERA_USERNAME")]
+        camera_username: Option<String>,
+        /// Camera account password, as set in the Tapo app.
+        #[arg(long, env = "TAPO_CAMERA_PASSWORD", hide_env_values = true)]
+        camera_password: Option<String>,
+    },
+    /// Pan and tilt a camera, or manage its presets.
+    Ptz {
+        /// IP address of the device.
+        ip: String,
+        #[command(subcommand)]
+        action: PtzAction,
+    },
+    /// Inspect a hub.
+    Hub {
+        #[command(subcommand)]
+        action: HubAction,
+    },
+    /// Print the shell completion script.
+    Completions {
+        /// The shell to generate the completion script for.
+        shell: Shell,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum TimerAction {
+    /// Print the armed timer.
+    Get,
+    /// Arm the timer, replacing the one that is currently armed.
+    Set {
+        /// Delay in seconds, between 1 second and 24 hours.
+        #[arg(value_parser = clap::value_parser!(u64).range(1..=86_400))]
+        delay: u64,
+        /// The state the plug transitions to when the timer fires.
+        #[arg(long, value_enum, default_value_t = State::Off)]
+        state: State,
+    },
+    /// Cancel the armed timer.
+    Clear,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum State {
+    On,
+    Off,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum PtzAction {
+    /// Move the camera. Positive values move right and up, negative values move left and down.
+    Move {
+        #[arg(long, default_value_t = 0, allow_negative_numbers = true)]
+        pan: i32,
+        #[arg(long, default_value_t = 0, allow_negative_numbers = true)]
+        tilt: i32,
+    },
+    /// List the saved presets.
+    Presets,
+    /// Move the camera to a saved preset.
+    Goto {
+        /// Preset ID.
+        id: String,
+    },
+    /// Save the current position as a preset.
+    Save {
+        /// Preset name.
+        name: String,
+    },
+    /// Delete a saved preset.
+    Delete {
+        /// Preset ID.
+        id: String,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum HubAction {
+    /// List the child devices of the hub.
+    Children {
+        /// IP address of the hub.
+        ip: String,
+    },
+}
@@ -0,0 +1,303 @@
+use std::io::Write;
+use std::time::Duration;
+
+use anyhow::{Context as _, anyhow};
+use clap::CommandFactory as _;
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+use tapo::requests::{Color, LightingEffectPreset};
+use tapo::responses::PowerState;
+use tapo::{ApiClient, DiscoveryResult, StreamExt as _};
+
+use crate::cli::{Cli, Command, HubAction, PtzAction, State, TimerAction};
+use crate::output;
+use crate::settings::Settings;
+
+const DEFAULT_DISCOVERY_TARGET: &str = "255.255.255.255";
+
+/// Runs the parsed command and writes its output to `out`.
+pub async fn run(cli: Cli, out: &mut impl Write) -> anyhow::Result<()> {
+    if let Command::Completions { shell } = cli.command {
+        clap_complete::generate(shell, &mut Cli::command(), "tapo", out);
+        return Ok(());
+    }
+
+    let settings = Settings::load(cli.config.as_deref())?;
+    let client = client(&cli, &settings)?;
+
+    let value = match cli.command {
+        Command::Discover { target, timeout } => {
+            let target = target
+                .or(settings.discovery_target)
+                .unwrap_or_else(|| DEFAULT_DISCOVERY_TARGET.to_string());
+            discover(client, target, timeout).await?
+        }
+        Command::Info { ip } => {
+            let device = client.connect(ip).await?;
+            device_info(&device)?
+        }
+        Command::On { ip } => {
+            let device = client.connect(ip).await?;
+            let on_off = device
+                .as_on_off()
+                .ok_or_else(|| unsupported(&device, "being turned on"))?;
+            on_off.on().await?;
+            summary(&device)
+        }
+        Command::Off { ip } => {
+            let device = client.connect(ip).await?;
+            let on_off = device
+                .as_on_off()
+                .ok_or_else(|| unsupported(&device, "being turned off"))?;
+            on_off.off().await?;
+            summary(&device)
+        }
+        Command::Brightness { ip, brightness } => {
+            let device = client.connect(ip).await?;
+            let dimmable = device
+                .as_dimmable()
+                .ok_or_else(|| unsupported(&device, "brightness"))?;
+            dimmable.set_brightness(brightness).await?;
+            summary(&device)
+        }
+        Command::Color {
+            ip,
+            name,
+            hue,
+            saturation,
+            temperature,
+        } => {
+            let device = client.connect(ip).await?;
+            let color_control = device
+                .as_color_control()
+                .ok_or_else(|| unsupported(&device, "colors"))?;
+
+            match (name, hue.zip(saturation), temperature) {
+                (Some(name), _, _) => {
+                    color_control
+                        .set_color(parse_preset::<Color>("color", &name)?)
+                        .await?
+                }
+                (_, Some((hue, saturation)), _) => {
+                    color_control.set_hue_saturation(hue, saturation).await?
+                }
+                (_, _, Some(temperature)) => {
+                    color_control.set_color_temperature(temperature).await?
+                }
+                _ => unreachable!("clap requires one of name, hue or temperature"),
+            }
+
+            summary(&device)
+        }
+        Command::Effect { ip, name } => {
+            let device = client.connect(ip).await?;
+            let DiscoveryResult::RgbicLightStrip { handler, .. } = &device else {
+                return Err(unsupported(&device, "lighting effects"));
+            };
+            handler
+                .set_lighting_effect(parse_preset::<LightingEffectPreset>(
+                    "lighting effect",
+                    &name,
+                )?)
+                .await?;
+            summary(&device)
+        }
+        Command::Energy { ip } => {
+            let device = client.connect(ip).await?;
+            let energy_meter = device
+                .as_energy_meter()
+                .ok_or_else(|| unsupported(&device, "energy monitoring"))?;
+
+            let mut energy = serde_json::to_value(energy_meter.get_energy_usage().await?)?;
+            energy["current_power"] =
+                serde_json::to_value(energy_meter.get_current_power().await?.current_power)?;
+            energy
+        }
+        Command::Timer { ip, action } => {
+            let device = client.connect(ip).await?;
+            timer(&device, action).await?
+        }
+        Command::Snapshot {
+            ip,
+            output,
+            camera_username,
+            camera_password,
+        } => {
+            let camera_username = camera_username.or(settings.camera_username).context(
+                "Missing the camera username, set --camera-username or TAPO_CAMERA_USERNAME",
+            )?;
+            let camera_password = camera_password.or(settings.camera_password).context(
+                "Missing the camera password, set --camera-password or TAPO_CAMERA_PASSWORD",
+            )?;
+
+            let device = client.connect(ip).await?;
+            let DiscoveryResult::CameraPtz { handler, .. } = &device else {
+                return Err(unsupported(&device, "snapshots"));
+            };
+
+            let snapshot = handler
+                .get_snapshot(&camera_username, &camera_password)
+                .await?;
+            std::fs::write(&output, &snapshot.data)
+                .with_context(|| format!("Failed to write {}", output.display()))?;
+
+            json!({
+                "path": output,
+                "content_type": snapshot.content_type,
+                "size": snapshot.data.len(),
+            })
+        }
+        Command::Ptz { ip, action } => {
+            let device = client.connect(ip).await?;
+            let DiscoveryResult::CameraPtz { handler, .. } = &device else {
+                return Err(unsupported(&device, "pan and tilt"));
+            };
+
+            match action {
+                PtzAction::Presets => serde_json::to_value(handler.get_presets().await?)?,
+                PtzAction::Move { pan, tilt } => {
+                    handler.pan_tilt(pan, tilt).await?;
+                    summary(&device)
+                }
+                PtzAction::Goto { id } => {
+                    handler.goto_preset(&id).await?;
+                    summary(&device)
+                }
+                PtzAction::Save { name } => {
+                    handler.save_preset(&name).await?;
+                    summary(&device)
+                }
+                PtzAction::Delete { id } => {
+                    handler.delete_preset(&id).await?;
+                    summary(&device)
+                }
+            }
+        }
+        Command::Hub {
+            action: HubAction::Children { ip },
+        } => {
+            let device = client.connect(ip).await?;
+            let DiscoveryResult::Hub { handler, .. } = &device else {
+                return Err(unsupported(&device, "child devices"));
+            };
+            serde_json::to_value(handler.get_child_device_list().await?)?
+        }
+        Command::Completions { .. } => unreachable!("handled above"),
+    };
+
+    writeln!(out, "{}", output::render(&value, cli.json))?;
+
+    Ok(())
+}
+
+fn client(cli: &Cli, settings: &Settings) -> anyhow::Result<ApiClient> {
+    let username = cli
+        .username
+        .clone()
+        .or_else(|| settings.username.clone())
+        .context("Missing the Tapo username, set --username or TAPO_USERNAME")?;
+    let password = cli
+        .password
+        .clone()
+        .or_else(|| settings.password.clone())
+        .context("Missing the Tapo password, set --password or TAPO_PASSWORD")?;
+
+    Ok(ApiClient::new(username, password))
+}
+
+async fn discover(client: ApiClient, target: String, timeout: u64) -> anyhow::Result<Value> {
+    let mut discovery = client.discover_devices(target, timeout).await?;
+    let mut devices = Vec::new();
+
+    while let Some(result) = discovery.next().await {
+        match result {
+            Ok(device) => devices.push(json!({
+                "ip": device.ip(),
+                "device_id": device.device_id(),
+                "nickname": device.nickname(),
+                "model": device.model(),
+                "device_type": device.device_type().as_str(),
+            })),
+            Err(err) => eprintln!("Error discovering device: {err}"),
+        }
+    }
+
+    Ok(Value::Array(devices))
+}
+
+fn device_info(device: &DiscoveryResult) -> anyhow::Result<Value> {
+    let device_info = match device {
+        DiscoveryResult::Light { device_info, .. } => serde_json::to_value(device_info),
+        DiscoveryResult::ColorLight { device_info, .. } => serde_json::to_value(device_info),
+        DiscoveryResult::RgbLightStrip { device_info, .. } => serde_json::to_value(device_info),
+        DiscoveryResult::RgbicLightStrip { device_info, .. } => serde_json::to_value(device_info),
+        DiscoveryResult::Plug { device_info, .. } => serde_json::to_value(device_info),
+        DiscoveryResult::PlugEnergyMonitoring { device_info, .. } => {
+            serde_json::to_value(device_info)
+        }
+        DiscoveryResult::PowerStrip { device_info, .. }
+        | DiscoveryResult::PowerStripEnergyMonitoring { device_info, .. } => {
+            serde_json::to_value(device_info)
+        }
+        DiscoveryResult::Hub { device_info, .. } => serde_json::to_value(device_info),
+        DiscoveryResult::CameraPtz { device_info, .. } => serde_json::to_value(device_info),
+        DiscoveryResult::Other { device_info, .. } => serde_json::to_value(device_info),
+    };
+
+    Ok(device_info?)
+}
+
+async fn timer(device: &DiscoveryResult, action: TimerAction) -> anyhow::Result<Value> {
+    macro_rules! timer {
+        ($handler:expr) => {
+            match action {
+                TimerAction::Get => serde_json::to_value($handler.get_timer().await?)?,
+                TimerAction::Set { delay, state } => {
+                    let state = match state {
+                        State::On => PowerState::On,
+                        State::Off => PowerState::Off,
+                    };
+                    serde_json::to_value(
+                        $handler
+                            .set_timer(Duration::from_secs(delay), state)
+                            .await?,
+                    )?
+                }
+                TimerAction::Clear => {
+                    $handler.clear_timer().await?;
+                    summary(device)
+                }
+            }
+        };
+    }
+
+    Ok(match device {
+        DiscoveryResult::Plug { handler, .. } => timer!(handler),
+        DiscoveryResult::PlugEnergyMonitoring { handler, .. } => timer!(handler),
+        _ => return Err(unsupported(device, "timers")),
+    })
+}
+
+/// The output of the commands that change the state of the device.
+fn summary(device: &DiscoveryResult) -> Value {
+    json!({
+        "ip": device.ip(),
+        "device_id": device.device_id(),
+        "nickname": device.nickname(),
+        "model": device.model(),
+    })
+}
+
+fn unsupported(device: &DiscoveryResult, feature: &str) -> anyhow::Error {
+    anyhow!(
+        "{} ({}) does not support {feature}",
+        device.nickname(),
+        device.model()
+    )
+}
+
+/// Parses a preset by its name, as it appears in the documentation, e.g. `WarmWhite`.
+fn parse_preset<T: DeserializeOwned>(kind: &str, name: &str) -> anyhow::Result<T> {
+    serde_json::from_value(Value::String(name.to_string()))
+        .map_err(|_| anyhow!("Unknown {kind} `{name}`"))
+}
@@ -0,0 +1,10 @@
+use clap::Parser as _;
+
+use tapo_cli::cli::Cli;
+use tapo_cli::commands;
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+    commands::run(cli, &mut std::io::stdout().lock()).await
+}
@@ -0,0 +1,61 @@
+use serde_json::Value;
+
+/// Renders the output of a command, either as pretty-printed JSON
+/// or as human readable `key: value` lines.
+pub fn render(value: &Value, json: bool) -> String {
+    if json {
+        // safe: serializing a `Value` never fails.
+        return serde_json::to_string_pretty(value).unwrap();
+    }
+
+    match value {
+        Value::Array(items) => items
+            .iter()
+            .map(|item| render(item, false))
+            .collect::<Vec<_>>()
+            .join("\n\n"),
+        Value::Object(fields) => {
+            let width = fields.keys().map(String::len).max().unwrap_or_default();
+
+            fields
+                .iter()
+                .map(|(key, value)| format!("{key:<width$}  {}", render_scalar(value)))
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+        value => render_scalar(value),
+    }
+}
+
+/// Renders nested objects and arrays as compact JSON, so that each field stays on one line.
+fn render_scalar(value: &Value) -> String {
+    match value {
+        Value::Null => "none".to_string(),
+        Value::String(value) => value.clone(),
+        value => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn render_aligns_fields() {
+        let value = json!([
+            { "model": "P110", "device_on": true, "nickname": null },
+            { "model": "L530", "color": { "hue": 120 } },
+        ]);
+
+        assert_eq!(
+            render(&value, false),
+            "device_on  true\n\
+             model      P110\n\
+             nickname   none\n\
+             \n\
+             color  {\"hue\":120}\n\
+             model  L530"
+        );
+    }
+}
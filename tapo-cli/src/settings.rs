@@ -0,0 +1,102 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Context as _;
+use serde::Deserialize;
+
+/// The optional configuration file, e.g.
+///
+/// ```toml
+/// username = "tapo-username@example.com"
+/// password = "tapo-password"
+/// discovery_target = "192.168.1.255"
+/// camera_username = "camera-username"
+/// camera_password = "camera-password"
+/// ```
+#[derive(Default, Deserialize)]
+pub struct Settings {
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub discovery_target: Option<String>,
+    pub camera_username: Option<String>,
+    pub camera_password: Option<String>,
+}
+
+impl std::fmt::Debug for Settings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Settings")
+            .field("username", &self.username.as_ref().map(|_| "[redacted]"))
+            .field("password", &self.password.as_ref().map(|_| "[redacted]"))
+            .field("discovery_target", &self.discovery_target)
+            .field(
+                "camera_username",
+                &self.camera_username.as_ref().map(|_| "[redacted]"),
+            )
+            .field(
+                "camera_password",
+                &self.camera_password.as_ref().map(|_| "[redacted]"),
+            )
+            .finish()
+    }
+}
+
+impl Settings {
+    /// Loads the configuration file at `path`, or at the default location if `path` is `None`.
+    ///
+    /// A missing file is only an error if `path` is given explicitly.
+    pub fn load(path: Option<&Path>) -> anyhow::Result<Self> {
+        let (path, required) = match path {
+            Some(path) => (path.to_path_buf(), true),
+            None => match default_path() {
+                Some(path) => (path, false),
+                None => return Ok(Self::default()),
+            },
+        };
+
+        config::Config::builder()
+            .add_source(config::File::from(path.as_path()).required(required))
+            .build()
+            .and_then(|config| config.try_deserialize())
+            .with_context(|| format!("Failed to load the configuration file {}", path.display()))
+    }
+}
+
+fn default_path() -> Option<PathBuf> {
+    let config_dir = std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+
+    Some(config_dir.join("tapo").join("config.toml"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn load_reads_the_given_file() {
+        let path = std::env::temp_dir().join(format!("tapo-cli-{}.toml", std::process::id()));
+        std::fs::write(
+            &path,
+            "username = \"user@example.com\"\npassword = \"secret\"\n",
+        )
+        .unwrap();
+
+        let settings = Settings::load(Some(&path));
+        std::fs::remove_file(&path).unwrap();
+        let settings = settings.unwrap();
+
+        assert_eq!(settings.username.as_deref(), Some("user@example.com"));
+        assert_eq!(settings.password.as_deref(), Some("secret"));
+        assert_eq!(settings.discovery_target, None);
+        assert!(!format!("{settings:?}").contains("secret"));
+    }
+
+    #[test]
+    fn load_fails_if_the_given_file_is_missing() {
+        let path = std::env::temp_dir().join("tapo-cli-missing.toml");
+
+        let err = Settings::load(Some(&path)).unwrap_err().to_string();
+        assert!(err.contains("tapo-cli-missing.toml"), "{err}");
+    }
+}
@@ -0,0 +1,120 @@
+use clap::Parser as _;
+use serde_json::{Value, json};
+use tapo::testing::{MockChild, MockDevice};
+use tapo_cli::cli::Cli;
+use tapo_cli::commands;
+
+async fn run(args: &[&str]) -> anyhow::Result<Value> {
+    let cli = Cli::try_parse_from(
+        [
+            "tapo",
+            "--username",
+            "tapo-username@example.com",
+            "--password",
+            "tapo-password",
+            "--json",
+        ]
+        .iter()
+        .chain(args),
+    )?;
+
+    let mut out = Vec::new();
+    commands::run(cli, &mut out).await?;
+
+    Ok(serde_json::from_slice(&out)?)
+}
+
+#[tokio::test]
+async fn info_prints_the_device_info() {
+    let server = MockDevice::new("P110").start().await.unwrap();
+
+    let info = run(&["info", &server.ip_address()]).await.unwrap();
+
+    assert_eq!(info["device_id"], server.device_id());
+    assert_eq!(info["model"], "P110");
+}
+
+#[tokio::test]
+async fn on_and_off_change_the_device_state() {
+    let server = MockDevice::new("L530").start().await.unwrap();
+
+    let output = run(&["off", &server.ip_address()]).await.unwrap();
+    assert_eq!(output["device_id"], server.device_id());
+    assert_eq!(server.device_info()["device_on"], false);
+
+    run(&["on", &server.ip_address()]).await.unwrap();
+    assert_eq!(server.device_info()["device_on"], true);
+}
+
+#[tokio::test]
+async fn energy_merges_the_current_power_into_the_energy_usage() {
+    let server = MockDevice::new("P110")
+        .with_response("get_current_power", json!({ "current_power": 35 }))
+        .with_response(
+            "get_energy_usage",
+            json!({
+                "local_time": "2026-03-09 12:00:00",
+                "month_energy": 1200,
+                "month_runtime": 6000,
+                "today_energy": 80,
+                "today_runtime": 180,
+            }),
+        )
+        .start()
+        .await
+        .unwrap();
+
+    let energy = run(&["energy", &server.ip_address()]).await.unwrap();
+
+    assert_eq!(energy["current_power"], 35);
+    assert_eq!(energy["today_energy"], 80);
+    assert_eq!(energy["month_energy"], 1200);
+}
+
+#[tokio::test]
+async fn hub_children_lists_the_child_devices() {
+    let server = MockDevice::new("H100")
+        .with_child(MockChild::new("T310"))
+        .with_child(MockChild::new("T100"))
+        .start()
+        .await
+        .unwrap();
+
+    let children = run(&["hub", "children", &server.ip_address()])
+        .await
+        .unwrap();
+    let models: Vec<_> = children
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|child| child["model"].as_str().unwrap())
+        .collect();
+
+    assert_eq!(models, ["T310", "T100"]);
+}
+
+#[tokio::test]
+async fn unsupported_commands_fail() {
+    let server = MockDevice::new("P100").start().await.unwrap();
+
+    let err = run(&["effect", &server.ip_address(), "Aurora"])
+        .await
+        .unwrap_err();
+
+    assert!(
+        err.to_string()
+            .contains("does not support lighting effects"),
+        "{err}"
+    );
+}
+
+#[tokio::test]
+async fn color_requires_a_single_target() {
+    for args in [
+        vec!["color", "127.0.0.1"],
+        vec!["color", "127.0.0.1", "--hue", "120"],
+        vec!["color", "127.0.0.1", "Crimson", "--temperature", "2700"],
+    ] {
+        assert!(run(&args).await.is_err(), "{args:?}");
+    }
+}
@@ -18,7 +18,7 @@ pub struct DevicesList {
 pub enum SetCapability {
     /// Set the device brightness (1-100).
     Brightness,
-    /// Set the device color using a preset name.
+    /// Set the device color using a preset name, a hex or RGB color, or a color temperature.
     Color,
     /// Turn the device on or off.
     OnOff,
@@ -101,10 +101,10 @@ pub enum SetCapabilityRequest {
         #[schemars(range(min = 1, max = 100))]
         value: u8,
     },
-    /// Set the device color using a preset name. Also turns the device on if it's off.
+    /// Set the device color. Also turns the device on if it's off.
     Color {
-        /// Preset color name.
-        value: Color,
+        /// Preset color name, or a free-form color.
+        value: ColorValue,
     },
     /// Turn the device on or off.
     OnOff {
@@ -113,6 +113,18 @@ pub enum SetCapabilityRequest {
     },
 }
 
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum ColorValue {
+    /// Preset color name.
+    Preset(Color),
+    /// `#rrggbb`, `#rgb` or `rgb(r, g, b)` color, or a color temperature in Kelvin such as `2700K`.
+    /// Colors only set the hue and saturation, the brightness is left unchanged.
+    /// Color temperatures are clamped to the range supported by the device.
+    /// CSS color names other than the preset names, e.g. `orange`, aren't supported.
+    Custom(String),
+}
+
 #[derive(Debug, Clone, Deserialize, JsonSchema)]
 #[serde(tag = "type")]
 pub enum GetCapabilityRequest {
@@ -1,9 +1,8 @@
 use tapo::{
-    ApiClient, DiscoveryResult, HubHandler, PowerStripEnergyMonitoringHandler, PowerStripHandler,
-    StreamExt as _, responses::ChildDeviceHubResult,
+    DeviceRegistry, DiscoveryResult, HubHandler, PowerStripEnergyMonitoringHandler,
+    PowerStripHandler, responses::ChildDeviceHubResult,
 };
 
-use crate::config::AppConfig;
 use crate::errors::TapoMcpError;
 use crate::models::CheckDeviceParams;
 
@@ -25,48 +24,59 @@ pub enum CheckedDevice {
 }
 
 pub async fn check_device(
-    config: &AppConfig,
+    registry: &DeviceRegistry,
     params: CheckDeviceParams,
 ) -> Result<CheckedDevice, TapoMcpError> {
-    let api_client = ApiClient::new(config.username.clone(), config.password.clone());
-    let mut discovery = api_client
-        .discover_devices(params.ip.clone(), config.discovery_timeout)
-        .await?;
+    let device = get_device(registry, &params).await?;
+    let found_id = device.device_id().to_string();
 
-    let mut last_seen: Option<(String, String)> = None;
-
-    while let Some(discovery_result) = discovery.next().await {
-        let device = discovery_result.map_err(TapoMcpError::InternalDiscovery)?;
-        let device_id = device.device_id().to_string();
-        let device_ip = device.ip().to_string();
+    if let Some(checked) = find_device(device, &params.id).await? {
+        return Ok(checked);
+    }
 
-        if device_ip == params.ip && device_id == params.id {
-            return Ok(CheckedDevice::Parent(device));
-        }
+    // The cached session can belong to a device that was at this IP address
+    // before a DHCP lease change, so reconnect before reporting a mismatch.
+    registry.remove(&found_id);
 
-        if device_ip == params.ip
-            && let Some(checked) = find_child(device, &params.id).await?
-        {
-            return Ok(checked);
-        }
+    let device = get_device(registry, &params).await?;
+    let found_id = device.device_id().to_string();
+    let found_ip = device.ip().to_string();
 
-        if device_ip == params.ip {
-            last_seen.get_or_insert((device_id, device_ip));
-        }
+    if let Some(checked) = find_device(device, &params.id).await? {
+        return Ok(checked);
     }
 
-    match last_seen {
-        Some((found_id, found_ip)) => Err(TapoMcpError::DeviceMismatch {
-            expected_id: params.id,
-            expected_ip: params.ip,
-            found_id,
-            found_ip,
-        }),
-        None => Err(TapoMcpError::DeviceNotFound {
-            id: params.id,
-            ip: params.ip,
+    Err(TapoMcpError::DeviceMismatch {
+        expected_id: params.id,
+        expected_ip: params.ip,
+        found_id,
+        found_ip,
+    })
+}
+
+async fn get_device(
+    registry: &DeviceRegistry,
+    params: &CheckDeviceParams,
+) -> Result<DiscoveryResult, TapoMcpError> {
+    match registry.get_by_ip(params.ip.clone()).await {
+        Ok(device) => Ok(device),
+        Err(tapo::Error::Http(_)) => Err(TapoMcpError::DeviceNotFound {
+            id: params.id.clone(),
+            ip: params.ip.clone(),
         }),
+        Err(err) => Err(err.into()),
+    }
+}
+
+async fn find_device(
+    device: DiscoveryResult,
+    target_id: &str,
+) -> Result<Option<CheckedDevice>, TapoMcpError> {
+    if device.device_id() == target_id {
+        return Ok(Some(CheckedDevice::Parent(device)));
     }
+
+    find_child(device, target_id).await
 }
 
 async fn find_child(
@@ -1,5 +1,5 @@
 use tapo::responses::ChildDeviceHubResult;
-use tapo::{ApiClient, DiscoveryResult, StreamExt as _};
+use tapo::{DeviceRegistry, DiscoveryResult, StreamExt as _};
 use tokio::task::JoinSet;
 
 use crate::config::AppConfig;
@@ -9,16 +9,18 @@ use crate::models::{
     UnsupportedDevice,
 };
 
-pub async fn get_devices(config: &AppConfig) -> Result<DevicesList, TapoMcpError> {
+pub async fn get_devices(
+    config: &AppConfig,
+    registry: &DeviceRegistry,
+) -> Result<DevicesList, TapoMcpError> {
     tracing::info!(
         discovery_target = config.discovery_target.as_str(),
         discovery_timeout = config.discovery_timeout,
         "Discovering devices",
     );
 
-    let api_client = ApiClient::new(config.username.clone(), config.password.clone());
-    let mut discovery = api_client
-        .discover_devices(config.discovery_target.clone(), config.discovery_timeout)
+    let mut discovery = registry
+        .discover(config.discovery_target.clone(), config.discovery_timeout)
         .await?;
 
     let mut errors = Vec::new();
@@ -1,5 +1,7 @@
 use rmcp::model::{ReadResourceResult, Resource, ResourceContents};
 
+use tapo::DeviceRegistry;
+
 use crate::config::AppConfig;
 use crate::errors::TapoMcpError;
 use crate::requests::get_devices;
@@ -14,8 +16,11 @@ pub fn build_devices_resource() -> Resource {
         .with_mime_type(JSON_MIME_TYPE)
 }
 
-pub async fn read_devices(config: &AppConfig) -> Result<ReadResourceResult, TapoMcpError> {
-    let devices = get_devices(config).await?;
+pub async fn read_devices(
+    config: &AppConfig,
+    registry: &DeviceRegistry,
+) -> Result<ReadResourceResult, TapoMcpError> {
+    let devices = get_devices(config, registry).await?;
     let text = serde_json::to_string_pretty(&devices)?;
     let contents =
         vec![ResourceContents::text(text, DEVICES_RESOURCE_URI).with_mime_type(JSON_MIME_TYPE)];
@@ -10,6 +10,7 @@ use rmcp::service::{RequestContext, RoleServer};
 use rmcp::transport::streamable_http_server::session::local::LocalSessionManager;
 use rmcp::transport::streamable_http_server::{StreamableHttpServerConfig, StreamableHttpService};
 use rmcp::{ErrorData as McpError, ServerHandler, tool, tool_handler, tool_router};
+use tapo::{ApiClient, DeviceRegistry};
 
 use crate::config::AppConfig;
 use crate::models::{
@@ -21,12 +22,13 @@ use crate::tools;
 #[derive(Clone)]
 pub struct TapoMcp {
     config: Arc<AppConfig>,
+    registry: Arc<DeviceRegistry>,
 }
 
 #[tool_router]
 impl TapoMcp {
-    pub(crate) fn new(config: Arc<AppConfig>) -> Self {
-        Self { config }
+    pub(crate) fn new(config: Arc<AppConfig>, registry: Arc<DeviceRegistry>) -> Self {
+        Self { config, registry }
     }
 
     #[tool(
@@ -42,7 +44,7 @@ impl TapoMcp {
         &self,
         Parameters(params): Parameters<CheckDeviceParams>,
     ) -> Result<CallToolResult, McpError> {
-        tools::check_device(&self.config, params).await
+        tools::check_device(&self.registry, params).await
     }
 
     #[tool(
@@ -58,7 +60,7 @@ impl TapoMcp {
         &self,
         Parameters(params): Parameters<GetDeviceStateParams>,
     ) -> Result<CallToolResult, McpError> {
-        tools::get_device_state(&self.config, params).await
+        tools::get_device_state(&self.registry, params).await
     }
 
     #[tool(
@@ -74,7 +76,7 @@ impl TapoMcp {
         &self,
         Parameters(params): Parameters<ControlDeviceParams>,
     ) -> Result<CallToolResult, McpError> {
-        tools::control_device(&self.config, params).await
+        tools::control_device(&self.registry, params).await
     }
 
     #[tool(
@@ -87,7 +89,7 @@ impl TapoMcp {
         )
     )]
     async fn list_devices(&self) -> Result<CallToolResult, McpError> {
-        tools::list_devices(&self.config).await
+        tools::list_devices(&self.config, &self.registry).await
     }
 
     #[tool(
@@ -103,7 +105,7 @@ impl TapoMcp {
         &self,
         Parameters(params): Parameters<TakeSnapshotParams>,
     ) -> Result<CallToolResult, McpError> {
-        tools::take_snapshot(&self.config, params).await
+        tools::take_snapshot(&self.config, &self.registry, params).await
     }
 }
 
@@ -139,7 +141,9 @@ impl ServerHandler for TapoMcp {
     ) -> Result<ReadResourceResponse, McpError> {
         match request.uri.as_str() {
             resources::DEVICES_RESOURCE_URI => {
-                Ok(resources::read_devices(&self.config).await?.into())
+                Ok(resources::read_devices(&self.config, &self.registry)
+                    .await?
+                    .into())
             }
             _ => Err(McpError::resource_not_found(
                 "Unknown resource URI",
@@ -160,8 +164,13 @@ pub fn new_service(
     if !app_config.allowed_hosts.is_empty() {
         server_config = server_config.with_allowed_hosts(app_config.allowed_hosts.clone());
     }
+    // Shared across MCP sessions, so that device logins are reused between tool calls.
+    let registry = Arc::new(DeviceRegistry::new(ApiClient::new(
+        app_config.username.clone(),
+        app_config.password.clone(),
+    )));
     StreamableHttpService::new(
-        move || Ok(TapoMcp::new(Arc::clone(&app_config))),
+        move || Ok(TapoMcp::new(Arc::clone(&app_config), Arc::clone(&registry))),
         session_manager,
         server_config,
     )
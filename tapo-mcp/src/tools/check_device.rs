@@ -1,14 +1,15 @@
 use rmcp::ErrorData as McpError;
 use rmcp::model::CallToolResult;
 
-use crate::config::AppConfig;
+use tapo::DeviceRegistry;
+
 use crate::models::CheckDeviceParams;
 use crate::requests;
 
 pub async fn check_device(
-    config: &AppConfig,
+    registry: &DeviceRegistry,
     params: CheckDeviceParams,
 ) -> Result<CallToolResult, McpError> {
-    requests::check_device(config, params).await?;
+    requests::check_device(registry, params).await?;
     Ok(CallToolResult::success(vec![]))
 }
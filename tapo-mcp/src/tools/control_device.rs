@@ -1,23 +1,22 @@
 use rmcp::ErrorData as McpError;
 use rmcp::model::CallToolResult;
-use tapo::DiscoveryResult;
-use tapo::requests::Color;
+use tapo::requests::{ColorLightSetDeviceInfoParams, RgbColor};
+use tapo::{DeviceRegistry, DiscoveryResult, OnOff};
 
-use crate::config::AppConfig;
 use crate::errors::TapoMcpError;
-use crate::models::{CheckDeviceParams, ControlDeviceParams, SetCapabilityRequest};
+use crate::models::{CheckDeviceParams, ColorValue, ControlDeviceParams, SetCapabilityRequest};
 use crate::requests;
 use crate::requests::CheckedDevice;
 
 pub async fn control_device(
-    config: &AppConfig,
+    registry: &DeviceRegistry,
     params: ControlDeviceParams,
 ) -> Result<CallToolResult, McpError> {
     let check_params = CheckDeviceParams {
         id: params.id.clone(),
         ip: params.ip.clone(),
     };
-    let checked = requests::check_device(config, check_params).await?;
+    let checked = requests::check_device(registry, check_params).await?;
 
     for capability in &params.capabilities {
         match capability {
@@ -41,115 +40,119 @@ async fn apply_brightness(
     checked: &CheckedDevice,
     brightness: u8,
 ) -> Result<(), TapoMcpError> {
-    match checked {
-        CheckedDevice::Parent(device) => match device {
-            DiscoveryResult::Light { handler, .. } => handler.set_brightness(brightness).await?,
-            DiscoveryResult::ColorLight { handler, .. } => {
-                handler.set_brightness(brightness).await?
-            }
-            DiscoveryResult::RgbLightStrip { handler, .. } => {
-                handler.set_brightness(brightness).await?
-            }
-            DiscoveryResult::RgbicLightStrip { handler, .. } => {
-                handler.set_brightness(brightness).await?
-            }
-            _ => {
-                return Err(TapoMcpError::WrongDeviceType {
-                    id: id.to_string(),
-                    capability: "Brightness".to_string(),
-                    expected: "a light device".to_string(),
-                });
-            }
-        },
-        CheckedDevice::PowerStripChild { .. }
-        | CheckedDevice::PowerStripEnergyMonitoringChild { .. }
-        | CheckedDevice::HubChild { .. } => {
-            return Err(TapoMcpError::WrongDeviceType {
-                id: id.to_string(),
-                capability: "Brightness".to_string(),
-                expected: "a light device".to_string(),
-            });
-        }
-    }
+    let CheckedDevice::Parent(device) = checked else {
+        return Err(wrong_device_type(id, "Brightness", "a light device"));
+    };
+
+    let handler = device
+        .as_dimmable()
+        .ok_or_else(|| wrong_device_type(id, "Brightness", "a light device"))?;
+    handler.set_brightness(brightness).await?;
 
     Ok(())
 }
 
-async fn apply_color(id: &str, checked: &CheckedDevice, color: Color) -> Result<(), TapoMcpError> {
-    match checked {
-        CheckedDevice::Parent(device) => match device {
-            DiscoveryResult::ColorLight { handler, .. } => handler.set_color(color).await?,
-            DiscoveryResult::RgbLightStrip { handler, .. } => handler.set_color(color).await?,
-            DiscoveryResult::RgbicLightStrip { handler, .. } => handler.set_color(color).await?,
-            _ => {
-                return Err(TapoMcpError::WrongDeviceType {
-                    id: id.to_string(),
-                    capability: "Color".to_string(),
-                    expected: "a color light device".to_string(),
-                });
-            }
-        },
-        CheckedDevice::PowerStripChild { .. }
-        | CheckedDevice::PowerStripEnergyMonitoringChild { .. }
-        | CheckedDevice::HubChild { .. } => {
-            return Err(TapoMcpError::WrongDeviceType {
-                id: id.to_string(),
-                capability: "Color".to_string(),
-                expected: "a color light device".to_string(),
-            });
+async fn apply_color(
+    id: &str,
+    checked: &CheckedDevice,
+    color: ColorValue,
+) -> Result<(), TapoMcpError> {
+    let CheckedDevice::Parent(device) = checked else {
+        return Err(wrong_device_type(id, "Color", "a color light device"));
+    };
+
+    let params = |params: ColorLightSetDeviceInfoParams| -> Result<_, tapo::Error> {
+        Ok(match &color {
+            ColorValue::Preset(color) => params.color(color.clone()),
+            ColorValue::Custom(value) => match parse_kelvin(value) {
+                Some(kelvin) => params.kelvin(kelvin),
+                // Only the hue and saturation are set, the brightness is left to the `Brightness` capability.
+                None => {
+                    let (hue, saturation, _) = value.parse::<RgbColor>()?.to_hsv();
+                    params.hue_saturation(hue, saturation.max(1))
+                }
+            },
+        })
+    };
+
+    match device {
+        DiscoveryResult::ColorLight { handler, .. } => params(handler.set())?.send(handler).await?,
+        DiscoveryResult::RgbLightStrip { handler, .. } => {
+            params(handler.set())?.send(handler).await?
         }
+        DiscoveryResult::RgbicLightStrip { handler, .. } => {
+            params(handler.set())?.send(handler).await?
+        }
+        _ => return Err(wrong_device_type(id, "Color", "a color light device")),
     }
 
     Ok(())
 }
 
+/// Parses a color temperature such as `2700K`.
+fn parse_kelvin(value: &str) -> Option<u16> {
+    value
+        .trim()
+        .strip_suffix(['K', 'k'])
+        .and_then(|kelvin| kelvin.trim_end().parse().ok())
+}
+
 async fn apply_on_off(id: &str, checked: &CheckedDevice, on: bool) -> Result<(), TapoMcpError> {
-    macro_rules! on_off {
-        ($handler:expr) => {
-            if on {
-                $handler.on().await?
-            } else {
-                $handler.off().await?
-            }
-        };
+    async fn on_off(handler: &dyn OnOff, on: bool) -> Result<(), tapo::Error> {
+        if on {
+            handler.on().await
+        } else {
+            handler.off().await
+        }
     }
 
     match checked {
-        CheckedDevice::Parent(device) => match device {
-            DiscoveryResult::Light { handler, .. } => on_off!(handler),
-            DiscoveryResult::ColorLight { handler, .. } => on_off!(handler),
-            DiscoveryResult::RgbLightStrip { handler, .. } => on_off!(handler),
-            DiscoveryResult::RgbicLightStrip { handler, .. } => on_off!(handler),
-            DiscoveryResult::Plug { handler, .. } => on_off!(handler),
-            DiscoveryResult::PlugEnergyMonitoring { handler, .. } => on_off!(handler),
-            DiscoveryResult::Other { .. }
-            | DiscoveryResult::PowerStrip { .. }
-            | DiscoveryResult::PowerStripEnergyMonitoring { .. }
-            | DiscoveryResult::Hub { .. }
-            | DiscoveryResult::CameraPtz { .. } => {
-                return Err(TapoMcpError::WrongDeviceType {
-                    id: id.to_string(),
-                    capability: "OnOff".to_string(),
-                    expected: "an on/off-capable device".to_string(),
-                });
-            }
-        },
+        CheckedDevice::Parent(device) => {
+            let handler = device
+                .as_on_off()
+                .ok_or_else(|| wrong_device_type(id, "OnOff", "an on/off-capable device"))?;
+            on_off(handler, on).await?;
+        }
         CheckedDevice::PowerStripChild { handler, child_id } => {
-            let plug = handler.plug_unchecked(child_id);
-            on_off!(plug);
+            on_off(&handler.plug_unchecked(child_id), on).await?;
         }
         CheckedDevice::PowerStripEnergyMonitoringChild { handler, child_id } => {
-            let plug = handler.plug_unchecked(child_id);
-            on_off!(plug);
+            on_off(&handler.plug_unchecked(child_id), on).await?;
         }
         CheckedDevice::HubChild { .. } => {
-            return Err(TapoMcpError::WrongDeviceType {
-                id: id.to_string(),
-                capability: "OnOff".to_string(),
-                expected: "a power strip child".to_string(),
-            });
+            return Err(wrong_device_type(id, "OnOff", "a power strip child"));
         }
     }
 
     Ok(())
 }
+
+fn wrong_device_type(id: &str, capability: &str, expected: &str) -> TapoMcpError {
+    TapoMcpError::WrongDeviceType {
+        id: id.to_string(),
+        capability: capability.to_string(),
+        expected: expected.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use tapo::requests::Color;
+
+    use super::*;
+
+    #[test]
+    fn color_values_are_presets_or_free_form() {
+        let color: ColorValue = serde_json::from_value(json!("WarmWhite")).unwrap();
+        assert!(matches!(color, ColorValue::Preset(Color::WarmWhite)));
+
+        let color: ColorValue = serde_json::from_value(json!("#ff8800")).unwrap();
+        assert!(matches!(color, ColorValue::Custom(value) if value == "#ff8800"));
+
+        assert_eq!(parse_kelvin("2700K"), Some(2700));
+        assert_eq!(parse_kelvin(" 6500 k"), Some(6500));
+        assert_eq!(parse_kelvin("#ff8800"), None);
+        assert_eq!(parse_kelvin("K"), None);
+    }
+}
@@ -1,23 +1,22 @@
 use rmcp::ErrorData as McpError;
 use rmcp::model::{CallToolResult, ContentBlock};
-use tapo::DiscoveryResult;
 use tapo::responses::ChildDeviceHubResult;
+use tapo::{DeviceRegistry, DiscoveryResult};
 
-use crate::config::AppConfig;
 use crate::errors::TapoMcpError;
 use crate::models::{CheckDeviceParams, GetCapabilityRequest, GetDeviceStateParams};
 use crate::requests;
 use crate::requests::CheckedDevice;
 
 pub async fn get_device_state(
-    config: &AppConfig,
+    registry: &DeviceRegistry,
     params: GetDeviceStateParams,
 ) -> Result<CallToolResult, McpError> {
     let check_params = CheckDeviceParams {
         id: params.id.clone(),
         ip: params.ip.clone(),
     };
-    let checked = requests::check_device(config, check_params).await?;
+    let checked = requests::check_device(registry, check_params).await?;
 
     let value = match params.capability {
         GetCapabilityRequest::DeviceInfo => get_device_info(checked).await?,
@@ -43,30 +42,37 @@ pub async fn get_device_state(
 
 async fn get_device_info(checked: CheckedDevice) -> Result<serde_json::Value, TapoMcpError> {
     match checked {
+        // Devices are cached by the registry, so the discovered device info can be stale.
         CheckedDevice::Parent(device) => match device {
-            DiscoveryResult::Light { device_info, .. } => Ok(serde_json::to_value(&*device_info)?),
-            DiscoveryResult::ColorLight { device_info, .. } => {
-                Ok(serde_json::to_value(&*device_info)?)
+            DiscoveryResult::Light { handler, .. } => {
+                Ok(serde_json::to_value(handler.get_device_info().await?)?)
             }
-            DiscoveryResult::RgbLightStrip { device_info, .. } => {
-                Ok(serde_json::to_value(&*device_info)?)
+            DiscoveryResult::ColorLight { handler, .. } => {
+                Ok(serde_json::to_value(handler.get_device_info().await?)?)
             }
-            DiscoveryResult::RgbicLightStrip { device_info, .. } => {
-                Ok(serde_json::to_value(&*device_info)?)
+            DiscoveryResult::RgbLightStrip { handler, .. } => {
+                Ok(serde_json::to_value(handler.get_device_info().await?)?)
             }
-            DiscoveryResult::Plug { device_info, .. } => Ok(serde_json::to_value(&*device_info)?),
-            DiscoveryResult::PlugEnergyMonitoring { device_info, .. } => {
-                Ok(serde_json::to_value(&*device_info)?)
+            DiscoveryResult::RgbicLightStrip { handler, .. } => {
+                Ok(serde_json::to_value(handler.get_device_info().await?)?)
             }
-            DiscoveryResult::PowerStrip { device_info, .. } => {
-                Ok(serde_json::to_value(&*device_info)?)
+            DiscoveryResult::Plug { handler, .. } => {
+                Ok(serde_json::to_value(handler.get_device_info().await?)?)
             }
-            DiscoveryResult::PowerStripEnergyMonitoring { device_info, .. } => {
-                Ok(serde_json::to_value(&*device_info)?)
+            DiscoveryResult::PlugEnergyMonitoring { handler, .. } => {
+                Ok(serde_json::to_value(handler.get_device_info().await?)?)
             }
-            DiscoveryResult::Hub { device_info, .. } => Ok(serde_json::to_value(&*device_info)?),
-            DiscoveryResult::CameraPtz { device_info, .. } => {
-                Ok(serde_json::to_value(&*device_info)?)
+            DiscoveryResult::PowerStrip { handler, .. } => {
+                Ok(serde_json::to_value(handler.get_device_info().await?)?)
+            }
+            DiscoveryResult::PowerStripEnergyMonitoring { handler, .. } => {
+                Ok(serde_json::to_value(handler.get_device_info().await?)?)
+            }
+            DiscoveryResult::Hub { handler, .. } => {
+                Ok(serde_json::to_value(handler.get_device_info().await?)?)
+            }
+            DiscoveryResult::CameraPtz { handler, .. } => {
+                Ok(serde_json::to_value(handler.get_device_info().await?)?)
             }
             DiscoveryResult::Other { device_info, .. } => Ok(serde_json::to_value(&*device_info)?),
         },
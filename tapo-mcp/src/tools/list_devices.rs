@@ -1,11 +1,16 @@
 use rmcp::ErrorData as McpError;
 use rmcp::model::{CallToolResult, ContentBlock};
 
+use tapo::DeviceRegistry;
+
 use crate::config::AppConfig;
 use crate::requests::get_devices;
 
-pub async fn list_devices(config: &AppConfig) -> Result<CallToolResult, McpError> {
-    let devices = get_devices(config).await?;
+pub async fn list_devices(
+    config: &AppConfig,
+    registry: &DeviceRegistry,
+) -> Result<CallToolResult, McpError> {
+    let devices = get_devices(config, registry).await?;
     let content = vec![ContentBlock::json(devices)?];
     Ok(CallToolResult::success(content))
 }
@@ -2,7 +2,7 @@ use base64::Engine;
 use base64::engine::general_purpose::STANDARD;
 use rmcp::ErrorData as McpError;
 use rmcp::model::{CallToolResult, ContentBlock};
-use tapo::DiscoveryResult;
+use tapo::{DeviceRegistry, DiscoveryResult};
 
 use crate::config::AppConfig;
 use crate::errors::TapoMcpError;
@@ -12,6 +12,7 @@ use crate::requests::CheckedDevice;
 
 pub async fn take_snapshot(
     config: &AppConfig,
+    registry: &DeviceRegistry,
     params: TakeSnapshotParams,
 ) -> Result<CallToolResult, McpError> {
     let camera_username = config
@@ -27,7 +28,7 @@ pub async fn take_snapshot(
         id: params.id.clone(),
         ip: params.ip.clone(),
     };
-    let checked = requests::check_device(config, check_params).await?;
+    let checked = requests::check_device(registry, check_params).await?;
 
     let snapshot = match checked {
         CheckedDevice::Parent(DiscoveryResult::CameraPtz { handler, .. }) => handler
@@ -0,0 +1,222 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::Serialize;
+use serde_json::Value;
+use tapo::responses::{ChildDeviceHubResult, TemperatureUnit};
+use tapo::{ApiClient, DeviceRegistry, DiscoveryResult, StreamExt as _};
+use tokio::sync::RwLock;
+use tokio::task::JoinSet;
+
+use crate::config::AppConfig;
+use crate::metrics::{DeviceLabels, Metric, Metrics};
+
+/// Refreshes `rendered` with the metrics of the discovered devices every `refresh_interval` seconds.
+///
+/// The device sessions are kept in a [`DeviceRegistry`] across refreshes.
+pub async fn run(config: AppConfig, rendered: Arc<RwLock<String>>) {
+    let registry = DeviceRegistry::new(ApiClient::new(&config.username, &config.password));
+
+    let mut interval = tokio::time::interval(Duration::from_secs(config.refresh_interval));
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        interval.tick().await;
+
+        let metrics = collect(&config, &registry).await;
+        registry.evict_idle();
+        *rendered.write().await = metrics.render();
+    }
+}
+
+/// Discovers the devices and polls their metrics.
+///
+/// Devices that fail to be polled are logged and left out, so that one unreachable device
+/// doesn't fail the whole refresh.
+///
+/// # Arguments
+///
+/// * `config` - the discovery settings
+/// * `registry` - the registry the discovered devices are added to
+pub async fn collect(config: &AppConfig, registry: &DeviceRegistry) -> Metrics {
+    tracing::info!(
+        discovery_target = config.discovery_target.as_str(),
+        discovery_timeout = config.discovery_timeout,
+        "Discovering devices",
+    );
+
+    let mut discovery = match registry
+        .discover(config.discovery_target.clone(), config.discovery_timeout)
+        .await
+    {
+        Ok(discovery) => discovery,
+        Err(err) => {
+            tracing::warn!(%err, "Failed to start device discovery");
+            return Metrics::default();
+        }
+    };
+
+    let mut joinset: JoinSet<Metrics> = JoinSet::new();
+    let mut device_ids = HashMap::new();
+    while let Some(result) = discovery.next().await {
+        match result {
+            Ok(device) => {
+                let device_id = device.device_id().to_string();
+                let task = joinset.spawn(collect_device(device));
+                device_ids.insert(task.id(), device_id);
+            }
+            Err(err) => tracing::warn!(%err, "Error discovering device"),
+        }
+    }
+
+    let mut metrics = Metrics::default();
+    while let Some(joined) = joinset.join_next().await {
+        match joined {
+            Ok(device_metrics) => metrics.extend(device_metrics),
+            Err(err) => {
+                let device_id = device_ids.get(&err.id()).map(String::as_str);
+                tracing::error!(%err, device_id, "Failed to collect the device metrics, skipping it")
+            }
+        }
+    }
+
+    tracing::info!("Metrics refreshed");
+
+    metrics
+}
+
+async fn collect_device(device: DiscoveryResult) -> Metrics {
+    let mut metrics = Metrics::default();
+    let labels = DeviceLabels::new(device.device_id(), device.nickname(), device.model());
+
+    if let Some(device_info) = device_info(&device) {
+        add_common(&mut metrics, &labels, &device_info);
+    }
+
+    if let Some(energy_meter) = device.as_energy_meter() {
+        match energy_meter.get_current_power().await {
+            Ok(current_power) => metrics.add(
+                Metric::CurrentPower,
+                &labels,
+                current_power.current_power as f64,
+            ),
+            Err(err) => {
+                tracing::warn!(%err, device_id = labels.device_id, "Failed to get the current power")
+            }
+        }
+
+        match energy_meter.get_energy_usage().await {
+            Ok(energy_usage) => metrics.add(
+                Metric::TodayEnergy,
+                &labels,
+                energy_usage.today_energy as f64,
+            ),
+            Err(err) => {
+                tracing::warn!(%err, device_id = labels.device_id, "Failed to get the energy usage")
+            }
+        }
+    }
+
+    let children = match &device {
+        DiscoveryResult::PowerStrip { handler, .. } => handler
+            .get_child_device_list()
+            .await
+            .map(|children| children.iter().map(child_info).collect()),
+        DiscoveryResult::PowerStripEnergyMonitoring { handler, .. } => handler
+            .get_child_device_list()
+            .await
+            .map(|children| children.iter().map(child_info).collect()),
+        DiscoveryResult::Hub { handler, .. } => {
+            handler.get_child_device_list().await.map(|children| {
+                children
+                    .iter()
+                    .map(|child| {
+                        add_hub_child(&mut metrics, child);
+                        child_info(child)
+                    })
+                    .collect()
+            })
+        }
+        _ => Ok(Vec::new()),
+    };
+
+    match children {
+        Ok(children) => {
+            for (labels, child_info) in children.into_iter().flatten() {
+                add_common(&mut metrics, &labels, &child_info);
+            }
+        }
+        Err(err) => {
+            tracing::warn!(%err, device_id = labels.device_id, "Failed to get the child device list")
+        }
+    }
+
+    metrics
+}
+
+fn device_info(device: &DiscoveryResult) -> Option<Value> {
+    let device_info = match device {
+        DiscoveryResult::Light { device_info, .. } => serde_json::to_value(device_info),
+        DiscoveryResult::ColorLight { device_info, .. } => serde_json::to_value(device_info),
+        DiscoveryResult::RgbLightStrip { device_info, .. } => serde_json::to_value(device_info),
+        DiscoveryResult::RgbicLightStrip { device_info, .. } => serde_json::to_value(device_info),
+        DiscoveryResult::Plug { device_info, .. } => serde_json::to_value(device_info),
+        DiscoveryResult::PlugEnergyMonitoring { device_info, .. } => {
+            serde_json::to_value(device_info)
+        }
+        DiscoveryResult::PowerStrip { device_info, .. }
+        | DiscoveryResult::PowerStripEnergyMonitoring { device_info, .. } => {
+            serde_json::to_value(device_info)
+        }
+        DiscoveryResult::Hub { device_info, .. } => serde_json::to_value(device_info),
+        DiscoveryResult::CameraPtz { device_info, .. } => serde_json::to_value(device_info),
+        DiscoveryResult::Other { device_info, .. } => serde_json::to_value(device_info),
+    };
+
+    device_info.ok()
+}
+
+/// Returns the labels and the serialized device info of a power strip or hub child device.
+fn child_info<C: Serialize>(child: &C) -> Option<(DeviceLabels, Value)> {
+    let child_info = serde_json::to_value(child).ok()?;
+    let labels = DeviceLabels::new(
+        child_info["device_id"].as_str()?,
+        child_info["nickname"].as_str().unwrap_or_default(),
+        child_info["model"].as_str().unwrap_or_default(),
+    );
+
+    Some((labels, child_info))
+}
+
+/// Adds the on/off state, the signal strength and the low battery flag,
+/// for the devices that report them.
+fn add_common(metrics: &mut Metrics, labels: &DeviceLabels, device_info: &Value) {
+    if let Some(device_on) = device_info["device_on"].as_bool() {
+        metrics.add(Metric::DeviceOn, labels, device_on.into());
+    }
+    if let Some(rssi) = device_info["rssi"].as_f64() {
+        metrics.add(Metric::Rssi, labels, rssi);
+    }
+    if let Some(signal_level) = device_info["signal_level"].as_f64() {
+        metrics.add(Metric::SignalLevel, labels, signal_level);
+    }
+    if let Some(at_low_battery) = device_info["at_low_battery"].as_bool() {
+        metrics.add(Metric::LowBattery, labels, at_low_battery.into());
+    }
+}
+
+fn add_hub_child(metrics: &mut Metrics, child: &ChildDeviceHubResult) {
+    if let ChildDeviceHubResult::T31X(sensor) = child {
+        let labels = DeviceLabels::new(&sensor.device_id, &sensor.nickname, &sensor.model);
+        let temperature = sensor
+            .temperature_unit
+            .convert(sensor.current_temperature, &TemperatureUnit::Celsius);
+
+        // Rounded, so that e.g. 22.3 isn't exposed as 22.299999237060547.
+        let temperature = (f64::from(temperature) * 100.0).round() / 100.0;
+
+        metrics.add(Metric::Temperature, &labels, temperature);
+        metrics.add(Metric::Humidity, &labels, sensor.current_humidity as f64);
+    }
+}
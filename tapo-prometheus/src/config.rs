@@ -0,0 +1,189 @@
+use serde::Deserialize;
+
+const ENV_PREFIX: &str = "TAPO_PROMETHEUS";
+const DEFAULT_HTTP_ADDR: &str = "0.0.0.0:9757";
+const DEFAULT_DISCOVERY_TIMEOUT: u64 = 5;
+const DEFAULT_REFRESH_INTERVAL: u64 = 60;
+
+#[derive(Clone, Deserialize)]
+pub struct AppConfig {
+    #[serde(default = "AppConfig::default_http_addr")]
+    pub http_addr: String,
+    pub username: String,
+    pub password: String,
+    pub discovery_target: String,
+    #[serde(default = "AppConfig::default_discovery_timeout")]
+    pub discovery_timeout: u64,
+    /// Seconds between two refreshes of the metrics.
+    /// Each refresh discovers the devices and polls them.
+    #[serde(default = "AppConfig::default_refresh_interval")]
+    pub refresh_interval: u64,
+}
+
+impl std::fmt::Debug for AppConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AppConfig")
+            .field("http_addr", &self.http_addr)
+            .field("username", &"[redacted]")
+            .field("password", &"[redacted]")
+            .field("discovery_target", &self.discovery_target)
+            .field("discovery_timeout", &self.discovery_timeout)
+            .field("refresh_interval", &self.refresh_interval)
+            .finish()
+    }
+}
+
+impl AppConfig {
+    fn default_http_addr() -> String {
+        DEFAULT_HTTP_ADDR.to_string()
+    }
+
+    fn default_discovery_timeout() -> u64 {
+        DEFAULT_DISCOVERY_TIMEOUT
+    }
+
+    fn default_refresh_interval() -> u64 {
+        DEFAULT_REFRESH_INTERVAL
+    }
+
+    pub fn from_env() -> Result<Self, config::ConfigError> {
+        let required_envs = [
+            format!("{ENV_PREFIX}_USERNAME"),
+            format!("{ENV_PREFIX}_PASSWORD"),
+            format!("{ENV_PREFIX}_DISCOVERY_TARGET"),
+        ];
+
+        let missing: Vec<String> = required_envs
+            .iter()
+            .filter(|name| {
+                std::env::var(name)
+                    .map(|v| v.trim().is_empty())
+                    .unwrap_or(true)
+            })
+            .cloned()
+            .collect();
+
+        if !missing.is_empty() {
+            return Err(config::ConfigError::Message(format!(
+                "Missing or empty required environment variable(s): {}",
+                missing.join(", ")
+            )));
+        }
+
+        let config: Self = config::Config::builder()
+            .add_source(config::Environment::default().prefix(ENV_PREFIX))
+            .build()?
+            .try_deserialize()?;
+
+        if config.refresh_interval == 0 {
+            return Err(config::ConfigError::Message(format!(
+                "{ENV_PREFIX}_REFRESH_INTERVAL must be at least 1 second"
+            )));
+        }
+
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    // Env var tests must run serially since they mutate process-wide state.
+    static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+    /// # Safety
+    ///
+    /// Callers must hold `ENV_LOCK` to ensure no concurrent env mutation.
+    unsafe fn clear_tapo_env() {
+        for key in [
+            "TAPO_PROMETHEUS_USERNAME",
+            "TAPO_PROMETHEUS_PASSWORD",
+            "TAPO_PROMETHEUS_DISCOVERY_TARGET",
+            "TAPO_PROMETHEUS_HTTP_ADDR",
+            "TAPO_PROMETHEUS_DISCOVERY_TIMEOUT",
+            "TAPO_PROMETHEUS_REFRESH_INTERVAL",
+        ] {
+            unsafe { std::env::remove_var(key) };
+        }
+    }
+
+    /// # Safety
+    ///
+    /// Callers must hold `ENV_LOCK` to ensure no concurrent env mutation.
+    unsafe fn set_required_env() {
+        unsafe {
+            std::env::set_var("TAPO_PROMETHEUS_USERNAME", "user@example.com");
+            std::env::set_var("TAPO_PROMETHEUS_PASSWORD", "secret");
+            std::env::set_var("TAPO_PROMETHEUS_DISCOVERY_TARGET", "192.168.1.255");
+        }
+    }
+
+    #[test]
+    fn from_env_missing_required_vars() {
+        let _lock = ENV_LOCK.lock().unwrap();
+        unsafe {
+            clear_tapo_env();
+            std::env::set_var("TAPO_PROMETHEUS_USERNAME", "user@example.com");
+        }
+
+        let err = AppConfig::from_env().unwrap_err().to_string();
+        assert!(
+            err.contains("TAPO_PROMETHEUS_PASSWORD"),
+            "error should mention PASSWORD: {err}"
+        );
+        assert!(
+            err.contains("TAPO_PROMETHEUS_DISCOVERY_TARGET"),
+            "error should mention DISCOVERY_TARGET: {err}"
+        );
+        assert!(
+            !err.contains("TAPO_PROMETHEUS_USERNAME"),
+            "should not mention present vars: {err}"
+        );
+    }
+
+    #[test]
+    fn from_env_applies_defaults() {
+        let _lock = ENV_LOCK.lock().unwrap();
+        unsafe {
+            clear_tapo_env();
+            set_required_env();
+        }
+
+        let config = AppConfig::from_env().unwrap();
+        assert_eq!(config.http_addr, DEFAULT_HTTP_ADDR);
+        assert_eq!(config.discovery_timeout, DEFAULT_DISCOVERY_TIMEOUT);
+        assert_eq!(config.refresh_interval, DEFAULT_REFRESH_INTERVAL);
+    }
+
+    #[test]
+    fn zero_refresh_interval_is_rejected() {
+        let _lock = ENV_LOCK.lock().unwrap();
+        unsafe {
+            clear_tapo_env();
+            set_required_env();
+            std::env::set_var("TAPO_PROMETHEUS_REFRESH_INTERVAL", "0");
+        }
+
+        let err = AppConfig::from_env().unwrap_err().to_string();
+        assert!(err.contains("REFRESH_INTERVAL"), "{err}");
+    }
+
+    #[test]
+    fn debug_redacts_credentials() {
+        let config = AppConfig {
+            http_addr: DEFAULT_HTTP_ADDR.to_string(),
+            username: "user@example.com".to_string(),
+            password: "super-secret".to_string(),
+            discovery_target: "192.168.1.255".to_string(),
+            discovery_timeout: 5,
+            refresh_interval: 60,
+        };
+
+        let debug = format!("{config:?}");
+        assert!(debug.contains("[redacted]"));
+        assert!(!debug.contains("user@example.com"));
+        assert!(!debug.contains("super-secret"));
+    }
+}
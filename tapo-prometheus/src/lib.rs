@@ -0,0 +1,28 @@
+pub mod collector;
+pub mod config;
+pub mod metrics;
+
+use std::sync::Arc;
+
+use axum::Router;
+use axum::extract::State;
+use axum::http::header;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use tokio::sync::RwLock;
+
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Serves the last rendered metrics on `/metrics`.
+pub fn router(rendered: Arc<RwLock<String>>) -> Router {
+    Router::new()
+        .route("/metrics", get(metrics))
+        .with_state(rendered)
+}
+
+async fn metrics(State(rendered): State<Arc<RwLock<String>>>) -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, CONTENT_TYPE)],
+        rendered.read().await.clone(),
+    )
+}
@@ -0,0 +1,59 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use tokio::sync::RwLock;
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+use tapo_prometheus::collector;
+use tapo_prometheus::config::AppConfig;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let env_filter = tracing_subscriber::EnvFilter::try_from_default_env()
+        // safe: hardcoded filter directive always parses successfully.
+        .unwrap_or_else(|_| "tapo_prometheus=info".parse().unwrap());
+
+    tracing_subscriber::registry()
+        .with(env_filter)
+        .with(tracing_subscriber::fmt::layer())
+        .try_init()?;
+
+    let app_config = AppConfig::from_env()?;
+    let listener = tokio::net::TcpListener::bind(&app_config.http_addr).await?;
+    tracing::info!(addr = %app_config.http_addr, "Tapo Prometheus exporter listening");
+
+    let rendered = Arc::new(RwLock::new(String::new()));
+    tokio::spawn(collector::run(app_config, rendered.clone()));
+
+    axum::serve(listener, tapo_prometheus::router(rendered))
+        .with_graceful_shutdown(shutdown_signal())
+        .await?;
+
+    Ok(())
+}
+
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("Failed to install Ctrl+C handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("Failed to install SIGTERM handler")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+
+    tracing::info!("Shutdown signal received");
+}
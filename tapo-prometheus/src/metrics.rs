@@ -0,0 +1,159 @@
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+
+/// The gauges exposed on `/metrics`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Metric {
+    DeviceOn,
+    Rssi,
+    SignalLevel,
+    CurrentPower,
+    TodayEnergy,
+    LowBattery,
+    Temperature,
+    Humidity,
+}
+
+impl Metric {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Metric::DeviceOn => "tapo_device_on",
+            Metric::Rssi => "tapo_rssi_dbm",
+            Metric::SignalLevel => "tapo_signal_level",
+            Metric::CurrentPower => "tapo_current_power_watts",
+            Metric::TodayEnergy => "tapo_today_energy_watt_hours",
+            Metric::LowBattery => "tapo_battery_low",
+            Metric::Temperature => "tapo_temperature_celsius",
+            Metric::Humidity => "tapo_humidity_percent",
+        }
+    }
+
+    fn help(&self) -> &'static str {
+        match self {
+            Metric::DeviceOn => "Whether the device is on (1) or off (0).",
+            Metric::Rssi => "Received signal strength indicator of the device.",
+            Metric::SignalLevel => "Signal level of the device, from 0 to 3.",
+            Metric::CurrentPower => "Current power draw of the device.",
+            Metric::TodayEnergy => "Energy used by the device today.",
+            Metric::LowBattery => {
+                "Whether the battery of the hub child device is low (1) or not (0)."
+            }
+            Metric::Temperature => "Current temperature measured by the sensor.",
+            Metric::Humidity => "Current relative humidity measured by the sensor.",
+        }
+    }
+}
+
+/// The labels every sample is labelled with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeviceLabels {
+    pub device_id: String,
+    pub nickname: String,
+    pub model: String,
+}
+
+impl DeviceLabels {
+    pub fn new(
+        device_id: impl Into<String>,
+        nickname: impl Into<String>,
+        model: impl Into<String>,
+    ) -> Self {
+        Self {
+            device_id: device_id.into(),
+            nickname: nickname.into(),
+            model: model.into(),
+        }
+    }
+}
+
+/// A set of samples, rendered in the Prometheus text exposition format.
+#[derive(Debug, Clone, Default)]
+pub struct Metrics {
+    samples: BTreeMap<Metric, Vec<(DeviceLabels, f64)>>,
+}
+
+impl Metrics {
+    pub fn add(&mut self, metric: Metric, labels: &DeviceLabels, value: f64) {
+        self.samples
+            .entry(metric)
+            .or_default()
+            .push((labels.clone(), value));
+    }
+
+    pub fn extend(&mut self, other: Metrics) {
+        for (metric, samples) in other.samples {
+            self.samples.entry(metric).or_default().extend(samples);
+        }
+    }
+
+    /// Returns the value of the sample of `metric` for `device_id`, if any.
+    pub fn get(&self, metric: Metric, device_id: &str) -> Option<f64> {
+        self.samples
+            .get(&metric)?
+            .iter()
+            .find(|(labels, _)| labels.device_id == device_id)
+            .map(|(_, value)| *value)
+    }
+
+    pub fn render(&self) -> String {
+        let mut output = String::new();
+
+        for (metric, samples) in &self.samples {
+            let name = metric.name();
+
+            // safe: writing to a String never fails.
+            writeln!(output, "# HELP {name} {}", metric.help()).unwrap();
+            writeln!(output, "# TYPE {name} gauge").unwrap();
+
+            for (labels, value) in samples {
+                writeln!(
+                    output,
+                    "{name}{{device_id=\"{}\",nickname=\"{}\",model=\"{}\"}} {value}",
+                    escape_label_value(&labels.device_id),
+                    escape_label_value(&labels.nickname),
+                    escape_label_value(&labels.model),
+                )
+                .unwrap();
+            }
+        }
+
+        output
+    }
+}
+
+/// Escapes backslashes, double quotes and line feeds, as required by the text exposition format.
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_groups_samples_by_metric() {
+        let plug = DeviceLabels::new("802D", "Desk \"left\" plug", "P110");
+        let sensor = DeviceLabels::new("AB12", "Kitchen\\Sensor", "T310");
+
+        let mut metrics = Metrics::default();
+        metrics.add(Metric::DeviceOn, &plug, 1.0);
+        metrics.add(Metric::Temperature, &sensor, 22.5);
+        metrics.add(Metric::CurrentPower, &plug, 35.0);
+
+        assert_eq!(
+            metrics.render(),
+            "# HELP tapo_device_on Whether the device is on (1) or off (0).\n\
+             # TYPE tapo_device_on gauge\n\
+             tapo_device_on{device_id=\"802D\",nickname=\"Desk \\\"left\\\" plug\",model=\"P110\"} 1\n\
+             # HELP tapo_current_power_watts Current power draw of the device.\n\
+             # TYPE tapo_current_power_watts gauge\n\
+             tapo_current_power_watts{device_id=\"802D\",nickname=\"Desk \\\"left\\\" plug\",model=\"P110\"} 35\n\
+             # HELP tapo_temperature_celsius Current temperature measured by the sensor.\n\
+             # TYPE tapo_temperature_celsius gauge\n\
+             tapo_temperature_celsius{device_id=\"AB12\",nickname=\"Kitchen\\\\Sensor\",model=\"T310\"} 22.5\n"
+        );
+    }
+}
@@ -0,0 +1,124 @@
+use std::net::{IpAddr, Ipv4Addr};
+use std::sync::Arc;
+
+use axum::body::Body;
+use axum::extract::Request;
+use axum::http::StatusCode;
+use serde_json::json;
+use tapo::testing::{MockChild, MockDevice};
+use tapo::{ApiClient, DeviceRegistry};
+use tapo_prometheus::collector;
+use tapo_prometheus::config::AppConfig;
+use tapo_prometheus::metrics::Metric;
+use tokio::sync::RwLock;
+use tower::ServiceExt;
+
+fn test_config(discovery_target: IpAddr) -> AppConfig {
+    AppConfig {
+        http_addr: "127.0.0.1:0".to_string(),
+        username: "tapo-username@example.com".to_string(),
+        password: "tapo-password".to_string(),
+        discovery_target: discovery_target.to_string(),
+        discovery_timeout: 2,
+        refresh_interval: 60,
+    }
+}
+
+fn registry() -> DeviceRegistry {
+    DeviceRegistry::new(ApiClient::new("tapo-username@example.com", "tapo-password"))
+}
+
+#[tokio::test]
+async fn energy_monitoring_plug_metrics_are_collected() {
+    let ip = IpAddr::V4(Ipv4Addr::new(127, 0, 0, 31));
+    let server = MockDevice::new("P110")
+        .with_ip(ip)
+        .with_discovery()
+        .with_device_info(json!({ "device_on": true, "rssi": -48, "signal_level": 3 }))
+        .with_response("get_current_power", json!({ "current_power": 35 }))
+        .with_response(
+            "get_energy_usage",
+            json!({
+                "local_time": "2026-03-09 12:00:00",
+                "month_energy": 1200,
+                "month_runtime": 6000,
+                "today_energy": 80,
+                "today_runtime": 180,
+            }),
+        )
+        .start()
+        .await
+        .unwrap();
+
+    let registry = registry();
+    collector::collect(&test_config(ip), &registry).await;
+    let metrics = collector::collect(&test_config(ip), &registry).await;
+    let device_id = server.device_id();
+
+    assert_eq!(registry.len(), 1);
+
+    assert_eq!(metrics.get(Metric::DeviceOn, device_id), Some(1.0));
+    assert_eq!(metrics.get(Metric::Rssi, device_id), Some(-48.0));
+    assert_eq!(metrics.get(Metric::SignalLevel, device_id), Some(3.0));
+    assert_eq!(metrics.get(Metric::CurrentPower, device_id), Some(35.0));
+    assert_eq!(metrics.get(Metric::TodayEnergy, device_id), Some(80.0));
+}
+
+#[tokio::test]
+async fn hub_children_metrics_are_collected() {
+    let ip = IpAddr::V4(Ipv4Addr::new(127, 0, 0, 32));
+    let server = MockDevice::new("H100")
+        .with_ip(ip)
+        .with_discovery()
+        .with_child(MockChild::new("T310").with_device_info(json!({
+            "current_temp": 72.5,
+            "temp_unit": "fahrenheit",
+            "current_humidity": 48,
+            "at_low_battery": true,
+        })))
+        .with_child(MockChild::new("T100"))
+        .start()
+        .await
+        .unwrap();
+
+    let metrics = collector::collect(&test_config(ip), &registry()).await;
+    let children = server.child_device_list();
+    let sensor_id = children[0]["device_id"].as_str().unwrap();
+    let motion_id = children[1]["device_id"].as_str().unwrap();
+
+    assert_eq!(metrics.get(Metric::Temperature, sensor_id), Some(22.5));
+    assert_eq!(metrics.get(Metric::Humidity, sensor_id), Some(48.0));
+    assert_eq!(metrics.get(Metric::LowBattery, sensor_id), Some(1.0));
+    assert_eq!(metrics.get(Metric::LowBattery, motion_id), Some(0.0));
+    assert_eq!(metrics.get(Metric::Temperature, motion_id), None);
+    assert!(metrics.get(Metric::Rssi, server.device_id()).is_some());
+
+    let rendered = metrics.render();
+    assert!(rendered.contains(&format!(
+        "tapo_temperature_celsius{{device_id=\"{sensor_id}\",nickname=\"T310 Mock 1\",model=\"T310\"}} 22.5"
+    )));
+}
+
+#[tokio::test]
+async fn metrics_endpoint_serves_the_rendered_metrics() {
+    let rendered = Arc::new(RwLock::new("tapo_device_on{} 1\n".to_string()));
+    let app = tapo_prometheus::router(rendered);
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/metrics")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(
+        response.headers()["content-type"]
+            .to_str()
+            .unwrap()
+            .starts_with("text/plain; version=0.0.4")
+    );
+}
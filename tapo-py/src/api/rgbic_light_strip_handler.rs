@@ -6,7 +6,9 @@ use tapo::RgbicLightStripHandler;
 use tapo::requests::{
     Color, LightingEffect, LightingEffectPreset, SegmentEffect, SegmentEffectPreset,
 };
-use tapo::responses::{DeviceInfoRgbicLightStripResult, DeviceUsageEnergyMonitoringResult};
+use tapo::responses::{
+    DeviceInfoRgbicLightStripResult, DeviceUsageEnergyMonitoringResult, LightingEffectInfo,
+};
 
 use crate::call_handler_method;
 use crate::requests::{PyColorLightSetDeviceInfoParams, PyLightingEffect, PySegmentEffect};
@@ -72,6 +74,14 @@ impl PyRgbicLightStripHandler {
         )
     }
 
+    pub async fn get_lighting_effect(&self) -> PyResult<Option<LightingEffectInfo>> {
+        let handler = self.inner.clone();
+        call_handler_method!(
+            handler.read().await.deref(),
+            RgbicLightStripHandler::get_lighting_effect
+        )
+    }
+
     pub async fn set_segment_effect(&self, segment_effect: Py<PyAny>) -> PyResult<()> {
         let handler = self.inner.clone();
         let segment_effect = map_segment_effect(segment_effect)?;
@@ -81,6 +91,32 @@ impl PyRgbicLightStripHandler {
             segment_effect
         )
     }
+
+    pub async fn get_segment_count(&self) -> PyResult<Option<u8>> {
+        let handler = self.inner.clone();
+        call_handler_method!(
+            handler.read().await.deref(),
+            RgbicLightStripHandler::get_segment_count
+        )
+    }
+
+    pub async fn set_segments(&self, segments: Vec<(u8, [u16; 3])>) -> PyResult<()> {
+        let handler = self.inner.clone();
+        call_handler_method!(
+            handler.read().await.deref(),
+            RgbicLightStripHandler::set_segments,
+            &segments
+        )
+    }
+
+    pub async fn set_segment_gradient(&self, stops: Vec<[u16; 3]>) -> PyResult<()> {
+        let handler = self.inner.clone();
+        call_handler_method!(
+            handler.read().await.deref(),
+            RgbicLightStripHandler::set_segment_gradient,
+            &stops
+        )
+    }
 }
 
 fn map_lighting_effect(lighting_effect: Py<PyAny>) -> PyResult<LightingEffect> {
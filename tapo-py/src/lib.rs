@@ -19,10 +19,10 @@ use tapo::responses::{
     DeviceInfoLightResult, DeviceInfoPlugEnergyMonitoringResult, DeviceInfoPlugResult,
     DeviceInfoPowerStripResult, DeviceInfoRgbLightStripResult, DeviceInfoRgbicLightStripResult,
     DeviceUsageEnergyMonitoringResult, DeviceUsageResult, EnergyDataIntervalResult,
-    EnergyDataResult, EnergyUsageResult, KE100Result, OtherResult, OvercurrentStatus,
-    OverheatStatus, PlugState, PowerDataIntervalResult, PowerDataResult, PowerProtectionStatus,
-    PowerState, PowerStripPlugEnergyMonitoringResult, PowerStripPlugResult, Preset,
-    RgbLightStripState, RgbicLightStripState, RtspStreamUrl, S200Log, S200Result,
+    EnergyDataResult, EnergyUsageResult, KE100Result, LightingEffectInfo, OtherResult,
+    OvercurrentStatus, OverheatStatus, PlugState, PowerDataIntervalResult, PowerDataResult,
+    PowerProtectionStatus, PowerState, PowerStripPlugEnergyMonitoringResult, PowerStripPlugResult,
+    Preset, RgbLightStripState, RgbicLightStripState, RtspStreamUrl, S200Log, S200Result,
     S200RotationParams, S210Result, Snapshot, Status, T31XResult, T100Log, T100Result, T110Log,
     T110Result, T300Log, T300Result, TemperatureHumidityRecord, TemperatureHumidityRecords,
     TemperatureUnit, TemperatureUnitKE100, Timer, UsageByPeriodResult, WaterLeakStatus,
@@ -172,6 +172,7 @@ fn register_responses(module: &Bound<'_, PyModule>) -> Result<(), PyErr> {
     module.add_class::<DeviceInfoRgbicLightStripResult>()?;
     module.add_class::<DefaultRgbicLightStripState>()?;
     module.add_class::<RgbicLightStripState>()?;
+    module.add_class::<LightingEffectInfo>()?;
     module.add_class::<PyLightingEffect>()?;
     module.add_class::<LightingEffectType>()?;
 
@@ -72,12 +72,42 @@ impl PyColorLightSetDeviceInfoParams {
         }
     }
 
+    pub fn rgb(&self, r: u8, g: u8, b: u8) -> Self {
+        Self {
+            params: self.params.clone().rgb(r, g, b),
+        }
+    }
+
+    pub fn hex(&self, color: &str) -> Self {
+        Self {
+            params: self.params.clone().hex(color),
+        }
+    }
+
+    pub fn xy(&self, x: f64, y: f64) -> Self {
+        Self {
+            params: self.params.clone().xy(x, y),
+        }
+    }
+
     pub fn color_temperature(&self, color_temperature: u16) -> Self {
         Self {
             params: self.params.clone().color_temperature(color_temperature),
         }
     }
 
+    pub fn kelvin(&self, kelvin: u16) -> Self {
+        Self {
+            params: self.params.clone().kelvin(kelvin),
+        }
+    }
+
+    pub fn color_temperature_range(&self, min: u16, max: u16) -> Self {
+        Self {
+            params: self.params.clone().color_temperature_range(min..=max),
+        }
+    }
+
     async fn send(&self, handler: Py<PyAny>) -> PyResult<()> {
         if let Some(handler) = Python::attach(|py| handler.extract::<PyColorLightHandler>(py).ok())
         {
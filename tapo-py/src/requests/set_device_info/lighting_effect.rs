@@ -192,6 +192,10 @@ impl PyLightingEffect {
         (*slf).inner.transition_sequence = Some(transition_sequence);
         slf
     }
+
+    pub fn validate(&self) -> PyResult<()> {
+        Ok(self.inner.validate()?)
+    }
 }
 
 impl From<PyLightingEffect> for LightingEffect {
@@ -48,6 +48,46 @@ class ColorLightSetDeviceInfoParams:
             saturation (int): between 1 and 100
         """
 
+    def rgb(self, r: int, g: int, b: int) -> ColorLightSetDeviceInfoParams:
+        """Sets the *hue* and *saturation* of an RGB color.
+        `ColorLightSetDeviceInfoParams.send` must be called at the end to apply the changes.
+        The device will also be turned *on*, unless `ColorLightSetDeviceInfoParams.off` is called.
+
+        The *value* of the color is used as the *brightness*,
+        unless `ColorLightSetDeviceInfoParams.brightness` is called.
+        Grays are set with the lowest *saturation* supported by the device.
+
+        Args:
+            r (int): the red channel, between 0 and 255
+            g (int): the green channel, between 0 and 255
+            b (int): the blue channel, between 0 and 255
+        """
+
+    def hex(self, color: str) -> ColorLightSetDeviceInfoParams:
+        """Sets the *hue* and *saturation* of a CSS color, like `ColorLightSetDeviceInfoParams.rgb`.
+        `ColorLightSetDeviceInfoParams.send` must be called at the end to apply the changes.
+        The device will also be turned *on*, unless `ColorLightSetDeviceInfoParams.off` is called.
+
+        An invalid color is reported by `ColorLightSetDeviceInfoParams.send`.
+        Named colors, e.g. `orange`, aren't supported.
+
+        Args:
+            color (str): in the `#rgb`, `#rrggbb` or `rgb(r, g, b)` notation, e.g. `#ff8800`
+        """
+
+    def xy(self, x: float, y: float) -> ColorLightSetDeviceInfoParams:
+        """Sets the *hue* and *saturation* of a CIE 1931 chromaticity, as used by Zigbee and Philips Hue.
+        `ColorLightSetDeviceInfoParams.send` must be called at the end to apply the changes.
+        The device will also be turned *on*, unless `ColorLightSetDeviceInfoParams.off` is called.
+
+        Chromaticities outside of the sRGB gamut are mapped to the nearest color the device can display.
+        The *brightness* is left unchanged.
+
+        Args:
+            x (float): between 0 and 1
+            y (float): between 0 and 1, excluding 0
+        """
+
     def color_temperature(self, color_temperature: int) -> ColorLightSetDeviceInfoParams:
         """
         Sets the *color temperature*.
@@ -58,6 +98,30 @@ class ColorLightSetDeviceInfoParams:
             color_temperature (int): between 2500 and 6500
         """
 
+    def kelvin(self, kelvin: int) -> ColorLightSetDeviceInfoParams:
+        """Sets the *color temperature*, clamped to the range supported by the device.
+        `ColorLightSetDeviceInfoParams.send` must be called at the end to apply the changes.
+        The device will also be turned *on*, unless `ColorLightSetDeviceInfoParams.off` is called.
+
+        The range is read from the device info once per session, unless
+        `ColorLightSetDeviceInfoParams.color_temperature_range` is called.
+        Devices that don't report their range are clamped to 2500 to 6500.
+
+        Args:
+            kelvin (int): the color temperature in Kelvin
+        """
+
+    def color_temperature_range(self, min: int, max: int) -> ColorLightSetDeviceInfoParams:
+        """Sets the *color temperature* range supported by the device, used to validate
+        `ColorLightSetDeviceInfoParams.color_temperature` and clamp `ColorLightSetDeviceInfoParams.kelvin`.
+
+        The range of a device is reported by the `color_temp_range` of its device info.
+
+        Args:
+            min (int): the lowest color temperature in Kelvin
+            max (int): the highest color temperature in Kelvin
+        """
+
     async def send(
         self, handler: Union[ColorLightHandler, RgbLightStripHandler, RgbicLightStripHandler]
     ) -> None:
@@ -72,6 +72,15 @@ class LightingEffect(ToDictExt):
     def with_transition(self, transition: int) -> LightingEffect: ...
     def with_transition_range(self, transition_range: Tuple[int, int]) -> LightingEffect: ...
     def with_transition_sequence(self, transition_sequence: List[int]) -> LightingEffect: ...
+    def validate(self) -> None:
+        """Checks that the fields are within the ranges accepted by the device,
+        and that custom effects list their `segments` (and a `sequence` for sequence and static effects).
+        The ranges are checked before an effect is sent, this allows checking the whole effect earlier,
+        e.g. after loading it from a file.
+
+        Raises:
+            Exception: if a field is out of range.
+        """
 
 class LightingEffectPreset(str, Enum):
     Aurora = "Aurora"
@@ -1,4 +1,4 @@
-from typing import Optional
+from typing import List, Optional
 
 from tapo.responses.device_info_result.default_state import DefaultStateType
 from tapo.responses.device_info_result.device_info_ext import DeviceInfoSmartExt
@@ -8,6 +8,9 @@ class DeviceInfoColorLightResult(DeviceInfoSmartExt, ToDictExt):
     """Device info of Tapo L530, L535 and L630."""
 
     brightness: int
+    color_temp_range: Optional[List[int]]
+    """The lowest and highest color temperature supported by the device, in Kelvin.
+    Not reported by older firmware."""
     color_temp: int
     default_states: DefaultColorLightState
     """The default state of a device to be used when internet connectivity is lost after a power cut."""
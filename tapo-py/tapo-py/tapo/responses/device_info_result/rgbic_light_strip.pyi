@@ -1,8 +1,8 @@
-from typing import List, Optional
+from typing import List, Optional, Tuple
 
 from tapo.responses.device_info_result.default_state import DefaultStateType
 from tapo.responses.device_info_result.device_info_ext import DeviceInfoSmartExt
-from tapo.requests.set_device_info.lighting_effect import LightingEffect
+from tapo.requests.set_device_info.lighting_effect import LightingEffect, LightingEffectPreset
 from tapo.to_dict_ext import ToDictExt
 
 class DeviceInfoRgbicLightStripResult(DeviceInfoSmartExt, ToDictExt):
@@ -15,9 +15,13 @@ class DeviceInfoRgbicLightStripResult(DeviceInfoSmartExt, ToDictExt):
     """The default state of a device to be used when internet connectivity is lost after a power cut."""
     device_on: bool
     hue: Optional[int]
+    lighting_effect: Optional[LightingEffectInfo]
+    """The *lighting effect* last set on the device. Not reported by older firmware."""
     nickname: str
     overheated: bool
     saturation: Optional[int]
+    segment_count: Optional[int]
+    """The number of segments, the individually colored zones of the strip. Not reported by older firmware."""
 
 class DefaultRgbicLightStripState(ToDictExt):
     """RGB IC Light Strip Default State."""
@@ -33,3 +37,26 @@ class RgbicLightStripState(ToDictExt):
     saturation: Optional[int]
     color_temp: Optional[int]
     lighting_effect: Optional[LightingEffect]
+
+class LightingEffectInfo(ToDictExt):
+    """The *lighting effect* of an RGB IC Light Strip, as reported by its device info.
+
+    The device only reports the identity of the effect, not its full definition.
+    For the built-in effects, `LightingEffectInfo.preset` returns the matching `LightingEffectPreset`.
+    """
+
+    brightness: int
+    """The brightness of the effect, between 0 and 100."""
+    is_custom: bool
+    """Whether the effect was created by the user instead of being built-in."""
+    display_colors: List[Tuple[int, int, int]]
+    """The colors displayed in the Tapo app."""
+    enabled: bool
+    """Whether the effect is currently running."""
+    id: str
+    """The effect identifier."""
+    name: str
+    """The effect name shown in the Tapo app."""
+
+    def preset(self) -> Optional[LightingEffectPreset]:
+        """Returns the `LightingEffectPreset` with the same identifier, if this is a built-in effect."""
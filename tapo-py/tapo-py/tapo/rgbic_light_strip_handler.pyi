@@ -1,4 +1,4 @@
-from typing import Union
+from typing import List, Optional, Tuple, Union
 
 from tapo.debug_ext import DebugExt
 from tapo.device_management_ext import DeviceManagementExt
@@ -12,7 +12,11 @@ from tapo.requests import (
     SegmentEffect,
     SegmentEffectPreset,
 )
-from tapo.responses import DeviceInfoRgbicLightStripResult, DeviceUsageEnergyMonitoringResult
+from tapo.responses import (
+    DeviceInfoRgbicLightStripResult,
+    DeviceUsageEnergyMonitoringResult,
+    LightingEffectInfo,
+)
 
 class RgbicLightStripHandler(OnOffExt, DeviceManagementExt, RefreshSessionExt, DebugExt):
     """Handler for the [L920](https://www.tapo.com/en/search/?q=L920) and
@@ -90,6 +94,15 @@ class RgbicLightStripHandler(OnOffExt, DeviceManagementExt, RefreshSessionExt, D
             lighting_effect (LightingEffect | LightingEffectPreset)
         """
 
+    async def get_lighting_effect(self) -> Optional[LightingEffectInfo]:
+        """Returns the running *lighting effect*, or `None` if no effect is running.
+        The device only reports the identity of the effect, use `LightingEffectInfo.preset`
+        to get the full definition of a built-in effect.
+
+        Returns:
+            Optional[LightingEffectInfo]: the running lighting effect
+        """
+
     async def set_segment_effect(
         self, segment_effect: Union[SegmentEffect, SegmentEffectPreset]
     ) -> None:
@@ -101,3 +114,35 @@ class RgbicLightStripHandler(OnOffExt, DeviceManagementExt, RefreshSessionExt, D
         Args:
             segment_effect (SegmentEffect | SegmentEffectPreset)
         """
+
+    async def get_segment_count(self) -> Optional[int]:
+        """Returns the number of segments, the individually colored zones of the strip.
+
+        Returns:
+            Optional[int]: the segment count, `None` if the firmware doesn't report it
+        """
+
+    async def set_segments(self, segments: List[Tuple[int, Tuple[int, int, int]]]) -> None:
+        """Paints individual segments with static colors and turns *on* the device.
+        Pre-existing *lighting effect* will be replaced. Segments that aren't listed are switched off.
+
+        Args:
+            segments (List[Tuple[int, Tuple[int, int, int]]]): the index, between 0 and
+                `get_segment_count` excluded, and the `(hue, saturation, brightness)` color
+                of each segment to paint. The indexes can't be checked on firmware versions
+                that don't report the segment count.
+        """
+
+    async def set_segment_gradient(self, stops: List[Tuple[int, int, int]]) -> None:
+        """Spreads a gradient evenly over all the segments and turns *on* the device.
+        Pre-existing *lighting effect* will be replaced.
+
+        The first stop is painted on the first segment and the last stop on the last segment.
+        The segments in between are interpolated linearly, with the hue taking the shortest
+        way around the color wheel.
+        Fails on firmware versions that don't report the segment count.
+
+        Args:
+            stops (List[Tuple[int, int, int]]): the `(hue, saturation, brightness)` colors
+                of the gradient, from the first to the last segment
+        """
@@ -1,11 +1,26 @@
 mod api_client;
+pub(crate) mod batch;
 #[macro_use]
 mod tapo_handler;
+#[macro_use]
+mod capabilities;
+#[macro_use]
+pub(crate) mod device_watch;
+#[macro_use]
+mod schedule_rules;
+#[macro_use]
+mod antitheft_rules;
+#[macro_use]
+pub(crate) mod device_settings;
 mod camera_ptz_handler;
 mod child_devices;
 mod color_light_handler;
+mod device_registry;
 mod discovery;
+mod firmware_rollout;
 mod handler_ext;
+mod hub_child_device_list;
+mod hub_events;
 mod hub_handler;
 mod light_handler;
 mod plug;
@@ -13,17 +28,24 @@ mod plug_energy_monitoring_handler;
 mod plug_handler;
 mod power_strip_energy_monitoring_handler;
 mod power_strip_handler;
-mod protocol;
+pub(crate) mod protocol;
 mod rgb_light_strip_handler;
 mod rgbic_light_strip_handler;
 mod rtsp_snapshot;
 
 pub use api_client::*;
+pub use batch::Batch;
 pub use camera_ptz_handler::*;
+pub use capabilities::*;
 pub use child_devices::*;
 pub use color_light_handler::*;
+pub use device_registry::*;
+pub use device_watch::{DeviceDiff, DeviceWatch, FieldChange};
 pub use discovery::*;
+pub use firmware_rollout::*;
 pub use handler_ext::*;
+pub use hub_child_device_list::{ChildDeviceListDiff, ChildDeviceListStream, ChildDeviceRename};
+pub use hub_events::*;
 pub use hub_handler::*;
 pub use light_handler::*;
 pub use plug::*;
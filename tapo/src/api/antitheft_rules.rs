@@ -0,0 +1,72 @@
+/// Implements the `antitheft_rules` methods.
+macro_rules! impl_antitheft_rules {
+    ($name:ident) => {
+        impl $name {
+            /// Returns the *away mode rules* (the "Away Mode" feature in the Tapo app)
+            /// as [`AntitheftRules`](crate::responses::AntitheftRules).
+            pub async fn get_antitheft_rules(
+                &self,
+            ) -> Result<crate::responses::AntitheftRules, crate::error::Error> {
+                self.client.read().await.get_antitheft_rules().await
+            }
+
+            /// Adds an enabled away mode rule and returns it, including its device-assigned `id`.
+            /// While the rule is active, the device is randomly turned *on* and *off* between `start` and `end`.
+            ///
+            /// # Arguments
+            ///
+            /// * `start` - when the time window starts
+            /// * `end` - when the time window ends
+            /// * `recurrence` - on which days the rule is active
+            pub async fn add_antitheft_rule(
+                &self,
+                start: crate::requests::ScheduleTime,
+                end: crate::requests::ScheduleTime,
+                recurrence: crate::requests::ScheduleRecurrence,
+            ) -> Result<crate::responses::AntitheftRule, crate::error::Error> {
+                self.client
+                    .read()
+                    .await
+                    .add_antitheft_rule(start, end, recurrence)
+                    .await
+            }
+
+            /// Replaces the away mode rule with the same `id`, e.g. to enable or disable it.
+            ///
+            /// # Arguments
+            ///
+            /// * `rule` - a rule returned by `get_antitheft_rules` or `add_antitheft_rule`, with the changes applied
+            pub async fn edit_antitheft_rule(
+                &self,
+                rule: &crate::responses::AntitheftRule,
+            ) -> Result<(), crate::error::Error> {
+                self.client.read().await.edit_antitheft_rule(rule).await
+            }
+
+            /// Removes the away mode rules with the given `ids`.
+            ///
+            /// # Arguments
+            ///
+            /// * `ids` - the `id`s of the rules to remove
+            pub async fn remove_antitheft_rules(
+                &self,
+                ids: Vec<String>,
+            ) -> Result<(), crate::error::Error> {
+                self.client
+                    .read()
+                    .await
+                    .remove_antitheft_rules(crate::requests::RemoveRulesParams::remove(ids))
+                    .await
+            }
+
+            /// Removes all the away mode rules.
+            pub async fn remove_all_antitheft_rules(&self) -> Result<(), crate::error::Error> {
+                self.client
+                    .read()
+                    .await
+                    .remove_antitheft_rules(crate::requests::RemoveRulesParams::remove_all())
+                    .await
+            }
+        }
+    };
+}
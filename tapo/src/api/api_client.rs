@@ -1,5 +1,6 @@
 use std::fmt;
-use std::sync::Arc;
+use std::ops::RangeInclusive;
+use std::sync::{Arc, OnceLock};
 use std::time::Duration;
 
 use async_trait::async_trait;
@@ -10,10 +11,12 @@ use tokio::sync::RwLock;
 
 use crate::error::{Error, TapoResponseError};
 use crate::requests::{
-    AddTimerParams, ControlChildParams, DeviceRebootParams, EmptyObjectParams, EmptyParams,
-    EnergyDataInterval, GetChildDeviceListParams, GetEnergyDataParams, GetPowerDataParams,
-    LightingEffect, MultipleRequestParams, PlayAlarmParams, PowerDataInterval, RemoveTimersParams,
-    SegmentEffect, SmartCamDoParams, SmartCamGetParams, TapoParams, TapoRequest,
+    AddTimerParams, ChildDeviceListParams, ControlChildParams, DeviceRebootParams,
+    EmptyObjectParams, EmptyParams, EnergyDataInterval, GetChildDeviceListParams,
+    GetEnergyDataParams, GetPowerDataParams, GetRulesParams, LightingEffect, MultipleRequestParams,
+    PlayAlarmParams, PowerDataInterval, RemoveRulesParams, RemoveTimersParams, ScheduleRecurrence,
+    ScheduleTime, SegmentEffect, SetProtectionPowerParams, SmartCamDoParams, SmartCamGetParams,
+    TapoParams, TapoRequest,
 };
 #[cfg(feature = "debug")]
 use crate::responses::{
@@ -22,15 +25,20 @@ use crate::responses::{
 };
 
 use crate::responses::{
-    AddTimerResult, ControlChildResult, CurrentPowerResult, DecodableResultExt, EnergyDataResult,
-    EnergyDataResultRaw, EnergyUsageResult, PowerDataResult, PowerDataResultRaw, PowerState,
-    TapoMultipleResponse, TapoResponseExt, TapoResult, Timer, TimerListResultRaw,
-    validate_response,
+    AddAntitheftRuleResult, AddScheduleRuleResult, AddTimerResult, AntitheftRule,
+    AntitheftRuleListResultRaw, AntitheftRuleRaw, AntitheftRules, ControlChildResult,
+    CurrentPowerResult, DecodableResultExt, EnergyDataResult, EnergyDataResultRaw,
+    EnergyUsageResult, FirmwareDownloadStateResult, LatestFirmwareResult, LedInfoResult,
+    MaxPowerResult, OnOffGraduallyInfoResult, PowerDataResult, PowerDataResultRaw,
+    PowerProtectionResult, PowerProtectionStatus, PowerState, ScanChildDeviceListResult,
+    ScheduleRule, ScheduleRuleListResultRaw, ScheduleRuleRaw, ScheduleRules, TapoMultipleResponse,
+    TapoMultipleResult, TapoResponseExt, TapoResult, TemperatureUnitKE100, Timer,
+    TimerListResultRaw, TrvScheduleRule, TrvScheduleRules, validate_response,
 };
 
-use super::discovery::DeviceDiscovery;
 #[cfg(feature = "debug")]
 use super::discovery::DeviceDiscoveryRaw;
+use super::discovery::{DeviceDiscovery, DiscoveryResult};
 use super::protocol::{AuthProtocol, DeviceFamily, TapoProtocol};
 use super::{
     CameraPtzHandler, ColorLightHandler, HubHandler, LightHandler, PlugEnergyMonitoringHandler,
@@ -49,6 +57,11 @@ pub trait ApiClientExt: std::fmt::Debug + Send + Sync {
     async fn device_reboot(&self, delay_s: u16) -> Result<(), Error>;
     /// Hardware resets the device.
     async fn device_reset(&self) -> Result<(), Error>;
+    /// Returns the range of *color temperatures* reported by the device info, in Kelvin,
+    /// or `None` if the device doesn't report one.
+    async fn get_color_temperature_range(&self) -> Result<Option<RangeInclusive<u16>>, Error> {
+        Ok(None)
+    }
 }
 
 /// Tapo API Client. See [examples](https://github.com/mihai-dinculescu/tapo/tree/main/tapo/examples).
@@ -74,7 +87,10 @@ pub struct ApiClient {
     tapo_username: String,
     tapo_password: String,
     timeout: Option<Duration>,
+    session_refresh: bool,
     protocol: Option<TapoProtocol>,
+    color_temperature_range: OnceLock<Option<RangeInclusive<u16>>>,
+    segment_count: OnceLock<Option<u8>>,
 }
 
 /// Tapo API Client constructor.
@@ -90,12 +106,18 @@ impl ApiClient {
     ///
     /// Note: The default connection timeout is 30 seconds.
     /// Use [`ApiClient::with_timeout`] to change it.
+    ///
+    /// Note: Expired sessions are refreshed automatically by default.
+    /// Use [`ApiClient::with_session_refresh`] to change it.
     pub fn new(tapo_username: impl Into<String>, tapo_password: impl Into<String>) -> ApiClient {
         Self {
             tapo_username: tapo_username.into(),
             tapo_password: tapo_password.into(),
             timeout: None,
+            session_refresh: true,
             protocol: None,
+            color_temperature_range: OnceLock::new(),
+            segment_count: OnceLock::new(),
         }
     }
 
@@ -108,6 +130,22 @@ impl ApiClient {
         self.timeout = Some(timeout);
         self
     }
+
+    /// Enables or disables the automatic session refresh.
+    ///
+    /// When enabled (the default), a request that fails because the device session has expired
+    /// (e.g. `SESSION_TIMEOUT`, an invalidated login token or a KLAP `403`) triggers a re-login,
+    /// after which the request is replayed once.
+    /// When disabled, the error is returned as-is and the session must be refreshed manually
+    /// by calling `refresh_session` on the handler.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - whether expired sessions should be refreshed automatically.
+    pub fn with_session_refresh(mut self, enabled: bool) -> ApiClient {
+        self.session_refresh = enabled;
+        self
+    }
 }
 
 /// Device discovery.
@@ -168,6 +206,74 @@ impl ApiClient {
     }
 }
 
+/// Generic device handler builder.
+impl ApiClient {
+    /// Connects to the device at the given IP address and returns a [`DiscoveryResult`]
+    /// containing the device info and the handler that matches the device's model.
+    /// Unlike [`ApiClient::discover_devices`], it doesn't rely on UDP discovery,
+    /// which makes it suitable when the IP address is known but the model isn't.
+    ///
+    /// Tapo smart devices are tried first. If the device can't be reached that way,
+    /// the camera protocol is tried next.
+    ///
+    /// # Arguments
+    ///
+    /// * `ip_address` - the IP address of the device
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use tapo::{ApiClient, DiscoveryResult};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let device = ApiClient::new("tapo-username@example.com", "tapo-password")
+    ///     .connect("192.168.1.100")
+    ///     .await?;
+    ///
+    /// match device {
+    ///     DiscoveryResult::ColorLight { handler, .. } => handler.on().await?,
+    ///     DiscoveryResult::PlugEnergyMonitoring { handler, .. } => handler.on().await?,
+    ///     other => println!("Found a {} at {}", other.model(), other.ip()),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn connect(self, ip_address: impl Into<String>) -> Result<DiscoveryResult, Error> {
+        let ip_address = ip_address.into();
+
+        let error = match DiscoveryResult::connect(
+            self.clone(),
+            ip_address.clone(),
+            None,
+            DeviceFamily::Smart,
+            AuthProtocol::Unknown,
+        )
+        .await
+        {
+            Ok(result) => return Ok(result),
+            Err(error @ Error::Http(_)) => error,
+            Err(error) => return Err(error),
+        };
+
+        debug!("Failed to reach {ip_address} as a smart device ({error}), trying as a camera...");
+
+        match DiscoveryResult::connect(
+            self,
+            ip_address,
+            None,
+            DeviceFamily::SmartCam,
+            AuthProtocol::AesSsl,
+        )
+        .await
+        {
+            Ok(result) => Ok(result),
+            // Neither protocol reached the device, so the original error is the more relevant one.
+            Err(Error::Http(_)) => Err(error),
+            Err(error) => Err(error),
+        }
+    }
+}
+
 /// Device handler builders.
 impl ApiClient {
     /// Specializes the given [`ApiClient`] into an authenticated [`LightHandler`].
@@ -942,6 +1048,8 @@ impl ApiClient {
     ) -> Result<(), Error> {
         let tapo_username = self.tapo_username.clone();
         let tapo_password = self.tapo_password.clone();
+        self.color_temperature_range = OnceLock::new();
+        self.segment_count = OnceLock::new();
 
         self.protocol_mut()?
             .login(
@@ -963,14 +1071,17 @@ impl ApiClient {
             .await
     }
 
+    pub(crate) fn set_session_refresh(&mut self, enabled: bool) {
+        self.session_refresh = enabled;
+    }
+
     #[cfg(feature = "debug")]
     pub(crate) async fn get_supported_alarm_type_list(
         &self,
     ) -> Result<SupportedAlarmTypeListResult, Error> {
         let request = TapoRequest::GetSupportedAlarmTypeList(TapoParams::new(EmptyParams));
 
-        self.protocol()?
-            .execute_request(request)
+        self.execute_request(request)
             .await?
             .ok_or_else(|| Error::Tapo(TapoResponseError::EmptyResult))
     }
@@ -978,9 +1089,7 @@ impl ApiClient {
     pub(crate) async fn play_alarm(&self, params: PlayAlarmParams) -> Result<(), Error> {
         let request = TapoRequest::PlayAlarm(TapoParams::new(params));
 
-        self.protocol()?
-            .execute_request::<serde_json::Value>(request)
-            .await?;
+        self.execute_request::<serde_json::Value>(request).await?;
 
         Ok(())
     }
@@ -988,9 +1097,61 @@ impl ApiClient {
     pub(crate) async fn stop_alarm(&self) -> Result<(), Error> {
         let request = TapoRequest::StopAlarm(TapoParams::new(EmptyParams));
 
-        self.protocol()?
-            .execute_request::<serde_json::Value>(request)
-            .await?;
+        self.execute_request::<serde_json::Value>(request).await?;
+
+        Ok(())
+    }
+
+    pub(crate) async fn begin_scanning_child_device(&self) -> Result<(), Error> {
+        debug!("Begin scanning child device...");
+        let request = TapoRequest::BeginScanningChildDevice(TapoParams::new(EmptyParams));
+
+        self.execute_request::<serde_json::Value>(request).await?;
+
+        Ok(())
+    }
+
+    pub(crate) async fn end_scanning_child_device(&self) -> Result<(), Error> {
+        debug!("End scanning child device...");
+        let request = TapoRequest::EndScanningChildDevice(TapoParams::new(EmptyParams));
+
+        self.execute_request::<serde_json::Value>(request).await?;
+
+        Ok(())
+    }
+
+    pub(crate) async fn get_scan_child_device_list(
+        &self,
+    ) -> Result<ScanChildDeviceListResult, Error> {
+        debug!("Get Scan child device list...");
+        let request = TapoRequest::GetScanChildDeviceList(TapoParams::new(EmptyParams));
+
+        self.execute_request::<ScanChildDeviceListResult>(request)
+            .await?
+            .map(|result| result.decode())
+            .ok_or_else(|| Error::Tapo(TapoResponseError::EmptyResult))?
+    }
+
+    pub(crate) async fn add_child_device_list(
+        &self,
+        params: ChildDeviceListParams,
+    ) -> Result<(), Error> {
+        debug!("Add child device list...");
+        let request = TapoRequest::AddChildDeviceList(Box::new(TapoParams::new(params)));
+
+        self.execute_request::<serde_json::Value>(request).await?;
+
+        Ok(())
+    }
+
+    pub(crate) async fn remove_child_device_list(
+        &self,
+        params: ChildDeviceListParams,
+    ) -> Result<(), Error> {
+        debug!("Remove child device list...");
+        let request = TapoRequest::RemoveChildDeviceList(Box::new(TapoParams::new(params)));
+
+        self.execute_request::<serde_json::Value>(request).await?;
 
         Ok(())
     }
@@ -1001,7 +1162,6 @@ impl ApiClient {
         let request = TapoRequest::ComponentNegotiation(TapoParams::new(EmptyParams));
 
         let result: ComponentListResult = self
-            .protocol()?
             .execute_request(request)
             .await?
             .ok_or_else(|| Error::Tapo(TapoResponseError::EmptyResult))?;
@@ -1023,8 +1183,7 @@ impl ApiClient {
             DeviceFamily::Smart => {
                 let request = TapoRequest::GetDeviceInfo(TapoParams::new(EmptyParams));
 
-                self.protocol()?
-                    .execute_request::<R>(request)
+                self.execute_request::<R>(request)
                     .await?
                     .ok_or_else(|| Error::Tapo(TapoResponseError::EmptyResult))?
                     .decode()
@@ -1039,8 +1198,7 @@ impl ApiClient {
         debug!("Get Device usage...");
         let request = TapoRequest::GetDeviceUsage(TapoParams::new(EmptyParams));
 
-        self.protocol()?
-            .execute_request(request)
+        self.execute_request(request)
             .await?
             .ok_or_else(|| Error::Tapo(TapoResponseError::EmptyResult))
     }
@@ -1056,9 +1214,7 @@ impl ApiClient {
                 .set_terminal_uuid(TERMINAL_UUID),
         ));
 
-        self.protocol()?
-            .execute_request::<TapoResult>(request)
-            .await?;
+        self.execute_request::<TapoResult>(request).await?;
 
         Ok(())
     }
@@ -1075,19 +1231,32 @@ impl ApiClient {
                 .set_terminal_uuid(TERMINAL_UUID),
         ));
 
-        self.protocol()?
-            .execute_request::<TapoResult>(request)
-            .await?;
+        self.execute_request::<TapoResult>(request).await?;
 
         Ok(())
     }
 
+    /// Returns the number of segments reported by the device info,
+    /// or `None` if the device doesn't report it.
+    /// The result is cached until the next login.
+    pub(crate) async fn get_segment_count(&self) -> Result<Option<u8>, Error> {
+        if let Some(segment_count) = self.segment_count.get() {
+            return Ok(*segment_count);
+        }
+
+        let device_info = self.get_device_info::<serde_json::Value>().await?;
+        let segment_count = device_info["segment_count"]
+            .as_u64()
+            .and_then(|segment_count| u8::try_from(segment_count).ok());
+
+        Ok(*self.segment_count.get_or_init(|| segment_count))
+    }
+
     pub(crate) async fn get_energy_usage(&self) -> Result<EnergyUsageResult, Error> {
         debug!("Get Energy usage...");
         let request = TapoRequest::GetEnergyUsage(TapoParams::new(EmptyParams));
 
-        self.protocol()?
-            .execute_request(request)
+        self.execute_request(request)
             .await?
             .ok_or_else(|| Error::Tapo(TapoResponseError::EmptyResult))
     }
@@ -1096,8 +1265,7 @@ impl ApiClient {
         debug!("Get Current power...");
         let request = TapoRequest::GetCurrentPower(TapoParams::new(EmptyParams));
 
-        self.protocol()?
-            .execute_request(request)
+        self.execute_request(request)
             .await?
             .ok_or_else(|| Error::Tapo(TapoResponseError::EmptyResult))
     }
@@ -1110,8 +1278,7 @@ impl ApiClient {
         let params = GetEnergyDataParams::new(interval);
         let request = TapoRequest::GetEnergyData(TapoParams::new(params));
 
-        self.protocol()?
-            .execute_request::<EnergyDataResultRaw>(request)
+        self.execute_request::<EnergyDataResultRaw>(request)
             .await?
             .ok_or_else(|| Error::Tapo(TapoResponseError::EmptyResult))
             .map(|result| result.try_into())?
@@ -1125,8 +1292,7 @@ impl ApiClient {
         let params = GetPowerDataParams::new(interval);
         let request = TapoRequest::GetPowerData(TapoParams::new(params));
 
-        self.protocol()?
-            .execute_request::<PowerDataResultRaw>(request)
+        self.execute_request::<PowerDataResultRaw>(request)
             .await?
             .ok_or_else(|| Error::Tapo(TapoResponseError::EmptyResult))
             .map(|result| result.try_into())?
@@ -1141,8 +1307,7 @@ impl ApiClient {
             GetChildDeviceListParams::new(start_index),
         ));
 
-        self.protocol()?
-            .execute_request::<R>(request)
+        self.execute_request::<R>(request)
             .await?
             .map(|result| result.decode())
             .ok_or_else(|| Error::Tapo(TapoResponseError::EmptyResult))?
@@ -1156,7 +1321,6 @@ impl ApiClient {
         let request = TapoRequest::GetChildDeviceComponentList(TapoParams::new(EmptyParams));
 
         let result: ChildDeviceComponentListResult = self
-            .protocol()?
             .execute_request(request)
             .await?
             .ok_or_else(|| Error::Tapo(TapoResponseError::EmptyResult))?;
@@ -1180,7 +1344,6 @@ impl ApiClient {
         let request = TapoRequest::ControlChild(Box::new(TapoParams::new(params)));
 
         let responses = self
-            .protocol()?
             .execute_request::<ControlChildResult<TapoMultipleResponse<R>>>(request)
             .await?
             .ok_or_else(|| Error::Tapo(TapoResponseError::EmptyResult))?
@@ -1198,6 +1361,46 @@ impl ApiClient {
         Ok(response.result)
     }
 
+    pub(crate) async fn execute_multiple_request(
+        &self,
+        requests: Vec<TapoRequest>,
+        max_requests: usize,
+    ) -> Result<Vec<Option<serde_json::Value>>, Error> {
+        let mut results = Vec::with_capacity(requests.len());
+        let mut requests = requests.into_iter().peekable();
+
+        while requests.peek().is_some() {
+            let chunk = requests.by_ref().take(max_requests).collect::<Vec<_>>();
+            let chunk_len = chunk.len();
+            debug!("Multiple request with {chunk_len} requests...");
+
+            let params = MultipleRequestParams::new(chunk);
+            let request = TapoRequest::MultipleRequest(Box::new(TapoParams::new(params)));
+
+            let responses = self
+                .execute_request::<TapoMultipleResult<serde_json::Value>>(request)
+                .await?
+                .ok_or_else(|| Error::Tapo(TapoResponseError::EmptyResult))?
+                .responses;
+
+            if responses.len() != chunk_len {
+                return Err(Error::Tapo(TapoResponseError::ResponseError {
+                    description: format!(
+                        "Expected {chunk_len} responses to the multipleRequest, but received {}",
+                        responses.len()
+                    ),
+                }));
+            }
+
+            for response in responses {
+                validate_response(response.error_code)?;
+                results.push(response.result);
+            }
+        }
+
+        Ok(results)
+    }
+
     pub(crate) async fn execute_smart_cam_get<R>(
         &self,
         params: SmartCamGetParams,
@@ -1207,8 +1410,7 @@ impl ApiClient {
     {
         let request = TapoRequest::SmartCamGet(params);
 
-        self.protocol()?
-            .execute_request(request)
+        self.execute_request(request)
             .await?
             .ok_or_else(|| Error::Tapo(TapoResponseError::EmptyResult))
     }
@@ -1216,9 +1418,7 @@ impl ApiClient {
     pub(crate) async fn execute_smart_cam_do(&self, params: SmartCamDoParams) -> Result<(), Error> {
         let request = TapoRequest::SmartCamDo(params);
 
-        self.protocol()?
-            .execute_request::<serde_json::Value>(request)
-            .await?;
+        self.execute_request::<serde_json::Value>(request).await?;
 
         Ok(())
     }
@@ -1249,7 +1449,6 @@ impl ApiClient {
         let request = TapoRequest::AddCountdownRule(TapoParams::new(params));
 
         let result = self
-            .protocol()?
             .execute_request::<AddTimerResult>(request)
             .await?
             .ok_or_else(|| Error::Tapo(TapoResponseError::EmptyResult))?;
@@ -1265,7 +1464,6 @@ impl ApiClient {
     pub(crate) async fn get_timer(&self) -> Result<Option<Timer>, Error> {
         let request = TapoRequest::GetCountdownRules(TapoParams::new(EmptyObjectParams {}));
         let list = self
-            .protocol()?
             .execute_request::<TimerListResultRaw>(request)
             .await?
             .ok_or_else(|| Error::Tapo(TapoResponseError::EmptyResult))?;
@@ -1279,12 +1477,486 @@ impl ApiClient {
     pub(crate) async fn clear_timer(&self) -> Result<(), Error> {
         let request =
             TapoRequest::RemoveCountdownRules(TapoParams::new(RemoveTimersParams::remove_all()));
-        self.protocol()?
-            .execute_request::<serde_json::Value>(request)
+        self.execute_request::<serde_json::Value>(request).await?;
+        Ok(())
+    }
+
+    pub(crate) async fn get_schedule_rules(
+        &self,
+        child_device_id: Option<&str>,
+    ) -> Result<ScheduleRules, Error> {
+        debug!("Get Schedule rules...");
+        let list = self.get_schedule_rule_list(child_device_id).await?;
+
+        let mut rules = Vec::new();
+        let mut unsupported_rules = Vec::new();
+        for raw in list.rule_list {
+            match ScheduleRuleRaw::parse_rule(&raw) {
+                Some(rule) => rules.push(rule),
+                None => unsupported_rules.push(raw),
+            }
+        }
+
+        Ok(ScheduleRules {
+            enabled: list.enable,
+            max_count: list.schedule_rule_max_count,
+            rules,
+            unsupported_rules,
+        })
+    }
+
+    pub(crate) async fn get_trv_schedule_rules(
+        &self,
+        child_device_id: &str,
+    ) -> Result<TrvScheduleRules, Error> {
+        debug!("Get TRV Schedule rules...");
+        let list = self.get_schedule_rule_list(Some(child_device_id)).await?;
+
+        let mut rules = Vec::new();
+        let mut unsupported_rules = Vec::new();
+        for raw in list.rule_list {
+            match ScheduleRuleRaw::parse_trv_rule(&raw) {
+                Some(rule) => rules.push(rule),
+                None => unsupported_rules.push(raw),
+            }
+        }
+
+        Ok(TrvScheduleRules {
+            enabled: list.enable,
+            max_count: list.schedule_rule_max_count,
+            rules,
+            unsupported_rules,
+        })
+    }
+
+    /// Returns every page of the schedule rules, merged into a single list.
+    async fn get_schedule_rule_list(
+        &self,
+        child_device_id: Option<&str>,
+    ) -> Result<ScheduleRuleListResultRaw, Error> {
+        let mut rules = Vec::new();
+
+        loop {
+            let request = TapoRequest::GetScheduleRules(TapoParams::new(GetRulesParams::new(
+                rules.len() as u64,
+            )));
+
+            let page = self
+                .execute_device_request::<ScheduleRuleListResultRaw>(child_device_id, request)
+                .await?
+                .ok_or_else(|| Error::Tapo(TapoResponseError::EmptyResult))?;
+
+            let page_len = page.rule_list.len();
+            rules.extend(page.rule_list);
+
+            if page_len == 0 || rules.len() as u64 >= page.sum {
+                return Ok(ScheduleRuleListResultRaw {
+                    rule_list: rules,
+                    ..page
+                });
+            }
+        }
+    }
+
+    pub(crate) async fn add_schedule_rule(
+        &self,
+        child_device_id: Option<&str>,
+        time: ScheduleTime,
+        recurrence: ScheduleRecurrence,
+        desired_state: PowerState,
+    ) -> Result<ScheduleRule, Error> {
+        debug!("Add Schedule rule...");
+        let params = ScheduleRuleRaw::new(None, true, time, &recurrence, desired_state)?;
+        let id = self.add_schedule_rule_raw(child_device_id, params).await?;
+
+        Ok(ScheduleRule {
+            id,
+            enabled: true,
+            time,
+            recurrence,
+            desired_state,
+            raw: None,
+        })
+    }
+
+    pub(crate) async fn add_trv_schedule_rule(
+        &self,
+        child_device_id: &str,
+        time: ScheduleTime,
+        recurrence: ScheduleRecurrence,
+        target_temperature: f32,
+        temperature_unit: TemperatureUnitKE100,
+    ) -> Result<TrvScheduleRule, Error> {
+        debug!("Add TRV Schedule rule...");
+        let params = ScheduleRuleRaw::new_trv(
+            None,
+            true,
+            time,
+            &recurrence,
+            target_temperature,
+            temperature_unit.clone(),
+        )?;
+        let id = self
+            .add_schedule_rule_raw(Some(child_device_id), params)
             .await?;
+
+        Ok(TrvScheduleRule {
+            id,
+            enabled: true,
+            time,
+            recurrence,
+            target_temperature,
+            temperature_unit,
+            raw: None,
+        })
+    }
+
+    async fn add_schedule_rule_raw(
+        &self,
+        child_device_id: Option<&str>,
+        params: ScheduleRuleRaw,
+    ) -> Result<String, Error> {
+        let request = TapoRequest::AddScheduleRule(Box::new(TapoParams::new(params)));
+
+        let result = self
+            .execute_device_request::<AddScheduleRuleResult>(child_device_id, request)
+            .await?
+            .ok_or_else(|| Error::Tapo(TapoResponseError::EmptyResult))?;
+
+        Ok(result.id)
+    }
+
+    pub(crate) async fn edit_schedule_rule(
+        &self,
+        child_device_id: Option<&str>,
+        rule: &ScheduleRule,
+    ) -> Result<(), Error> {
+        debug!("Edit Schedule rule {}...", rule.id);
+        let params = ScheduleRuleRaw::new(
+            Some(rule.id.clone()),
+            rule.enabled,
+            rule.time,
+            &rule.recurrence,
+            rule.desired_state,
+        )?
+        .merge_into(rule.raw.as_ref())?;
+
+        self.edit_schedule_rule_raw(child_device_id, params).await
+    }
+
+    pub(crate) async fn edit_trv_schedule_rule(
+        &self,
+        child_device_id: &str,
+        rule: &TrvScheduleRule,
+    ) -> Result<(), Error> {
+        debug!("Edit TRV Schedule rule {}...", rule.id);
+        let params = ScheduleRuleRaw::new_trv(
+            Some(rule.id.clone()),
+            rule.enabled,
+            rule.time,
+            &rule.recurrence,
+            rule.target_temperature,
+            rule.temperature_unit.clone(),
+        )?
+        .merge_into(rule.raw.as_ref())?;
+
+        self.edit_schedule_rule_raw(Some(child_device_id), params)
+            .await
+    }
+
+    async fn edit_schedule_rule_raw(
+        &self,
+        child_device_id: Option<&str>,
+        params: serde_json::Value,
+    ) -> Result<(), Error> {
+        let request = TapoRequest::EditScheduleRule(Box::new(TapoParams::new(params)));
+
+        self.execute_device_request::<serde_json::Value>(child_device_id, request)
+            .await?;
+
+        Ok(())
+    }
+
+    pub(crate) async fn remove_schedule_rules(
+        &self,
+        child_device_id: Option<&str>,
+        params: RemoveRulesParams,
+    ) -> Result<(), Error> {
+        debug!("Remove Schedule rules...");
+        let request = TapoRequest::RemoveScheduleRules(TapoParams::new(params));
+
+        self.execute_device_request::<serde_json::Value>(child_device_id, request)
+            .await?;
+
         Ok(())
     }
 
+    pub(crate) async fn get_antitheft_rules(&self) -> Result<AntitheftRules, Error> {
+        debug!("Get Antitheft rules...");
+        let mut rules = Vec::new();
+
+        loop {
+            let request = TapoRequest::GetAntitheftRules(TapoParams::new(GetRulesParams::new(
+                rules.len() as u64,
+            )));
+
+            let page = self
+                .execute_request::<AntitheftRuleListResultRaw>(request)
+                .await?
+                .ok_or_else(|| Error::Tapo(TapoResponseError::EmptyResult))?;
+
+            let page_len = page.rule_list.len();
+            rules.extend(page.rule_list);
+
+            if page_len == 0 || rules.len() as u64 >= page.sum {
+                return Ok(AntitheftRules {
+                    enabled: page.enable,
+                    max_count: page.antitheft_rule_max_count,
+                    rules: rules
+                        .into_iter()
+                        .filter_map(AntitheftRuleRaw::into_rule)
+                        .collect(),
+                });
+            }
+        }
+    }
+
+    pub(crate) async fn add_antitheft_rule(
+        &self,
+        start: ScheduleTime,
+        end: ScheduleTime,
+        recurrence: ScheduleRecurrence,
+    ) -> Result<AntitheftRule, Error> {
+        debug!("Add Antitheft rule...");
+        let params = AntitheftRuleRaw::new(None, true, start, end, &recurrence)?;
+        let request = TapoRequest::AddAntitheftRule(Box::new(TapoParams::new(params)));
+
+        let result = self
+            .execute_request::<AddAntitheftRuleResult>(request)
+            .await?
+            .ok_or_else(|| Error::Tapo(TapoResponseError::EmptyResult))?;
+
+        Ok(AntitheftRule {
+            id: result.id,
+            enabled: true,
+            start,
+            end,
+            recurrence,
+        })
+    }
+
+    pub(crate) async fn edit_antitheft_rule(&self, rule: &AntitheftRule) -> Result<(), Error> {
+        debug!("Edit Antitheft rule {}...", rule.id);
+        let params = AntitheftRuleRaw::new(
+            Some(rule.id.clone()),
+            rule.enabled,
+            rule.start,
+            rule.end,
+            &rule.recurrence,
+        )?;
+        let request = TapoRequest::EditAntitheftRule(Box::new(TapoParams::new(params)));
+
+        self.execute_request::<serde_json::Value>(request).await?;
+
+        Ok(())
+    }
+
+    pub(crate) async fn remove_antitheft_rules(
+        &self,
+        params: RemoveRulesParams,
+    ) -> Result<(), Error> {
+        debug!("Remove Antitheft rules...");
+        let request = TapoRequest::RemoveAntitheftRules(TapoParams::new(params));
+
+        self.execute_request::<serde_json::Value>(request).await?;
+
+        Ok(())
+    }
+
+    pub(crate) async fn get_latest_firmware(&self) -> Result<LatestFirmwareResult, Error> {
+        debug!("Get Latest firmware...");
+        let request = TapoRequest::GetLatestFw(TapoParams::new(EmptyParams));
+
+        self.execute_request(request)
+            .await?
+            .ok_or_else(|| Error::Tapo(TapoResponseError::EmptyResult))
+    }
+
+    pub(crate) async fn get_fw_download_state(&self) -> Result<FirmwareDownloadStateResult, Error> {
+        debug!("Get Firmware download state...");
+        let request = TapoRequest::GetFwDownloadState(TapoParams::new(EmptyParams));
+
+        self.execute_request(request)
+            .await?
+            .ok_or_else(|| Error::Tapo(TapoResponseError::EmptyResult))
+    }
+
+    pub(crate) async fn fw_download(&self) -> Result<(), Error> {
+        debug!("Firmware download...");
+        let request = TapoRequest::FwDownload(TapoParams::new(EmptyParams));
+
+        self.execute_request::<serde_json::Value>(request).await?;
+
+        Ok(())
+    }
+
+    pub(crate) async fn get_led_info(&self) -> Result<LedInfoResult, Error> {
+        debug!("Get LED info...");
+        let request = TapoRequest::GetLedInfo(TapoParams::new(EmptyParams));
+
+        self.execute_request(request)
+            .await?
+            .ok_or_else(|| Error::Tapo(TapoResponseError::EmptyResult))
+    }
+
+    pub(crate) async fn set_led_info(&self, led_info: LedInfoResult) -> Result<(), Error> {
+        debug!("Set LED info: {led_info:?}");
+        let request = TapoRequest::SetLedInfo(Box::new(TapoParams::new(led_info)));
+
+        self.execute_request::<serde_json::Value>(request).await?;
+
+        Ok(())
+    }
+
+    pub(crate) async fn get_on_off_gradually_info(
+        &self,
+    ) -> Result<OnOffGraduallyInfoResult, Error> {
+        debug!("Get On/Off gradually info...");
+        let request = TapoRequest::GetOnOffGraduallyInfo(TapoParams::new(EmptyParams));
+
+        self.execute_request(request)
+            .await?
+            .ok_or_else(|| Error::Tapo(TapoResponseError::EmptyResult))
+    }
+
+    pub(crate) async fn set_on_off_gradually_info(
+        &self,
+        on_off_gradually_info: OnOffGraduallyInfoResult,
+    ) -> Result<(), Error> {
+        debug!("Set On/Off gradually info: {on_off_gradually_info:?}");
+        let current = if on_off_gradually_info.is_missing_max_duration() {
+            Some(self.get_on_off_gradually_info().await?)
+        } else {
+            None
+        };
+        on_off_gradually_info.validate(current.as_ref())?;
+
+        let request =
+            TapoRequest::SetOnOffGraduallyInfo(Box::new(TapoParams::new(on_off_gradually_info)));
+
+        self.execute_request::<serde_json::Value>(request).await?;
+
+        Ok(())
+    }
+
+    pub(crate) async fn get_power_protection(
+        &self,
+        child_device_id: Option<&str>,
+    ) -> Result<PowerProtectionResult, Error> {
+        debug!("Get Power protection...");
+        let request = TapoRequest::GetProtectionPower(TapoParams::new(EmptyParams));
+
+        self.execute_device_request(child_device_id, request)
+            .await?
+            .ok_or_else(|| Error::Tapo(TapoResponseError::EmptyResult))
+    }
+
+    pub(crate) async fn get_max_power(
+        &self,
+        child_device_id: Option<&str>,
+    ) -> Result<MaxPowerResult, Error> {
+        debug!("Get Max power...");
+        let request = TapoRequest::GetMaxPower(TapoParams::new(EmptyParams));
+
+        self.execute_device_request(child_device_id, request)
+            .await?
+            .ok_or_else(|| Error::Tapo(TapoResponseError::EmptyResult))
+    }
+
+    pub(crate) async fn set_power_protection(
+        &self,
+        child_device_id: Option<&str>,
+        protection_power: Option<u16>,
+    ) -> Result<(), Error> {
+        let params = match protection_power {
+            Some(protection_power) => {
+                let max_power = self.get_max_power(child_device_id).await?.max_power;
+                if protection_power == 0 || protection_power > max_power {
+                    return Err(Error::Validation {
+                        field: "protection_power".to_string(),
+                        message: format!("Must be between 1 and {max_power} W"),
+                    });
+                }
+
+                SetProtectionPowerParams::enable(protection_power)
+            }
+            None => SetProtectionPowerParams::disable(),
+        };
+
+        debug!("Set Power protection: {params:?}");
+        let request = TapoRequest::SetProtectionPower(TapoParams::new(params));
+
+        self.execute_device_request::<serde_json::Value>(child_device_id, request)
+            .await?;
+
+        Ok(())
+    }
+
+    pub(crate) async fn clear_power_protection_status(
+        &self,
+        child_device_id: Option<&str>,
+    ) -> Result<(), Error> {
+        debug!("Clear Power protection status...");
+        let request = set_device_info_request(
+            serde_json::json!({ "power_protection_status": PowerProtectionStatus::Normal }),
+        )?;
+
+        self.execute_device_request::<serde_json::Value>(child_device_id, request)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Executes `request` on the child device with the given ID, or on the device itself if `None`.
+    async fn execute_device_request<R>(
+        &self,
+        child_device_id: Option<&str>,
+        request: TapoRequest,
+    ) -> Result<Option<R>, Error>
+    where
+        R: fmt::Debug + DeserializeOwned + TapoResponseExt,
+    {
+        match child_device_id {
+            Some(device_id) => self.control_child(device_id.to_string(), request).await,
+            None => self.execute_request(request).await,
+        }
+    }
+
+    async fn execute_request<R>(&self, request: TapoRequest) -> Result<Option<R>, Error>
+    where
+        R: fmt::Debug + DeserializeOwned + TapoResponseExt,
+    {
+        let protocol = self.protocol()?;
+        let generation = protocol.session_generation();
+
+        match protocol.execute_request(&request).await {
+            Err(err) if self.session_refresh && err.is_session_expired() => {
+                debug!("Session expired ({err}), refreshing it and retrying the request...");
+
+                protocol
+                    .refresh_expired_session(
+                        generation,
+                        self.tapo_username.clone(),
+                        self.tapo_password.clone(),
+                    )
+                    .await?;
+
+                protocol.execute_request(&request).await
+            }
+            result => result,
+        }
+    }
+
     fn protocol_mut(&mut self) -> Result<&mut TapoProtocol, Error> {
         if self.protocol.is_none() {
             let timeout = self.timeout.unwrap_or_else(|| Duration::from_secs(30));
@@ -1315,14 +1987,9 @@ impl ApiClientExt for ApiClient {
     async fn set_device_info(&self, device_info_params: serde_json::Value) -> Result<(), Error> {
         debug!("Device info will change to: {device_info_params:?}");
 
-        let set_device_info_request = TapoRequest::SetDeviceInfo(Box::new(
-            TapoParams::new(device_info_params)
-                .set_request_time_mils()?
-                .set_terminal_uuid(TERMINAL_UUID),
-        ));
+        let set_device_info_request = set_device_info_request(device_info_params)?;
 
-        self.protocol()?
-            .execute_request::<TapoResult>(set_device_info_request)
+        self.execute_request::<TapoResult>(set_device_info_request)
             .await?;
 
         Ok(())
@@ -1332,9 +1999,7 @@ impl ApiClientExt for ApiClient {
         debug!("Device reboot...");
         let request = TapoRequest::DeviceReboot(TapoParams::new(DeviceRebootParams::new(delay)));
 
-        self.protocol()?
-            .execute_request::<serde_json::Value>(request)
-            .await?;
+        self.execute_request::<serde_json::Value>(request).await?;
 
         Ok(())
     }
@@ -1343,10 +2008,40 @@ impl ApiClientExt for ApiClient {
         debug!("Device reset...");
         let request = TapoRequest::DeviceReset(TapoParams::new(EmptyParams));
 
-        self.protocol()?
-            .execute_request::<serde_json::Value>(request)
-            .await?;
+        self.execute_request::<serde_json::Value>(request).await?;
 
         Ok(())
     }
+
+    /// The result is cached until the next login.
+    async fn get_color_temperature_range(&self) -> Result<Option<RangeInclusive<u16>>, Error> {
+        if let Some(range) = self.color_temperature_range.get() {
+            return Ok(range.clone());
+        }
+
+        let device_info = self.get_device_info::<serde_json::Value>().await?;
+        let range = match device_info["color_temp_range"]
+            .as_array()
+            .map(Vec::as_slice)
+        {
+            Some([min, max]) => min
+                .as_u64()
+                .zip(max.as_u64())
+                .and_then(|(min, max)| Some(u16::try_from(min).ok()?..=u16::try_from(max).ok()?)),
+            _ => None,
+        };
+
+        Ok(self.color_temperature_range.get_or_init(|| range).clone())
+    }
+}
+
+/// Builds the `set_device_info` request that changes the device info to `device_info_params`.
+pub(crate) fn set_device_info_request(
+    device_info_params: serde_json::Value,
+) -> Result<TapoRequest, Error> {
+    Ok(TapoRequest::SetDeviceInfo(Box::new(
+        TapoParams::new(device_info_params)
+            .set_request_time_mils()?
+            .set_terminal_uuid(TERMINAL_UUID),
+    )))
 }
@@ -0,0 +1,340 @@
+use std::marker::PhantomData;
+
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use tokio::sync::RwLock;
+
+use crate::api::ApiClient;
+use crate::api::api_client::set_device_info_request;
+use crate::error::{Error, TapoResponseError};
+use crate::requests::{
+    EmptyParams, EnergyDataInterval, GetEnergyDataParams, GetPowerDataParams, PowerDataInterval,
+    TapoParams, TapoRequest,
+};
+use crate::responses::{
+    CurrentPowerResult, DecodableResultExt, DeviceUsageEnergyMonitoringResult, DeviceUsageResult,
+    EnergyDataResult, EnergyDataResultRaw, EnergyUsageResult, PowerDataResult, PowerDataResultRaw,
+};
+
+use super::PlugEnergyMonitoringHandler;
+
+const DEFAULT_MAX_REQUESTS: usize = 5;
+
+/// Sends several requests to a device in a single round trip, using the `multipleRequest` method.
+///
+/// Created by the handlers' `batch` method. Every request that is added to the batch
+/// extends the result type, so that [`Batch::send`] returns a tuple with the typed result
+/// of each request, in the order they were added.
+///
+/// Devices only accept a limited number of requests in a single `multipleRequest`,
+/// so larger batches are split into several round trips.
+/// The limit defaults to 5 requests, see [`Batch::with_max_requests`].
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use tapo::ApiClient;
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let device = ApiClient::new("tapo-username@example.com", "tapo-password")
+///     .p110("192.168.1.100")
+///     .await?;
+///
+/// let (device_info, energy_usage, current_power) = device
+///     .batch()
+///     .get_device_info()
+///     .get_energy_usage()
+///     .get_current_power()
+///     .send()
+///     .await?;
+///
+/// println!("{}: {}W", device_info.nickname, current_power.current_power);
+/// println!("Today: {}Wh", energy_usage.today_energy);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+#[must_use = "a batch does nothing until it is sent"]
+pub struct Batch<'h, H, T = ()> {
+    client: &'h RwLock<ApiClient>,
+    requests: Vec<BatchRequest>,
+    max_requests: Option<usize>,
+    results: PhantomData<fn() -> (&'h H, T)>,
+}
+
+impl<'h, H> Batch<'h, H> {
+    pub(crate) fn new(client: &'h RwLock<ApiClient>) -> Self {
+        Self {
+            client,
+            requests: Vec::new(),
+            max_requests: None,
+            results: PhantomData,
+        }
+    }
+}
+
+impl<'h, H, T> Batch<'h, H, T> {
+    /// Sets the maximum number of requests sent in a single round trip.
+    ///
+    /// Defaults to 5.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_requests` - the number of requests the device accepts in a single `multipleRequest`.
+    ///   Values lower than 1 are treated as 1.
+    pub fn with_max_requests(mut self, max_requests: usize) -> Self {
+        self.max_requests = Some(max_requests.max(1));
+        self
+    }
+
+    /// Sends the requests and returns their results, in the order they were added.
+    ///
+    /// Fails if any of the requests fails.
+    pub async fn send(self) -> Result<T, Error>
+    where
+        T: private::BatchResults,
+    {
+        let requests = self
+            .requests
+            .into_iter()
+            .map(BatchRequest::build)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let max_requests = self.max_requests.unwrap_or(DEFAULT_MAX_REQUESTS);
+
+        let results = self
+            .client
+            .read()
+            .await
+            .execute_multiple_request(requests, max_requests)
+            .await?;
+
+        T::from_results(results)
+    }
+
+    pub(crate) fn push<R>(self, request: TapoRequest) -> Batch<'h, H, T::Output>
+    where
+        T: private::Append<R>,
+    {
+        self.push_request::<R>(BatchRequest::Request(request))
+    }
+
+    /// Adds a `set_device_info` request, built the same way as [`crate::ApiClientExt::set_device_info`]
+    /// when the batch is sent.
+    pub(crate) fn push_set_device_info<R>(self, params: Value) -> Batch<'h, H, T::Output>
+    where
+        T: private::Append<R>,
+    {
+        self.push_request::<R>(BatchRequest::SetDeviceInfo(params))
+    }
+
+    fn push_request<R>(mut self, request: BatchRequest) -> Batch<'h, H, T::Output>
+    where
+        T: private::Append<R>,
+    {
+        self.requests.push(request);
+
+        Batch {
+            client: self.client,
+            requests: self.requests,
+            max_requests: self.max_requests,
+            results: PhantomData,
+        }
+    }
+}
+
+impl<'h, T> Batch<'h, PlugEnergyMonitoringHandler, T> {
+    /// Adds a request for the *current power* as [`CurrentPowerResult`].
+    pub fn get_current_power(
+        self,
+    ) -> Batch<'h, PlugEnergyMonitoringHandler, <T as private::Append<CurrentPowerResult>>::Output>
+    where
+        T: private::Append<CurrentPowerResult>,
+    {
+        self.push::<CurrentPowerResult>(TapoRequest::GetCurrentPower(TapoParams::new(EmptyParams)))
+    }
+
+    /// Adds a request for the *energy usage* as [`EnergyUsageResult`].
+    pub fn get_energy_usage(
+        self,
+    ) -> Batch<'h, PlugEnergyMonitoringHandler, <T as private::Append<EnergyUsageResult>>::Output>
+    where
+        T: private::Append<EnergyUsageResult>,
+    {
+        self.push::<EnergyUsageResult>(TapoRequest::GetEnergyUsage(TapoParams::new(EmptyParams)))
+    }
+
+    /// Adds a request for the *energy data* as [`EnergyDataResult`].
+    pub fn get_energy_data(
+        self,
+        interval: EnergyDataInterval,
+    ) -> Batch<'h, PlugEnergyMonitoringHandler, <T as private::Append<EnergyDataResult>>::Output>
+    where
+        T: private::Append<EnergyDataResult>,
+    {
+        self.push::<EnergyDataResult>(TapoRequest::GetEnergyData(TapoParams::new(
+            GetEnergyDataParams::new(interval),
+        )))
+    }
+
+    /// Adds a request for the *power data* as [`PowerDataResult`].
+    pub fn get_power_data(
+        self,
+        interval: PowerDataInterval,
+    ) -> Batch<'h, PlugEnergyMonitoringHandler, <T as private::Append<PowerDataResult>>::Output>
+    where
+        T: private::Append<PowerDataResult>,
+    {
+        self.push::<PowerDataResult>(TapoRequest::GetPowerData(TapoParams::new(
+            GetPowerDataParams::new(interval),
+        )))
+    }
+}
+
+/// A request of a [`Batch`].
+#[derive(Debug)]
+enum BatchRequest {
+    Request(TapoRequest),
+    /// `set_device_info` is stamped with the request time, so it's only built when the batch is sent.
+    SetDeviceInfo(Value),
+}
+
+impl BatchRequest {
+    fn build(self) -> Result<TapoRequest, Error> {
+        match self {
+            Self::Request(request) => Ok(request),
+            Self::SetDeviceInfo(params) => set_device_info_request(params),
+        }
+    }
+}
+
+pub(crate) mod private {
+    use super::*;
+
+    /// Converts the result of a single request of a [`Batch`].
+    pub trait BatchResult: Sized {
+        fn from_result(result: Option<Value>) -> Result<Self, Error>;
+    }
+
+    /// Converts the results of all the requests of a [`Batch`].
+    pub trait BatchResults: Sized {
+        fn from_results(results: Vec<Option<Value>>) -> Result<Self, Error>;
+    }
+
+    /// Extends the results of a [`Batch`] with the result of one more request.
+    pub trait Append<R> {
+        type Output;
+    }
+
+    impl<R> BatchResult for R
+    where
+        R: DeserializeOwned + DecodableResultExt,
+    {
+        fn from_result(result: Option<Value>) -> Result<Self, Error> {
+            deserialize::<R>(result)?.decode()
+        }
+    }
+
+    impl BatchResult for () {
+        fn from_result(_: Option<Value>) -> Result<Self, Error> {
+            Ok(())
+        }
+    }
+
+    macro_rules! impl_batch_result {
+        ($($result:ty),+) => {
+            $(
+                impl BatchResult for $result {
+                    fn from_result(result: Option<Value>) -> Result<Self, Error> {
+                        deserialize(result)
+                    }
+                }
+            )+
+        };
+    }
+
+    impl_batch_result!(
+        CurrentPowerResult,
+        DeviceUsageResult,
+        DeviceUsageEnergyMonitoringResult,
+        EnergyUsageResult
+    );
+
+    impl BatchResult for EnergyDataResult {
+        fn from_result(result: Option<Value>) -> Result<Self, Error> {
+            deserialize::<EnergyDataResultRaw>(result)?.try_into()
+        }
+    }
+
+    impl BatchResult for PowerDataResult {
+        fn from_result(result: Option<Value>) -> Result<Self, Error> {
+            deserialize::<PowerDataResultRaw>(result)?.try_into()
+        }
+    }
+
+    fn deserialize<R: DeserializeOwned>(result: Option<Value>) -> Result<R, Error> {
+        let result = result.ok_or_else(|| Error::Tapo(TapoResponseError::EmptyResult))?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    impl BatchResults for () {
+        fn from_results(_: Vec<Option<Value>>) -> Result<Self, Error> {
+            Ok(())
+        }
+    }
+
+    impl<R> Append<R> for () {
+        type Output = (R,);
+    }
+
+    macro_rules! impl_batch_results {
+        ($($result:ident),+) => {
+            impl<$($result: BatchResult),+> BatchResults for ($($result,)+) {
+                fn from_results(results: Vec<Option<Value>>) -> Result<Self, Error> {
+                    let mut results = results.into_iter();
+
+                    Ok(($(
+                        $result::from_result(
+                            results
+                                .next()
+                                .ok_or_else(|| Error::Tapo(TapoResponseError::EmptyResult))?,
+                        )?,
+                    )+))
+                }
+            }
+        };
+    }
+
+    macro_rules! impl_append {
+        ($($result:ident),+) => {
+            impl<$($result),+, R> Append<R> for ($($result,)+) {
+                type Output = ($($result,)+ R);
+            }
+        };
+    }
+
+    impl_batch_results!(A);
+    impl_batch_results!(A, B);
+    impl_batch_results!(A, B, C);
+    impl_batch_results!(A, B, C, D);
+    impl_batch_results!(A, B, C, D, E);
+    impl_batch_results!(A, B, C, D, E, F);
+    impl_batch_results!(A, B, C, D, E, F, G);
+    impl_batch_results!(A, B, C, D, E, F, G, H);
+    impl_batch_results!(A, B, C, D, E, F, G, H, I);
+    impl_batch_results!(A, B, C, D, E, F, G, H, I, J);
+    impl_batch_results!(A, B, C, D, E, F, G, H, I, J, K);
+    impl_batch_results!(A, B, C, D, E, F, G, H, I, J, K, L);
+
+    impl_append!(A);
+    impl_append!(A, B);
+    impl_append!(A, B, C);
+    impl_append!(A, B, C, D);
+    impl_append!(A, B, C, D, E);
+    impl_append!(A, B, C, D, E, F);
+    impl_append!(A, B, C, D, E, F, G);
+    impl_append!(A, B, C, D, E, F, G, H);
+    impl_append!(A, B, C, D, E, F, G, H, I);
+    impl_append!(A, B, C, D, E, F, G, H, I, J);
+    impl_append!(A, B, C, D, E, F, G, H, I, J, K);
+}
@@ -18,6 +18,8 @@ tapo_handler! {
     ip_address,
 }
 
+impl_watch!(CameraPtzHandler);
+
 impl CameraPtzHandler {
     /// Returns the RTSP stream URLs for the camera.
     ///
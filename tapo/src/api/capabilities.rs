@@ -0,0 +1,180 @@
+use std::ops::RangeInclusive;
+
+use async_trait::async_trait;
+
+use crate::error::Error;
+use crate::requests::Color;
+use crate::responses::{
+    CurrentPowerResult, EnergyUsageResult, FirmwareDownloadStateResult, LatestFirmwareResult,
+};
+
+/// Implemented by the handlers of devices that can be turned *on* and *off*.
+///
+/// The capability traits allow writing code that works with any device type,
+/// without having to match on every handler.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use tapo::{ApiClient, OnOff};
+/// async fn turn_off_all(devices: &[&dyn OnOff]) -> Result<(), tapo::Error> {
+///     for device in devices {
+///         device.off().await?;
+///     }
+///     Ok(())
+/// }
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = ApiClient::new("tapo-username@example.com", "tapo-password");
+/// let plug = client.clone().p100("192.168.1.100").await?;
+/// let light = client.l530("192.168.1.101").await?;
+///
+/// turn_off_all(&[&plug, &light]).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[async_trait]
+pub trait OnOff: Send + Sync {
+    /// Turns *on* the device.
+    async fn on(&self) -> Result<(), Error>;
+
+    /// Turns *off* the device.
+    async fn off(&self) -> Result<(), Error>;
+}
+
+/// Implemented by the handlers of devices whose *brightness* can be adjusted.
+#[async_trait]
+pub trait Dimmable: OnOff {
+    /// Sets the *brightness* and turns *on* the device.
+    ///
+    /// # Arguments
+    ///
+    /// * `brightness` - between 1 and 100
+    async fn set_brightness(&self, brightness: u8) -> Result<(), Error>;
+}
+
+/// Implemented by the handlers of devices whose *color* can be adjusted.
+#[async_trait]
+pub trait ColorControl: Dimmable {
+    /// Sets the *color* and turns *on* the device.
+    ///
+    /// # Arguments
+    ///
+    /// * `color` - one of [crate::requests::Color] as defined in the Google Home app
+    async fn set_color(&self, color: Color) -> Result<(), Error>;
+
+    /// Sets the *hue*, *saturation* and turns *on* the device.
+    ///
+    /// # Arguments
+    ///
+    /// * `hue` - between 0 and 360
+    /// * `saturation` - between 1 and 100
+    async fn set_hue_saturation(&self, hue: u16, saturation: u8) -> Result<(), Error>;
+
+    /// Sets the *color temperature* and turns *on* the device.
+    ///
+    /// # Arguments
+    ///
+    /// * `color_temperature` - between 2500 and 6500
+    async fn set_color_temperature(&self, color_temperature: u16) -> Result<(), Error>;
+
+    /// Returns the range of *color temperatures* supported by the device, in Kelvin.
+    async fn get_color_temperature_range(&self) -> Result<RangeInclusive<u16>, Error>;
+}
+
+/// Implemented by the handlers of devices that monitor their energy consumption.
+#[async_trait]
+pub trait EnergyMeter: Send + Sync {
+    /// Returns *current power* as [`CurrentPowerResult`].
+    async fn get_current_power(&self) -> Result<CurrentPowerResult, Error>;
+
+    /// Returns *energy usage* as [`EnergyUsageResult`].
+    async fn get_energy_usage(&self) -> Result<EnergyUsageResult, Error>;
+}
+
+/// Implemented by the handlers of devices whose firmware can be updated.
+///
+/// See [`FirmwareRollout`](crate::FirmwareRollout) for updating several devices at once.
+#[async_trait]
+pub trait Firmware: Send + Sync {
+    /// Returns the *latest firmware* available for the device as [`LatestFirmwareResult`].
+    async fn get_latest_firmware(&self) -> Result<LatestFirmwareResult, Error>;
+
+    /// Returns the progress of the firmware update as [`FirmwareDownloadStateResult`].
+    async fn get_fw_download_state(&self) -> Result<FirmwareDownloadStateResult, Error>;
+
+    /// Starts downloading and installing the latest firmware.
+    /// The device reboots once the firmware is installed.
+    async fn fw_download(&self) -> Result<(), Error>;
+}
+
+/// Implements [`Dimmable`] by delegating to the handler's inherent methods.
+macro_rules! impl_dimmable {
+    ($name:ident) => {
+        #[async_trait::async_trait]
+        impl crate::api::Dimmable for $name {
+            async fn set_brightness(&self, brightness: u8) -> Result<(), crate::error::Error> {
+                $name::set_brightness(self, brightness).await
+            }
+        }
+    };
+}
+
+/// Implements [`Dimmable`] and [`ColorControl`] by delegating to the handler's inherent methods.
+macro_rules! impl_color_control {
+    ($name:ident) => {
+        impl_dimmable!($name);
+
+        #[async_trait::async_trait]
+        impl crate::api::ColorControl for $name {
+            async fn set_color(
+                &self,
+                color: crate::requests::Color,
+            ) -> Result<(), crate::error::Error> {
+                $name::set_color(self, color).await
+            }
+
+            async fn set_hue_saturation(
+                &self,
+                hue: u16,
+                saturation: u8,
+            ) -> Result<(), crate::error::Error> {
+                $name::set_hue_saturation(self, hue, saturation).await
+            }
+
+            async fn set_color_temperature(
+                &self,
+                color_temperature: u16,
+            ) -> Result<(), crate::error::Error> {
+                $name::set_color_temperature(self, color_temperature).await
+            }
+
+            async fn get_color_temperature_range(
+                &self,
+            ) -> Result<std::ops::RangeInclusive<u16>, crate::error::Error> {
+                $name::get_color_temperature_range(self).await
+            }
+        }
+    };
+}
+
+/// Implements [`EnergyMeter`] by delegating to the handler's inherent methods.
+macro_rules! impl_energy_meter {
+    ($name:ident) => {
+        #[async_trait::async_trait]
+        impl crate::api::EnergyMeter for $name {
+            async fn get_current_power(
+                &self,
+            ) -> Result<crate::responses::CurrentPowerResult, crate::error::Error> {
+                $name::get_current_power(self).await
+            }
+
+            async fn get_energy_usage(
+                &self,
+            ) -> Result<crate::responses::EnergyUsageResult, crate::error::Error> {
+                $name::get_energy_usage(self).await
+            }
+        }
+    };
+}
@@ -1,13 +1,15 @@
 use crate::error::Error;
-use crate::requests::TemperatureUnitKE100;
+use crate::requests::{RemoveRulesParams, ScheduleRecurrence, ScheduleTime, TemperatureUnitKE100};
 use crate::requests::{TapoParams, TapoRequest, TrvSetDeviceInfoParams};
-use crate::responses::KE100Result;
+use crate::responses::{KE100Result, TrvScheduleRule, TrvScheduleRules};
 
 tapo_child_handler! {
     /// Handler for the [KE100](https://www.tp-link.com/en/search/?q=KE100) devices.
     KE100Handler(KE100Result),
 }
 
+impl_watch!(KE100Handler);
+
 impl KE100Handler {
     /// Sets *child protection* on the device to *on* or *off*.
     ///
@@ -156,4 +158,103 @@ impl KE100Handler {
 
         Ok(())
     }
+
+    /// Returns the *weekly heating schedule* as [`TrvScheduleRules`].
+    pub async fn get_schedule_rules(&self) -> Result<TrvScheduleRules, Error> {
+        self.client
+            .read()
+            .await
+            .get_trv_schedule_rules(&self.device_id)
+            .await
+    }
+
+    /// Adds a rule to the weekly heating schedule that changes the target temperature
+    /// at the given `time`, and returns it with its device-assigned `id`.
+    ///
+    /// # Arguments
+    ///
+    /// * `time` - when the rule fires during the day
+    /// * `recurrence` - on which days the rule fires
+    /// * `target_temperature` - between `min_control_temperature` and `max_control_temperature`,
+    ///   e.g. `21.5`
+    /// * `unit`
+    pub async fn add_schedule_rule(
+        &self,
+        time: ScheduleTime,
+        recurrence: ScheduleRecurrence,
+        target_temperature: f32,
+        unit: TemperatureUnitKE100,
+    ) -> Result<TrvScheduleRule, Error> {
+        self.validate_schedule_temperature(target_temperature, &unit)
+            .await?;
+
+        self.client
+            .read()
+            .await
+            .add_trv_schedule_rule(&self.device_id, time, recurrence, target_temperature, unit)
+            .await
+    }
+
+    /// Replaces the schedule rule with the same `id`.
+    ///
+    /// # Arguments
+    ///
+    /// * `rule` - the updated rule, usually obtained from [`KE100Handler::get_schedule_rules`]
+    pub async fn edit_schedule_rule(&self, rule: &TrvScheduleRule) -> Result<(), Error> {
+        self.validate_schedule_temperature(rule.target_temperature, &rule.temperature_unit)
+            .await?;
+
+        self.client
+            .read()
+            .await
+            .edit_trv_schedule_rule(&self.device_id, rule)
+            .await
+    }
+
+    /// Removes the schedule rules with the given `ids`.
+    ///
+    /// # Arguments
+    ///
+    /// * `ids` - the `id`s of the rules to remove
+    pub async fn remove_schedule_rules(&self, ids: Vec<String>) -> Result<(), Error> {
+        self.client
+            .read()
+            .await
+            .remove_schedule_rules(Some(&self.device_id), RemoveRulesParams::remove(ids))
+            .await
+    }
+
+    /// Removes all the schedule rules.
+    pub async fn remove_all_schedule_rules(&self) -> Result<(), Error> {
+        self.client
+            .read()
+            .await
+            .remove_schedule_rules(Some(&self.device_id), RemoveRulesParams::remove_all())
+            .await
+    }
+
+    async fn validate_schedule_temperature(
+        &self,
+        target_temperature: f32,
+        unit: &TemperatureUnitKE100,
+    ) -> Result<(), Error> {
+        let device_info = self.get_device_info().await?;
+        let range = f32::from(device_info.min_control_temperature)
+            ..=f32::from(device_info.max_control_temperature);
+
+        // The control temperatures are reported in the device's unit.
+        let target_temperature = unit.convert(target_temperature, &device_info.temperature_unit);
+
+        if !range.contains(&target_temperature) {
+            return Err(Error::Validation {
+                field: "target_temperature".to_string(),
+                message: format!(
+                    "Target temperature must be between {} (min_control_temperature) and {} (max_control_temperature)",
+                    device_info.min_control_temperature, device_info.max_control_temperature
+                ),
+            });
+        }
+
+        Ok(())
+    }
 }
@@ -5,7 +5,8 @@ use crate::requests::{
 };
 use crate::responses::{
     CurrentPowerResult, DeviceUsageEnergyMonitoringResult, EnergyDataResult, EnergyDataResultRaw,
-    EnergyUsageResult, PowerDataResult, PowerDataResultRaw, PowerStripPlugEnergyMonitoringResult,
+    EnergyUsageResult, MaxPowerResult, PowerDataResult, PowerDataResultRaw, PowerProtectionResult,
+    PowerStripPlugEnergyMonitoringResult,
 };
 
 tapo_child_handler! {
@@ -15,6 +16,10 @@ tapo_child_handler! {
     on_off,
 }
 
+impl_energy_meter!(PowerStripPlugEnergyMonitoringHandler);
+impl_watch!(PowerStripPlugEnergyMonitoringHandler, current_power);
+impl_schedule_rules!(PowerStripPlugEnergyMonitoringHandler, child);
+
 impl PowerStripPlugEnergyMonitoringHandler {
     /// Returns *current power* as [`CurrentPowerResult`].
     pub async fn get_current_power(&self) -> Result<CurrentPowerResult, Error> {
@@ -85,4 +90,54 @@ impl PowerStripPlugEnergyMonitoringHandler {
             .ok_or_else(|| Error::Tapo(TapoResponseError::EmptyResult))
             .map(|result| result.try_into())?
     }
+
+    /// Returns the *power protection* settings as [`PowerProtectionResult`].
+    pub async fn get_power_protection(&self) -> Result<PowerProtectionResult, Error> {
+        self.client
+            .read()
+            .await
+            .get_power_protection(Some(&self.device_id))
+            .await
+    }
+
+    /// Returns the *maximum power* the plug supports as [`MaxPowerResult`].
+    pub async fn get_max_power(&self) -> Result<MaxPowerResult, Error> {
+        self.client
+            .read()
+            .await
+            .get_max_power(Some(&self.device_id))
+            .await
+    }
+
+    /// Enables power protection: the plug turns itself *off* once the load exceeds `protection_power`.
+    ///
+    /// # Arguments
+    ///
+    /// * `protection_power` - the power limit in Watts (W), between 1 and the plug's *maximum power*
+    pub async fn set_power_protection(&self, protection_power: u16) -> Result<(), Error> {
+        self.client
+            .read()
+            .await
+            .set_power_protection(Some(&self.device_id), Some(protection_power))
+            .await
+    }
+
+    /// Disables power protection.
+    pub async fn disable_power_protection(&self) -> Result<(), Error> {
+        self.client
+            .read()
+            .await
+            .set_power_protection(Some(&self.device_id), None)
+            .await
+    }
+
+    /// Resets a tripped `power_protection_status` from `overloaded` back to `normal`,
+    /// so that the plug can be turned *on* again.
+    pub async fn clear_power_protection_status(&self) -> Result<(), Error> {
+        self.client
+            .read()
+            .await
+            .clear_power_protection_status(Some(&self.device_id))
+            .await
+    }
 }
@@ -6,3 +6,6 @@ tapo_child_handler! {
     PowerStripPlugHandler(PowerStripPlugResult),
     on_off,
 }
+
+impl_watch!(PowerStripPlugHandler);
+impl_schedule_rules!(PowerStripPlugHandler, child);
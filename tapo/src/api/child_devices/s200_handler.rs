@@ -8,6 +8,8 @@ tapo_child_handler! {
     S200Handler(S200Result),
 }
 
+impl_watch!(S200Handler);
+
 impl S200Handler {
     /// Returns a list of *trigger logs*.
     ///
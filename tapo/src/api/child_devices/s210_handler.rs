@@ -8,6 +8,8 @@ tapo_child_handler! {
     on_off,
 }
 
+impl_watch!(S210Handler);
+
 impl S210Handler {
     /// Returns *device usage* as [`DeviceUsageResult`].
     pub async fn get_device_usage(&self) -> Result<DeviceUsageResult, Error> {
@@ -7,6 +7,8 @@ tapo_child_handler! {
     T100Handler(T100Result),
 }
 
+impl_watch!(T100Handler);
+
 impl T100Handler {
     /// Returns a list of *trigger logs*.
     ///
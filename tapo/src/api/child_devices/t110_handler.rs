@@ -7,6 +7,8 @@ tapo_child_handler! {
     T110Handler(T110Result),
 }
 
+impl_watch!(T110Handler);
+
 impl T110Handler {
     /// Returns a list of *trigger logs*.
     ///
@@ -7,6 +7,8 @@ tapo_child_handler! {
     T300Handler(T300Result),
 }
 
+impl_watch!(T300Handler);
+
 impl T300Handler {
     /// Returns a list of *trigger logs*.
     ///
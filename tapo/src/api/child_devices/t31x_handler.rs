@@ -7,6 +7,8 @@ tapo_child_handler! {
     T31XHandler(T31XResult),
 }
 
+impl_watch!(T31XHandler);
+
 impl T31XHandler {
     /// Returns *temperature and humidity records* from the last 24 hours at 15 minute intervals as [`TemperatureHumidityRecords`].
     pub async fn get_temperature_humidity_records(
@@ -1,6 +1,11 @@
+use std::ops::RangeInclusive;
+
+use crate::api::ApiClientExt as _;
 use crate::error::Error;
-use crate::requests::{Color, ColorLightSetDeviceInfoParams};
-use crate::responses::{DeviceInfoColorLightResult, DeviceUsageEnergyMonitoringResult};
+use crate::requests::{Color, ColorLightSetDeviceInfoParams, DEFAULT_COLOR_TEMPERATURE_RANGE};
+use crate::responses::{
+    DefaultColorLightState, DeviceInfoColorLightResult, DeviceUsageEnergyMonitoringResult,
+};
 
 tapo_handler! {
     /// Handler for the [L530](https://www.tapo.com/en/search/?q=L530),
@@ -12,6 +17,13 @@ tapo_handler! {
     device_management,
 }
 
+impl_color_control!(ColorLightHandler);
+impl_watch!(ColorLightHandler);
+impl_schedule_rules!(ColorLightHandler);
+impl_antitheft_rules!(ColorLightHandler);
+impl_device_settings!(ColorLightHandler, DefaultColorLightState);
+impl_on_off_gradually!(ColorLightHandler);
+
 impl ColorLightHandler {
     /// Returns a [`ColorLightSetDeviceInfoParams`] builder that allows multiple properties to be set in a single request.
     /// [`ColorLightSetDeviceInfoParams::send`] must be called at the end to apply the changes.
@@ -87,4 +99,18 @@ impl ColorLightHandler {
             .send(self)
             .await
     }
+
+    /// Returns the range of *color temperatures* supported by the device, in Kelvin.
+    /// Pass it to [`ColorLightSetDeviceInfoParams::color_temperature_range`] to validate or clamp the color temperature.
+    /// The range is read from the device info once per session, [`ColorLightSetDeviceInfoParams::kelvin`] uses it as well.
+    pub async fn get_color_temperature_range(&self) -> Result<RangeInclusive<u16>, Error> {
+        let range = self
+            .client
+            .read()
+            .await
+            .get_color_temperature_range()
+            .await?;
+
+        Ok(range.unwrap_or(DEFAULT_COLOR_TEMPERATURE_RANGE))
+    }
 }
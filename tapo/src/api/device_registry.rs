@@ -0,0 +1,313 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use log::debug;
+use tokio_stream::{Stream, StreamExt as _};
+
+use crate::{ApiClient, DiscoveryError, DiscoveryResult, Error};
+
+const DEFAULT_IDLE_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+/// Pool of logged-in devices, keyed by their device ID.
+///
+/// Logging into a device requires a handshake that can take a few seconds.
+/// The registry keeps the [`DiscoveryResult`] of every device it has connected to,
+/// so that subsequent lookups reuse the existing session instead of logging in again.
+///
+/// * Devices are looked up by ID and IP address with [`DeviceRegistry::get`],
+///   or by IP address only with [`DeviceRegistry::get_by_ip`].
+/// * When a device is seen at a different IP address, either through
+///   [`DeviceRegistry::get`] or [`DeviceRegistry::discover`], the cached entry is replaced.
+/// * Devices that haven't been used for longer than the idle timeout are evicted
+///   and logged into again on the next lookup.
+///
+/// The returned [`DiscoveryResult`]s are clones that share the cached session.
+/// Their `device_info` reflects the state of the device at the time it was connected to;
+/// use the handler's `get_device_info` for the current state.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use tapo::{ApiClient, DeviceRegistry};
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = ApiClient::new("tapo-username@example.com", "tapo-password");
+/// let registry = DeviceRegistry::new(client);
+///
+/// // The first lookup logs into the device...
+/// let device = registry.get("device-id", "192.168.1.100").await?;
+/// // ...while subsequent lookups reuse the session.
+/// let device = registry.get("device-id", "192.168.1.100").await?;
+///
+/// if let Some(device) = device.as_on_off() {
+///     device.on().await?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct DeviceRegistry {
+    client: ApiClient,
+    idle_timeout: Duration,
+    devices: Mutex<HashMap<String, RegistryEntry>>,
+}
+
+#[derive(Debug)]
+struct RegistryEntry {
+    device: DiscoveryResult,
+    last_used: Instant,
+}
+
+impl DeviceRegistry {
+    /// Creates a new registry that logs into devices with the given [`ApiClient`].
+    ///
+    /// Note: Devices are evicted after 10 minutes of inactivity.
+    /// Use [`DeviceRegistry::with_idle_timeout`] to change it.
+    pub fn new(client: ApiClient) -> Self {
+        Self {
+            client,
+            idle_timeout: DEFAULT_IDLE_TIMEOUT,
+            devices: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Changes the idle timeout from the default value to the given value.
+    ///
+    /// # Arguments
+    ///
+    /// * `idle_timeout` - how long a device can go unused before it's evicted.
+    pub fn with_idle_timeout(mut self, idle_timeout: Duration) -> Self {
+        self.idle_timeout = idle_timeout;
+        self
+    }
+
+    /// Returns the device with the given ID, logging into it at `ip_address`
+    /// unless a session to it at that address is already cached.
+    ///
+    /// Returns [`Error::DeviceNotFound`] if a different device is found at `ip_address`.
+    ///
+    /// # Arguments
+    ///
+    /// * `device_id` - the ID of the device.
+    /// * `ip_address` - the IP address of the device.
+    pub async fn get(
+        &self,
+        device_id: &str,
+        ip_address: impl Into<String>,
+    ) -> Result<DiscoveryResult, Error> {
+        let ip_address = ip_address.into();
+
+        if let Some(device) =
+            self.lookup(|device| device.device_id() == device_id && device.ip() == ip_address)
+        {
+            return Ok(device);
+        }
+
+        let device = self.connect(ip_address).await?;
+
+        if device.device_id() != device_id {
+            return Err(Error::DeviceNotFound);
+        }
+
+        Ok(device)
+    }
+
+    /// Returns the device at the given IP address, logging into it
+    /// unless a session to it is already cached.
+    ///
+    /// # Arguments
+    ///
+    /// * `ip_address` - the IP address of the device.
+    pub async fn get_by_ip(&self, ip_address: impl Into<String>) -> Result<DiscoveryResult, Error> {
+        let ip_address = ip_address.into();
+
+        if let Some(device) = self.lookup(|device| device.ip() == ip_address) {
+            return Ok(device);
+        }
+
+        self.connect(ip_address).await
+    }
+
+    /// Discovers the devices located at a specified unicast or broadcast IP address
+    /// and adds them to the registry as they are found, replacing the entries of devices
+    /// whose IP address has changed. See [`ApiClient::discover_devices`] for details.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The IP address at which the discovery will take place.
+    /// * `timeout_s` - The maximum time to wait for a response from the device(s) in seconds.
+    ///   Must be between `1` and `60`.
+    pub async fn discover(
+        &self,
+        target: impl Into<String>,
+        timeout_s: u64,
+    ) -> Result<impl Stream<Item = Result<DiscoveryResult, DiscoveryError>> + Unpin + '_, Error>
+    {
+        let discovery = self
+            .client
+            .clone()
+            .discover_devices(target, timeout_s)
+            .await?;
+
+        Ok(discovery.map(|result| {
+            if let Ok(device) = &result {
+                self.insert(device.clone());
+            }
+            result
+        }))
+    }
+
+    /// Adds a device to the registry, replacing any entry with the same device ID or IP address.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - the device to add.
+    pub fn insert(&self, device: DiscoveryResult) {
+        let mut devices = self.devices.lock().expect("device registry lock poisoned");
+
+        devices.retain(|device_id, entry| {
+            device_id != device.device_id() && entry.device.ip() != device.ip()
+        });
+
+        debug!(
+            "Registering device {} at {}",
+            device.device_id(),
+            device.ip()
+        );
+        devices.insert(
+            device.device_id().to_string(),
+            RegistryEntry {
+                device,
+                last_used: Instant::now(),
+            },
+        );
+    }
+
+    /// Removes the device with the given ID from the registry, returning it if it was present.
+    ///
+    /// # Arguments
+    ///
+    /// * `device_id` - the ID of the device.
+    pub fn remove(&self, device_id: &str) -> Option<DiscoveryResult> {
+        self.devices
+            .lock()
+            .expect("device registry lock poisoned")
+            .remove(device_id)
+            .map(|entry| entry.device)
+    }
+
+    /// Evicts the devices that haven't been used for longer than the idle timeout,
+    /// returning how many were evicted.
+    ///
+    /// Idle devices are also evicted lazily on lookup; calling this method periodically
+    /// releases their sessions sooner.
+    pub fn evict_idle(&self) -> usize {
+        let mut devices = self.devices.lock().expect("device registry lock poisoned");
+        let before = devices.len();
+        devices.retain(|_, entry| entry.last_used.elapsed() < self.idle_timeout);
+        before - devices.len()
+    }
+
+    /// Returns the number of devices in the registry.
+    pub fn len(&self) -> usize {
+        self.devices
+            .lock()
+            .expect("device registry lock poisoned")
+            .len()
+    }
+
+    /// Returns `true` if the registry contains no devices.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn lookup(&self, predicate: impl Fn(&DiscoveryResult) -> bool) -> Option<DiscoveryResult> {
+        let mut devices = self.devices.lock().expect("device registry lock poisoned");
+        devices.retain(|_, entry| entry.last_used.elapsed() < self.idle_timeout);
+
+        devices
+            .values_mut()
+            .find(|entry| predicate(&entry.device))
+            .map(|entry| {
+                entry.last_used = Instant::now();
+                entry.device.clone()
+            })
+    }
+
+    async fn connect(&self, ip_address: String) -> Result<DiscoveryResult, Error> {
+        let device = self.client.clone().connect(ip_address).await?;
+        self.insert(device.clone());
+        Ok(device)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::responses::DeviceInfoBasicResult;
+
+    use super::*;
+
+    fn device(device_id: &str, ip: &str) -> DiscoveryResult {
+        let device_info: DeviceInfoBasicResult = serde_json::from_value(serde_json::json!({
+            "avatar": "",
+            "device_id": device_id,
+            "fw_ver": "1.0.0",
+            "has_set_location_info": false,
+            "hw_ver": "1.0",
+            "mac": "00-00-00-00-00-00",
+            "model": "X100",
+            "oem_id": "",
+            "type": "SMART.TAPOPLUG",
+        }))
+        .unwrap();
+
+        DiscoveryResult::Other {
+            device_info: Box::new(device_info),
+            ip: ip.to_string(),
+        }
+    }
+
+    fn registry() -> DeviceRegistry {
+        DeviceRegistry::new(ApiClient::new("tapo-username", "tapo-password"))
+    }
+
+    #[tokio::test]
+    async fn get_returns_cached_device() {
+        let registry = registry();
+        registry.insert(device("device-1", "192.168.1.100"));
+
+        let device = registry.get("device-1", "192.168.1.100").await.unwrap();
+        assert_eq!(device.device_id(), "device-1");
+
+        let device = registry.get_by_ip("192.168.1.100").await.unwrap();
+        assert_eq!(device.device_id(), "device-1");
+    }
+
+    #[test]
+    fn insert_follows_ip_changes() {
+        let registry = registry();
+        registry.insert(device("device-1", "192.168.1.100"));
+        registry.insert(device("device-2", "192.168.1.101"));
+
+        registry.insert(device("device-1", "192.168.1.102"));
+        assert_eq!(registry.len(), 2);
+
+        registry.insert(device("device-3", "192.168.1.101"));
+        assert_eq!(registry.len(), 2);
+        assert!(registry.remove("device-2").is_none());
+        assert_eq!(
+            registry.remove("device-1").map(|d| d.ip().to_string()),
+            Some("192.168.1.102".to_string())
+        );
+    }
+
+    #[test]
+    fn evict_idle() {
+        let registry = registry().with_idle_timeout(Duration::ZERO);
+        registry.insert(device("device-1", "192.168.1.100"));
+
+        assert_eq!(registry.evict_idle(), 1);
+        assert!(registry.is_empty());
+    }
+}
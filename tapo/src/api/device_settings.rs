@@ -0,0 +1,95 @@
+use serde::Serialize;
+use serde_json::{Value, json};
+
+use crate::error::Error;
+
+/// Implements `set_default_state` with the given default state type, and the status LED methods.
+macro_rules! impl_device_settings {
+    ($name:ident, $default_state:ty) => {
+        impl $name {
+            /// Sets the *default state*, the state the device is in after it's powered on,
+            /// e.g. after a power outage.
+            ///
+            /// # Arguments
+            ///
+            #[doc = concat!("* `default_state` - the new default state, see the `default_states` field of [`get_device_info`](", stringify!($name), "::get_device_info)")]
+            pub async fn set_default_state(
+                &self,
+                default_state: $default_state,
+            ) -> Result<(), crate::error::Error> {
+                let json = crate::api::device_settings::default_states_params(&default_state)?;
+                crate::api::ApiClientExt::set_device_info(&*self.client.read().await, json).await
+            }
+
+            /// Returns the *status LED* settings as [`LedInfoResult`](crate::responses::LedInfoResult).
+            pub async fn get_led_info(
+                &self,
+            ) -> Result<crate::responses::LedInfoResult, crate::error::Error> {
+                self.client.read().await.get_led_info().await
+            }
+
+            /// Changes the *status LED* settings.
+            ///
+            /// # Arguments
+            ///
+            /// * `led_info` - the result of `get_led_info`, with the changes applied
+            pub async fn set_led_info(
+                &self,
+                led_info: crate::responses::LedInfoResult,
+            ) -> Result<(), crate::error::Error> {
+                self.client.read().await.set_led_info(led_info).await
+            }
+        }
+    };
+}
+
+/// Implements the gradual on/off methods of the lights.
+macro_rules! impl_on_off_gradually {
+    ($name:ident) => {
+        impl $name {
+            /// Returns the *gradual on/off* settings as
+            /// [`OnOffGraduallyInfoResult`](crate::responses::OnOffGraduallyInfoResult).
+            pub async fn get_on_off_gradually_info(
+                &self,
+            ) -> Result<crate::responses::OnOffGraduallyInfoResult, crate::error::Error> {
+                self.client.read().await.get_on_off_gradually_info().await
+            }
+
+            /// Changes the *gradual on/off* settings, i.e. whether and how slowly the light fades
+            /// in and out when it's turned *on* and *off*.
+            ///
+            /// # Arguments
+            ///
+            /// * `on_off_gradually_info` - the result of `get_on_off_gradually_info`, with the changes applied.
+            ///   Each `duration` must not exceed the `max_duration` reported by the device.
+            pub async fn set_on_off_gradually_info(
+                &self,
+                on_off_gradually_info: crate::responses::OnOffGraduallyInfoResult,
+            ) -> Result<(), crate::error::Error> {
+                self.client
+                    .read()
+                    .await
+                    .set_on_off_gradually_info(on_off_gradually_info)
+                    .await
+            }
+        }
+    };
+}
+
+/// Returns the `set_device_info` params that change the default state.
+///
+/// The default state types are shared with the device info responses,
+/// so their unset optional fields are left out instead of being sent as `null`.
+pub(crate) fn default_states_params<S: Serialize>(default_state: &S) -> Result<Value, Error> {
+    let mut default_states = serde_json::to_value(default_state)?;
+    remove_nulls(&mut default_states);
+
+    Ok(json!({ "default_states": default_states }))
+}
+
+fn remove_nulls(value: &mut Value) {
+    if let Value::Object(map) = value {
+        map.retain(|_, value| !value.is_null());
+        map.values_mut().for_each(remove_nulls);
+    }
+}
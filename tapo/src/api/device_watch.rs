@@ -0,0 +1,305 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context as TaskContext, Poll};
+use std::time::Duration;
+
+use log::debug;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use tokio::sync::mpsc::{self, Receiver, Sender};
+use tokio::task::JoinHandle;
+use tokio_stream::Stream;
+
+use crate::error::Error;
+
+/// Fields that change on (almost) every poll without the device state changing.
+const IGNORED_FIELDS: [&str; 5] = [
+    "jamming_rssi",
+    "jamming_signal_level",
+    "on_time",
+    "rssi",
+    "signal_level",
+];
+
+/// Implements `watch` by polling `get_device_info`, and `get_current_power` if specified.
+macro_rules! impl_watch {
+    ($name:ident) => {
+        impl $name {
+            /// Returns a [`DeviceWatch`](crate::DeviceWatch) stream of the changes to the *device info*,
+            /// polled every `interval`.
+            ///
+            /// # Arguments
+            ///
+            /// * `interval` - how often the device is polled
+            pub fn watch(&self, interval: std::time::Duration) -> crate::DeviceWatch {
+                let handler = self.clone();
+
+                crate::DeviceWatch::new(interval, move || {
+                    let handler = handler.clone();
+                    async move {
+                        let device_info = handler.get_device_info().await?;
+                        crate::api::device_watch::state(&device_info, None::<&()>)
+                    }
+                })
+            }
+        }
+    };
+    ($name:ident, current_power) => {
+        impl $name {
+            /// Returns a [`DeviceWatch`](crate::DeviceWatch) stream of the changes to the *device info*
+            /// and *current power*, polled every `interval`.
+            ///
+            /// # Arguments
+            ///
+            /// * `interval` - how often the device is polled
+            pub fn watch(&self, interval: std::time::Duration) -> crate::DeviceWatch {
+                let handler = self.clone();
+
+                crate::DeviceWatch::new(interval, move || {
+                    let handler = handler.clone();
+                    async move {
+                        let device_info = handler.get_device_info().await?;
+                        let current_power = handler.get_current_power().await?;
+                        crate::api::device_watch::state(&device_info, Some(&current_power))
+                    }
+                })
+            }
+        }
+    };
+}
+
+/// A change to a single field of the device state, as reported by [`DeviceWatch`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FieldChange {
+    /// The name of the field, e.g. `device_on` or `brightness`.
+    pub field: String,
+    /// The previous value, or [`Value::Null`] if the field wasn't present.
+    pub old: Value,
+    /// The current value, or [`Value::Null`] if the field is no longer present.
+    pub new: Value,
+}
+
+/// The fields of the device state that changed between two polls, as reported by [`DeviceWatch`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DeviceDiff {
+    /// The changed fields, in alphabetical order.
+    pub changes: Vec<FieldChange>,
+}
+
+impl DeviceDiff {
+    /// Returns the change to the given field, if it changed.
+    ///
+    /// # Arguments
+    ///
+    /// * `field` - the name of the field, e.g. `device_on`
+    pub fn get(&self, field: &str) -> Option<&FieldChange> {
+        self.changes.iter().find(|change| change.field == field)
+    }
+}
+
+/// A [`Stream`] of the changes to a device's state, created by the handlers' `watch` method.
+///
+/// The device is polled at the given interval and its state is compared field by field
+/// with the previous one. Only the polls that changed at least one field are reported.
+/// The first poll sets the baseline and isn't reported.
+///
+/// Fields that change on every poll without the device state changing,
+/// like `on_time` and the signal strength (`rssi`, `signal_level`), are ignored.
+///
+/// Polling errors are reported as `Err` items and the stream keeps polling at the next interval,
+/// comparing against the last successfully polled state.
+/// The polling stops when the stream is dropped.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use std::time::Duration;
+/// # use tapo::{ApiClient, StreamExt as _};
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let device = ApiClient::new("tapo-username@example.com", "tapo-password")
+///     .p110("192.168.1.100")
+///     .await?;
+///
+/// let mut changes = device.watch(Duration::from_secs(5));
+/// while let Some(diff) = changes.next().await {
+///     for change in diff?.changes {
+///         println!("{}: {} -> {}", change.field, change.old, change.new);
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct DeviceWatch {
+    rx: Receiver<Result<DeviceDiff, Error>>,
+    task: JoinHandle<()>,
+}
+
+impl DeviceWatch {
+    pub(crate) fn new<F, Fut>(interval: Duration, get_state: F) -> Self
+    where
+        F: FnMut() -> Fut + Send + 'static,
+        Fut: Future<Output = Result<Map<String, Value>, Error>> + Send + 'static,
+    {
+        let (tx, rx) = mpsc::channel(1024);
+        let task = tokio::spawn(poll(interval, get_state, tx));
+
+        Self { rx, task }
+    }
+}
+
+impl Stream for DeviceWatch {
+    type Item = Result<DeviceDiff, Error>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut TaskContext<'_>,
+    ) -> Poll<Option<Result<DeviceDiff, Error>>> {
+        Pin::new(&mut self.rx).poll_recv(cx)
+    }
+}
+
+impl Drop for DeviceWatch {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn poll<F, Fut>(interval: Duration, mut get_state: F, tx: Sender<Result<DeviceDiff, Error>>)
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<Map<String, Value>, Error>>,
+{
+    let mut interval = tokio::time::interval(interval);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    let mut previous: Option<Map<String, Value>> = None;
+
+    loop {
+        interval.tick().await;
+
+        let item = match get_state().await {
+            Ok(state) => {
+                let diff = previous.as_ref().map(|previous| diff(previous, &state));
+                previous = Some(state);
+
+                match diff {
+                    Some(diff) if !diff.changes.is_empty() => Ok(diff),
+                    _ => continue,
+                }
+            }
+            Err(e) => {
+                debug!("Failed to poll the device state: {e}");
+                Err(e)
+            }
+        };
+
+        if tx.send(item).await.is_err() {
+            return;
+        }
+    }
+}
+
+/// Returns the state that is compared between polls: the fields of `device_info`,
+/// merged with the fields of `current_power` if any.
+pub(crate) fn state<D, P>(
+    device_info: &D,
+    current_power: Option<&P>,
+) -> Result<Map<String, Value>, Error>
+where
+    D: Serialize,
+    P: Serialize,
+{
+    let mut state = to_map(device_info)?;
+
+    if let Some(current_power) = current_power {
+        state.extend(to_map(current_power)?);
+    }
+
+    state.retain(|field, _| !IGNORED_FIELDS.contains(&field.as_str()));
+
+    Ok(state)
+}
+
+fn to_map<T: Serialize>(value: &T) -> Result<Map<String, Value>, Error> {
+    match serde_json::to_value(value)? {
+        Value::Object(map) => Ok(map),
+        value => Ok(Map::from_iter([("value".to_string(), value)])),
+    }
+}
+
+fn diff(previous: &Map<String, Value>, current: &Map<String, Value>) -> DeviceDiff {
+    let mut fields = previous.keys().chain(current.keys()).collect::<Vec<_>>();
+    fields.sort();
+    fields.dedup();
+
+    let changes = fields
+        .into_iter()
+        .filter_map(|field| {
+            let old = previous.get(field).unwrap_or(&Value::Null);
+            let new = current.get(field).unwrap_or(&Value::Null);
+
+            (old != new).then(|| FieldChange {
+                field: field.clone(),
+                old: old.clone(),
+                new: new.clone(),
+            })
+        })
+        .collect();
+
+    DeviceDiff { changes }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn diff_reports_changed_added_and_removed_fields() {
+        let previous = to_map(&json!({ "device_on": false, "brightness": 50, "hue": 10 })).unwrap();
+        let current =
+            to_map(&json!({ "device_on": true, "brightness": 50, "saturation": 80 })).unwrap();
+
+        let diff = diff(&previous, &current);
+
+        assert_eq!(
+            diff.changes,
+            vec![
+                FieldChange {
+                    field: "device_on".to_string(),
+                    old: json!(false),
+                    new: json!(true),
+                },
+                FieldChange {
+                    field: "hue".to_string(),
+                    old: json!(10),
+                    new: Value::Null,
+                },
+                FieldChange {
+                    field: "saturation".to_string(),
+                    old: Value::Null,
+                    new: json!(80),
+                },
+            ]
+        );
+        assert_eq!(diff.get("device_on").unwrap().new, json!(true));
+        assert!(diff.get("brightness").is_none());
+    }
+
+    #[test]
+    fn state_merges_current_power_and_skips_ignored_fields() {
+        let state = state(
+            &json!({ "device_on": true, "on_time": 120, "rssi": -40 }),
+            Some(&json!({ "current_power": 12 })),
+        )
+        .unwrap();
+
+        assert_eq!(
+            Value::Object(state),
+            json!({ "device_on": true, "current_power": 12 })
+        );
+    }
+}
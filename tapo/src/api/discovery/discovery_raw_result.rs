@@ -49,6 +49,24 @@ impl DiscoveryRawResult {
         }
     }
 
+    /// Returns the port advertised in `mgt_encrypt_schm.http_port`,
+    /// or `None` when the device doesn't advertise one or it's the default port of the protocol.
+    pub(crate) fn port(&self) -> Option<u16> {
+        let default_port = match self.auth_protocol() {
+            AuthProtocol::AesSsl => 443,
+            _ => 80,
+        };
+
+        let port = self
+            .message
+            .get("result")
+            .and_then(|r| r.get("mgt_encrypt_schm"))
+            .and_then(|s| s["http_port"].as_u64())
+            .and_then(|p| u16::try_from(p).ok());
+
+        port.filter(|port| *port != default_port)
+    }
+
     pub(crate) fn auth_protocol(&self) -> AuthProtocol {
         let scheme = self
             .message
@@ -66,3 +84,73 @@ impl DiscoveryRawResult {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use super::*;
+
+    fn raw_result(mgt_encrypt_schm: Value) -> DiscoveryRawResult {
+        DiscoveryRawResult {
+            ip: IpAddr::V4(Ipv4Addr::new(192, 168, 1, 100)),
+            message: serde_json::json!({
+                "error_code": 0,
+                "result": {
+                    "device_id": "REDACTED",
+                    "device_model": "P110(EU)",
+                    "device_type": "SMART.TAPOPLUG",
+                    "factory_default": false,
+                    "ip": "192.168.1.100",
+                    "is_support_iot_cloud": true,
+                    "mac": "AA-BB-CC-DD-EE-FF",
+                    "mgt_encrypt_schm": mgt_encrypt_schm,
+                    "obd_src": "tplink",
+                    "owner": "REDACTED",
+                },
+            }),
+        }
+    }
+
+    #[test]
+    fn port_omits_the_default_port() {
+        let klap = raw_result(serde_json::json!({
+            "encrypt_type": "KLAP",
+            "http_port": 80,
+            "is_support_https": false,
+            "lv": 2,
+        }));
+        assert_eq!(klap.port(), None);
+
+        let aes_ssl = raw_result(serde_json::json!({
+            "encrypt_type": "3",
+            "http_port": 443,
+            "is_support_https": true,
+        }));
+        assert_eq!(aes_ssl.port(), None);
+
+        let no_port = raw_result(serde_json::json!({
+            "encrypt_type": "AES",
+            "is_support_https": false,
+        }));
+        assert_eq!(no_port.port(), None);
+    }
+
+    #[test]
+    fn port_is_an_advertised_non_default_port() {
+        let klap = raw_result(serde_json::json!({
+            "encrypt_type": "KLAP",
+            "http_port": 8080,
+            "is_support_https": false,
+            "lv": 2,
+        }));
+        assert_eq!(klap.port(), Some(8080));
+
+        let aes_ssl = raw_result(serde_json::json!({
+            "encrypt_type": "3",
+            "http_port": 80,
+            "is_support_https": true,
+        }));
+        assert_eq!(aes_ssl.port(), Some(80));
+    }
+}
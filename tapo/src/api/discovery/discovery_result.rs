@@ -7,18 +7,21 @@ use crate::responses::{
     DeviceInfoRgbicLightStripResult,
 };
 use crate::{
-    ApiClient, CameraPtzHandler, ColorLightHandler, Error, HubHandler, LightHandler,
-    PlugEnergyMonitoringHandler, PlugHandler, PowerStripEnergyMonitoringHandler, PowerStripHandler,
-    RgbLightStripHandler, RgbicLightStripHandler,
+    ApiClient, CameraPtzHandler, ColorControl, ColorLightHandler, Dimmable, EnergyMeter, Error,
+    Firmware, HubHandler, LightHandler, OnOff, PlugEnergyMonitoringHandler, PlugHandler,
+    PowerStripEnergyMonitoringHandler, PowerStripHandler, RgbLightStripHandler,
+    RgbicLightStripHandler,
 };
 
-use crate::api::protocol::DeviceFamily;
+use crate::api::protocol::{AuthProtocol, DeviceFamily};
 
 use super::DeviceType;
 use super::discovery_raw_result::DiscoveryRawResult;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 /// Result of the device discovery process.
+///
+/// Clones share the logged-in session of the original handler.
 pub enum DiscoveryResult {
     /// Tapo L510, L520 and L610 devices.
     Light {
@@ -139,15 +142,34 @@ macro_rules! map_device_model {
 
 impl DiscoveryResult {
     pub(crate) async fn new(
-        mut client: ApiClient,
+        client: ApiClient,
         raw_result: DiscoveryRawResult,
     ) -> Result<Self, Error> {
-        let device_family = raw_result.device_family();
-        let auth_protocol = raw_result.auth_protocol();
+        Self::connect(
+            client,
+            raw_result.ip.to_string(),
+            raw_result.port(),
+            raw_result.device_family(),
+            raw_result.auth_protocol(),
+        )
+        .await
+    }
+
+    /// Logs into the device at `ip`, on `port` if it isn't the default port of the protocol,
+    /// and maps it to the matching variant based on the model reported by `get_device_info`.
+    pub(crate) async fn connect(
+        mut client: ApiClient,
+        ip: String,
+        port: Option<u16>,
+        device_family: DeviceFamily,
+        auth_protocol: AuthProtocol,
+    ) -> Result<Self, Error> {
+        let address = match port {
+            Some(port) => format!("{ip}:{port}"),
+            None => ip.clone(),
+        };
 
-        client
-            .login(raw_result.ip.to_string(), device_family, auth_protocol)
-            .await?;
+        client.login(address, device_family, auth_protocol).await?;
         let device_info: serde_json::Value = client.get_device_info().await?;
 
         let client = std::sync::Arc::new(tokio::sync::RwLock::new(client));
@@ -238,8 +260,8 @@ impl DiscoveryResult {
                 device_info: Box::new(serde_json::from_value::<DeviceInfoCameraResult>(
                     device_info,
                 )?),
-                handler: CameraPtzHandler::new(client.clone(), raw_result.ip.to_string()),
-                ip: raw_result.ip.to_string(),
+                handler: CameraPtzHandler::new(client.clone(), ip.clone()),
+                ip,
             },
             DeviceType::Other => {
                 let info: DeviceInfoBasicResult = serde_json::from_value(device_info)?;
@@ -249,7 +271,7 @@ impl DiscoveryResult {
                 };
                 DiscoveryResult::Other {
                     device_info: Box::new(info),
-                    ip: raw_result.ip.to_string(),
+                    ip,
                 }
             }
         };
@@ -352,4 +374,66 @@ impl DiscoveryResult {
                 .unwrap_or(DeviceType::Other.as_str()),
         }
     }
+
+    /// Returns the handler as [`OnOff`] if the device can be turned on and off.
+    pub fn as_on_off(&self) -> Option<&dyn OnOff> {
+        match self {
+            DiscoveryResult::Light { handler, .. } => Some(handler),
+            DiscoveryResult::ColorLight { handler, .. } => Some(handler),
+            DiscoveryResult::RgbLightStrip { handler, .. } => Some(handler),
+            DiscoveryResult::RgbicLightStrip { handler, .. } => Some(handler),
+            DiscoveryResult::Plug { handler, .. } => Some(handler),
+            DiscoveryResult::PlugEnergyMonitoring { handler, .. } => Some(handler),
+            DiscoveryResult::PowerStrip { .. }
+            | DiscoveryResult::PowerStripEnergyMonitoring { .. }
+            | DiscoveryResult::Hub { .. }
+            | DiscoveryResult::CameraPtz { .. }
+            | DiscoveryResult::Other { .. } => None,
+        }
+    }
+
+    /// Returns the handler as [`Dimmable`] if the device's brightness can be adjusted.
+    pub fn as_dimmable(&self) -> Option<&dyn Dimmable> {
+        match self {
+            DiscoveryResult::Light { handler, .. } => Some(handler),
+            DiscoveryResult::ColorLight { handler, .. } => Some(handler),
+            DiscoveryResult::RgbLightStrip { handler, .. } => Some(handler),
+            DiscoveryResult::RgbicLightStrip { handler, .. } => Some(handler),
+            _ => None,
+        }
+    }
+
+    /// Returns the handler as [`ColorControl`] if the device's color can be adjusted.
+    pub fn as_color_control(&self) -> Option<&dyn ColorControl> {
+        match self {
+            DiscoveryResult::ColorLight { handler, .. } => Some(handler),
+            DiscoveryResult::RgbLightStrip { handler, .. } => Some(handler),
+            DiscoveryResult::RgbicLightStrip { handler, .. } => Some(handler),
+            _ => None,
+        }
+    }
+
+    /// Returns the handler as [`Firmware`] if the device's firmware can be updated.
+    pub fn as_firmware(&self) -> Option<&dyn Firmware> {
+        match self {
+            DiscoveryResult::Light { handler, .. } => Some(handler),
+            DiscoveryResult::ColorLight { handler, .. } => Some(handler),
+            DiscoveryResult::RgbLightStrip { handler, .. } => Some(handler),
+            DiscoveryResult::RgbicLightStrip { handler, .. } => Some(handler),
+            DiscoveryResult::Plug { handler, .. } => Some(handler),
+            DiscoveryResult::PlugEnergyMonitoring { handler, .. } => Some(handler),
+            DiscoveryResult::PowerStrip { handler, .. } => Some(handler),
+            DiscoveryResult::PowerStripEnergyMonitoring { handler, .. } => Some(handler),
+            DiscoveryResult::Hub { handler, .. } => Some(handler),
+            DiscoveryResult::CameraPtz { .. } | DiscoveryResult::Other { .. } => None,
+        }
+    }
+
+    /// Returns the handler as [`EnergyMeter`] if the device monitors its energy consumption.
+    pub fn as_energy_meter(&self) -> Option<&dyn EnergyMeter> {
+        match self {
+            DiscoveryResult::PlugEnergyMonitoring { handler, .. } => Some(handler),
+            _ => None,
+        }
+    }
 }
@@ -0,0 +1,426 @@
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context as TaskContext, Poll};
+use std::time::Duration;
+
+use log::debug;
+use tokio::sync::Semaphore;
+use tokio::sync::mpsc::{self, Receiver, Sender};
+use tokio::task::{JoinHandle, JoinSet};
+use tokio::time::Instant;
+use tokio_stream::{Stream, StreamExt as _};
+
+use crate::error::{DiscoveryError, Error, FirmwareError};
+use crate::responses::{FirmwareDownloadStatus, LatestFirmwareResult};
+
+use super::DiscoveryResult;
+
+const DEFAULT_MAX_CONCURRENT: usize = 4;
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(15 * 60);
+
+/// Checks the firmware of several devices and rolls out the updates,
+/// a limited number of devices at a time.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use tapo::{ApiClient, FirmwareRollout, StreamExt as _};
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = ApiClient::new("tapo-username@example.com", "tapo-password");
+/// let discovery = client.discover_devices("192.168.1.255", 10).await?;
+///
+/// let rollout = FirmwareRollout::new().with_max_concurrent(2);
+/// let report = rollout.check(discovery).await;
+///
+/// for status in report.outdated() {
+///     println!(
+///         "{}: {} is available",
+///         status.device.nickname(),
+///         status.latest_firmware.fw_ver
+///     );
+/// }
+///
+/// let mut updates = rollout.update(report.outdated().cloned().collect());
+/// while let Some(update) = updates.next().await {
+///     println!("{}: {:?}", update.nickname, update.progress);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct FirmwareRollout {
+    max_concurrent: usize,
+    poll_interval: Duration,
+    timeout: Duration,
+}
+
+impl Default for FirmwareRollout {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FirmwareRollout {
+    /// Creates a new [`FirmwareRollout`] that checks and updates up to 4 devices at a time,
+    /// polls the update progress every 5 seconds and gives up on an update after 15 minutes.
+    pub fn new() -> Self {
+        Self {
+            max_concurrent: DEFAULT_MAX_CONCURRENT,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// Sets the maximum number of devices that are checked or updated at the same time.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_concurrent` - values lower than 1 are treated as 1
+    pub fn with_max_concurrent(mut self, max_concurrent: usize) -> Self {
+        self.max_concurrent = max_concurrent.max(1);
+        self
+    }
+
+    /// Sets how often the progress of an update is polled.
+    ///
+    /// # Arguments
+    ///
+    /// * `poll_interval` - the interval between two `get_fw_download_state` requests
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Sets how long an update may take, including the device reboot, before it's reported as failed.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - the maximum duration of the update of a single device
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Returns the latest firmware available for each of the discovered devices,
+    /// e.g. the ones returned by [`crate::ApiClient::discover_devices`].
+    ///
+    /// Devices without firmware updates support, like cameras, are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `devices` - the discovered devices
+    pub async fn check<S>(&self, mut devices: S) -> FirmwareReport
+    where
+        S: Stream<Item = Result<DiscoveryResult, DiscoveryError>> + Unpin,
+    {
+        let semaphore = Arc::new(Semaphore::new(self.max_concurrent));
+        let mut checks = JoinSet::new();
+        let mut ips = HashMap::new();
+        let mut report = FirmwareReport::default();
+
+        while let Some(device) = devices.next().await {
+            let device = match device {
+                Ok(device) if device.as_firmware().is_some() => device,
+                Ok(device) => {
+                    debug!(
+                        "Skipping {}, which doesn't support firmware updates",
+                        device.ip()
+                    );
+                    continue;
+                }
+                Err(DiscoveryError { ip, source }) => {
+                    report.errors.push(FirmwareError { ip, source });
+                    continue;
+                }
+            };
+
+            let ip = device.ip().to_string();
+            let semaphore = semaphore.clone();
+            let task = checks.spawn(async move {
+                let _permit = semaphore.acquire_owned().await;
+                let firmware = device.as_firmware().expect("filtered above");
+
+                match firmware.get_latest_firmware().await {
+                    Ok(latest_firmware) => Ok(FirmwareStatus {
+                        device,
+                        latest_firmware,
+                    }),
+                    Err(source) => Err(FirmwareError {
+                        ip: device.ip().to_string(),
+                        source,
+                    }),
+                }
+            });
+            ips.insert(task.id(), ip);
+        }
+
+        while let Some(result) = checks.join_next().await {
+            match result {
+                Ok(Ok(status)) => report.devices.push(status),
+                Ok(Err(error)) => report.errors.push(error),
+                Err(e) => report.errors.push(FirmwareError {
+                    ip: ips.remove(&e.id()).unwrap_or_default(),
+                    source: anyhow::anyhow!("The firmware check failed: {e}").into(),
+                }),
+            }
+        }
+
+        report
+            .devices
+            .sort_by(|a, b| a.device.ip().cmp(b.device.ip()));
+
+        report
+    }
+
+    /// Updates the firmware of the given devices, up to `max_concurrent` at a time,
+    /// and returns a [`FirmwareUpdates`] stream that reports the progress of each update.
+    ///
+    /// The update of a device ends with either [`FirmwareUpdateProgress::Completed`]
+    /// or [`FirmwareUpdateProgress::Failed`]. The stream ends once every update has ended.
+    ///
+    /// # Arguments
+    ///
+    /// * `devices` - the devices to update, usually [`FirmwareReport::outdated`]
+    pub fn update(&self, devices: Vec<FirmwareStatus>) -> FirmwareUpdates {
+        let (tx, rx) = mpsc::channel(1024);
+        let task = tokio::spawn(update_all(self.clone(), devices, tx));
+
+        FirmwareUpdates { rx, task }
+    }
+}
+
+/// The result of [`FirmwareRollout::check`].
+#[derive(Debug, Default)]
+pub struct FirmwareReport {
+    /// The devices whose firmware was checked, ordered by IP address.
+    pub devices: Vec<FirmwareStatus>,
+    /// The devices that couldn't be discovered or checked.
+    pub errors: Vec<FirmwareError>,
+}
+
+impl FirmwareReport {
+    /// Returns the devices that have a firmware update available.
+    pub fn outdated(&self) -> impl Iterator<Item = &FirmwareStatus> {
+        self.devices.iter().filter(|status| status.is_outdated())
+    }
+}
+
+/// The latest firmware available for a device, as reported by [`FirmwareRollout::check`].
+#[derive(Debug, Clone)]
+pub struct FirmwareStatus {
+    /// The device.
+    pub device: DiscoveryResult,
+    /// The latest firmware available for the device.
+    pub latest_firmware: LatestFirmwareResult,
+}
+
+impl FirmwareStatus {
+    /// Whether a firmware update is available for the device.
+    pub fn is_outdated(&self) -> bool {
+        self.latest_firmware.need_to_upgrade
+    }
+}
+
+/// A progress report of the firmware update of a device, as reported by [`FirmwareUpdates`].
+#[derive(Debug)]
+pub struct FirmwareUpdate {
+    /// The device ID of the device.
+    pub device_id: String,
+    /// The nickname of the device.
+    pub nickname: String,
+    /// The IP address of the device.
+    pub ip: String,
+    /// The progress of the update.
+    pub progress: FirmwareUpdateProgress,
+}
+
+/// The progress of the firmware update of a device.
+#[derive(Debug)]
+pub enum FirmwareUpdateProgress {
+    /// The update was requested.
+    Started,
+    /// The firmware is being downloaded.
+    Downloading {
+        /// The download progress, between 0 and 100.
+        progress: u8,
+    },
+    /// The firmware is being installed. The device reboots afterwards.
+    Installing,
+    /// The firmware was installed and the device is up to date.
+    Completed {
+        /// The version of the installed firmware.
+        fw_ver: String,
+    },
+    /// The update failed, e.g. the device went back to idle without installing the firmware,
+    /// or didn't complete before the timeout.
+    Failed(Error),
+}
+
+/// A [`Stream`] of the progress of firmware updates, created by [`FirmwareRollout::update`].
+///
+/// The updates stop when the stream is dropped.
+/// Note that updates that are already being downloaded or installed continue on the devices.
+#[derive(Debug)]
+pub struct FirmwareUpdates {
+    rx: Receiver<FirmwareUpdate>,
+    task: JoinHandle<()>,
+}
+
+impl Stream for FirmwareUpdates {
+    type Item = FirmwareUpdate;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut TaskContext<'_>,
+    ) -> Poll<Option<FirmwareUpdate>> {
+        Pin::new(&mut self.rx).poll_recv(cx)
+    }
+}
+
+impl Drop for FirmwareUpdates {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn update_all(
+    rollout: FirmwareRollout,
+    devices: Vec<FirmwareStatus>,
+    tx: Sender<FirmwareUpdate>,
+) {
+    let semaphore = Arc::new(Semaphore::new(rollout.max_concurrent));
+    let mut updates = JoinSet::new();
+    let mut update_devices = HashMap::new();
+
+    for status in devices {
+        let device = (
+            status.device.device_id().to_string(),
+            status.device.nickname().to_string(),
+            status.device.ip().to_string(),
+        );
+        let semaphore = semaphore.clone();
+        let rollout = rollout.clone();
+        let tx = tx.clone();
+
+        let task = updates.spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            update(&rollout, status, tx).await;
+        });
+        update_devices.insert(task.id(), device);
+    }
+
+    while let Some(result) = updates.join_next().await {
+        let Err(e) = result else {
+            continue;
+        };
+
+        let Some((device_id, nickname, ip)) = update_devices.remove(&e.id()) else {
+            continue;
+        };
+
+        let error = anyhow::anyhow!("The firmware update failed: {e}");
+        let _ = tx
+            .send(FirmwareUpdate {
+                device_id,
+                nickname,
+                ip,
+                progress: FirmwareUpdateProgress::Failed(error.into()),
+            })
+            .await;
+    }
+}
+
+async fn update(rollout: &FirmwareRollout, status: FirmwareStatus, tx: Sender<FirmwareUpdate>) {
+    let device = status.device;
+    let report = |progress| {
+        tx.send(FirmwareUpdate {
+            device_id: device.device_id().to_string(),
+            nickname: device.nickname().to_string(),
+            ip: device.ip().to_string(),
+            progress,
+        })
+    };
+
+    let Some(firmware) = device.as_firmware() else {
+        let error = anyhow::anyhow!("The device doesn't support firmware updates");
+        let _ = report(FirmwareUpdateProgress::Failed(error.into())).await;
+        return;
+    };
+
+    let _ = report(FirmwareUpdateProgress::Started).await;
+
+    if let Err(e) = firmware.fw_download().await {
+        let _ = report(FirmwareUpdateProgress::Failed(e)).await;
+        return;
+    }
+
+    let deadline = Instant::now() + rollout.timeout;
+    let mut download_progress = None;
+    let mut installing = false;
+
+    loop {
+        tokio::time::sleep(rollout.poll_interval).await;
+
+        if Instant::now() >= deadline {
+            let error = anyhow::anyhow!("The firmware update didn't complete in time");
+            let _ = report(FirmwareUpdateProgress::Failed(error.into())).await;
+            return;
+        }
+
+        // The device is unreachable while it reboots after installing the firmware.
+        let state = match firmware.get_fw_download_state().await {
+            Ok(state) => state,
+            Err(e) => {
+                debug!(
+                    "Failed to get the firmware download state of {}: {e}",
+                    device.ip()
+                );
+                continue;
+            }
+        };
+
+        match state.status {
+            FirmwareDownloadStatus::Downloading => {
+                if download_progress != Some(state.download_progress) {
+                    download_progress = Some(state.download_progress);
+                    let progress = state.download_progress;
+                    let _ = report(FirmwareUpdateProgress::Downloading { progress }).await;
+                }
+            }
+            FirmwareDownloadStatus::Installing => {
+                if !installing {
+                    installing = true;
+                    let _ = report(FirmwareUpdateProgress::Installing).await;
+                }
+            }
+            FirmwareDownloadStatus::Failed(code) => {
+                let error = anyhow::anyhow!("The firmware update failed with error code {code}");
+                let _ = report(FirmwareUpdateProgress::Failed(error.into())).await;
+                return;
+            }
+            FirmwareDownloadStatus::Idle | FirmwareDownloadStatus::Other(_) => {
+                // Idle either before the download starts or after the device has rebooted.
+                match firmware.get_latest_firmware().await {
+                    Ok(latest_firmware) if !latest_firmware.need_to_upgrade => {
+                        let fw_ver = status.latest_firmware.fw_ver;
+                        let _ = report(FirmwareUpdateProgress::Completed { fw_ver }).await;
+                        return;
+                    }
+                    // Back to idle after downloading or installing, without the update being installed.
+                    Ok(_) if download_progress.is_some() || installing => {
+                        let error = anyhow::anyhow!(
+                            "The device is idle, but the firmware update wasn't installed"
+                        );
+                        let _ = report(FirmwareUpdateProgress::Failed(error.into())).await;
+                        return;
+                    }
+                    Ok(_) => {}
+                    Err(e) => debug!("Failed to get the latest firmware of {}: {e}", device.ip()),
+                }
+            }
+        }
+    }
+}
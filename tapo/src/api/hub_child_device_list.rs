@@ -0,0 +1,172 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context as TaskContext, Poll, ready};
+
+use serde::Serialize;
+use tokio_stream::Stream;
+
+use crate::error::Error;
+use crate::responses::ChildDeviceHubResult;
+
+use super::HubHandler;
+
+pub(crate) const CHILD_DEVICE_LIST_PAGE_SIZE: u64 = 10;
+
+type PageFuture = Pin<Box<dyn Future<Output = Result<Vec<ChildDeviceHubResult>, Error>> + Send>>;
+
+/// A [`Stream`] of the hub's child devices, created by [`HubHandler::get_child_device_list_stream`].
+///
+/// The child device list is requested one page at a time, only once the previous page
+/// has been consumed. The stream ends after the last page, or after the first error.
+pub struct ChildDeviceListStream {
+    hub: HubHandler,
+    start_index: u64,
+    page: std::vec::IntoIter<ChildDeviceHubResult>,
+    request: Option<PageFuture>,
+    done: bool,
+}
+
+impl ChildDeviceListStream {
+    pub(crate) fn new(hub: HubHandler) -> Self {
+        Self {
+            hub,
+            start_index: 0,
+            page: Vec::new().into_iter(),
+            request: None,
+            done: false,
+        }
+    }
+}
+
+impl fmt::Debug for ChildDeviceListStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChildDeviceListStream")
+            .field("start_index", &self.start_index)
+            .field("done", &self.done)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Stream for ChildDeviceListStream {
+    type Item = Result<ChildDeviceHubResult, Error>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut TaskContext<'_>,
+    ) -> Poll<Option<Result<ChildDeviceHubResult, Error>>> {
+        loop {
+            if let Some(device) = self.page.next() {
+                return Poll::Ready(Some(Ok(device)));
+            }
+
+            if self.done {
+                return Poll::Ready(None);
+            }
+
+            let hub = self.hub.clone();
+            let start_index = self.start_index;
+            let request = self.request.get_or_insert_with(|| {
+                Box::pin(async move { hub.get_child_device_list_page(start_index).await })
+            });
+
+            let result = ready!(request.as_mut().poll(cx));
+            self.request = None;
+
+            match result {
+                Ok(devices) => {
+                    self.done = (devices.len() as u64) < CHILD_DEVICE_LIST_PAGE_SIZE;
+                    self.start_index += CHILD_DEVICE_LIST_PAGE_SIZE;
+                    self.page = devices.into_iter();
+                }
+                Err(e) => {
+                    self.done = true;
+                    return Poll::Ready(Some(Err(e)));
+                }
+            }
+        }
+    }
+}
+
+/// The changes to the hub's child devices between two child device lists,
+/// as reported by [`HubHandler::child_device_list_diff`].
+#[derive(Debug, Clone, Serialize)]
+pub struct ChildDeviceListDiff {
+    /// The child devices that weren't in the previous list.
+    pub added: Vec<ChildDeviceHubResult>,
+    /// The child devices that are no longer in the list.
+    pub removed: Vec<ChildDeviceHubResult>,
+    /// The child devices whose nickname changed.
+    pub renamed: Vec<ChildDeviceRename>,
+    /// The current child device list.
+    /// Pass it to the next [`HubHandler::child_device_list_diff`] call.
+    pub current: Vec<ChildDeviceHubResult>,
+}
+
+impl ChildDeviceListDiff {
+    /// Compares two child device lists by device ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `previous` - the previous child device list
+    /// * `current` - the current child device list
+    pub fn new(previous: &[ChildDeviceHubResult], current: Vec<ChildDeviceHubResult>) -> Self {
+        let previous_by_id = previous
+            .iter()
+            .map(|child| (child.device_id(), child))
+            .collect::<HashMap<_, _>>();
+        let current_by_id = current
+            .iter()
+            .map(|child| (child.device_id(), child))
+            .collect::<HashMap<_, _>>();
+
+        let added = current
+            .iter()
+            .filter(|child| !previous_by_id.contains_key(child.device_id()))
+            .cloned()
+            .collect();
+
+        let removed = previous
+            .iter()
+            .filter(|child| !current_by_id.contains_key(child.device_id()))
+            .cloned()
+            .collect();
+
+        let renamed = current
+            .iter()
+            .filter_map(|child| {
+                let old = previous_by_id.get(child.device_id())?;
+
+                (old.nickname() != child.nickname()).then(|| ChildDeviceRename {
+                    device_id: child.device_id().to_string(),
+                    old_nickname: old.nickname().to_string(),
+                    new_nickname: child.nickname().to_string(),
+                })
+            })
+            .collect();
+
+        Self {
+            added,
+            removed,
+            renamed,
+            current,
+        }
+    }
+
+    /// Whether no child device was added, removed or renamed.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.renamed.is_empty()
+    }
+}
+
+/// A child device whose nickname changed, as reported by [`ChildDeviceListDiff`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ChildDeviceRename {
+    /// The device ID of the child device.
+    pub device_id: String,
+    /// The previous nickname.
+    pub old_nickname: String,
+    /// The current nickname.
+    pub new_nickname: String,
+}
@@ -0,0 +1,386 @@
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context as TaskContext, Poll};
+use std::time::Duration;
+
+use log::debug;
+use serde::Serialize;
+use tokio::sync::mpsc::{self, Receiver, Sender};
+use tokio::task::JoinHandle;
+use tokio_stream::Stream;
+
+use crate::error::Error;
+use crate::responses::{ChildDeviceHubResult, S200Log, T100Log, T110Log, T300Log};
+
+use super::{HubHandler, S200Handler, T100Handler, T110Handler, T300Handler};
+
+const TRIGGER_LOGS_PAGE_SIZE: u64 = 10;
+
+/// An event reported by one of the hub's child devices.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct HubEvent {
+    /// The device ID of the child device that reported the event.
+    pub device_id: String,
+    /// The nickname of the child device that reported the event.
+    pub nickname: String,
+    /// The model of the child device that reported the event.
+    pub model: String,
+    /// The `id` of the trigger log item.
+    pub id: u64,
+    /// The time the event occurred at, as a UNIX timestamp in seconds.
+    pub timestamp: u64,
+    /// What happened.
+    pub kind: HubEventKind,
+}
+
+/// The kind of a [`HubEvent`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HubEventKind {
+    /// Motion was detected (T100).
+    Motion,
+    /// The door or window was opened (T110).
+    Open,
+    /// The door or window was closed (T110).
+    Close,
+    /// The door or window has been kept open (T110).
+    KeepOpen,
+    /// A water leak was detected (T300).
+    WaterLeak,
+    /// The water leak has dried (T300).
+    WaterDry,
+    /// The button was pressed once (S200B, S200D).
+    SingleClick,
+    /// The button was pressed twice (S200B, S200D).
+    DoubleClick,
+    /// The button was rotated by the given number of degrees (S200B, S200D).
+    Rotation {
+        /// The rotation in degrees, negative for counter-clockwise rotations.
+        rotation_degrees: i16,
+    },
+    /// The battery is low (S200B, S200D).
+    LowBattery,
+}
+
+/// A [`Stream`] of the events reported by the hub's child devices,
+/// created by [`HubHandler::events`].
+///
+/// The child devices' trigger logs are polled at the given interval.
+/// Each child device's last seen log `id` is tracked so that every log item is
+/// reported once, in chronological order.
+/// A log item only counts as seen once the stream has yielded its event,
+/// so events that were fetched but not consumed yet are reported again after resuming.
+///
+/// Polling errors, e.g. while the hub is unreachable, are reported as `Err` items
+/// and the stream keeps polling at the next interval, resuming from the last seen log items.
+/// The polling stops when the stream is dropped.
+#[derive(Debug)]
+pub struct HubEvents {
+    rx: Receiver<Result<HubEvent, Error>>,
+    last_seen_ids: Arc<Mutex<HashMap<String, u64>>>,
+    task: JoinHandle<()>,
+}
+
+impl HubEvents {
+    pub(crate) fn new(
+        hub: HubHandler,
+        interval: Duration,
+        last_seen_ids: HashMap<String, u64>,
+    ) -> Self {
+        let last_seen_ids = Arc::new(Mutex::new(last_seen_ids));
+        let (tx, rx) = mpsc::channel(1024);
+
+        let task = tokio::spawn(poll(hub, interval, last_seen_ids.clone(), tx));
+
+        Self {
+            rx,
+            last_seen_ids,
+            task,
+        }
+    }
+
+    /// Returns the `id` of the last seen trigger log item of each child device, keyed by device ID.
+    ///
+    /// Pass them to [`HubHandler::events_from`] to resume from where this stream left off,
+    /// e.g. after restarting the application.
+    pub fn last_seen_ids(&self) -> HashMap<String, u64> {
+        self.last_seen_ids
+            .lock()
+            .expect("last seen ids lock poisoned")
+            .clone()
+    }
+}
+
+impl Stream for HubEvents {
+    type Item = Result<HubEvent, Error>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut TaskContext<'_>,
+    ) -> Poll<Option<Result<HubEvent, Error>>> {
+        let poll = Pin::new(&mut self.rx).poll_recv(cx);
+
+        if let Poll::Ready(Some(Ok(event))) = &poll {
+            self.last_seen_ids
+                .lock()
+                .expect("last seen ids lock poisoned")
+                .insert(event.device_id.clone(), event.id);
+        }
+
+        poll
+    }
+}
+
+impl Drop for HubEvents {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn poll(
+    hub: HubHandler,
+    interval: Duration,
+    last_seen_ids: Arc<Mutex<HashMap<String, u64>>>,
+    tx: Sender<Result<HubEvent, Error>>,
+) {
+    let mut interval = tokio::time::interval(interval);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    // The ids of the events that were sent to the stream, which may be ahead of the consumed ones.
+    let mut sent_ids = last_seen_ids
+        .lock()
+        .expect("last seen ids lock poisoned")
+        .clone();
+
+    loop {
+        interval.tick().await;
+
+        let children = match hub.get_child_device_list().await {
+            Ok(children) => children,
+            Err(e) => {
+                debug!("Failed to get the hub's child device list: {e}");
+                if tx.send(Err(e)).await.is_err() {
+                    return;
+                }
+                continue;
+            }
+        };
+
+        for child in children {
+            let Some(source) = EventSource::new(&hub, &child) else {
+                continue;
+            };
+
+            let last_sent_id = sent_ids.get(&source.device_id).copied();
+
+            match source.get_new_events(last_sent_id).await {
+                Ok((newest_id, events)) if events.is_empty() => {
+                    if last_sent_id == Some(newest_id) {
+                        continue;
+                    }
+
+                    // A new device, or cleared logs: there's nothing to report, only the starting point moves.
+                    last_seen_ids
+                        .lock()
+                        .expect("last seen ids lock poisoned")
+                        .insert(source.device_id.clone(), newest_id);
+                    sent_ids.insert(source.device_id, newest_id);
+                }
+                Ok((newest_id, events)) => {
+                    for event in events {
+                        if tx.send(Ok(event)).await.is_err() {
+                            return;
+                        }
+                    }
+                    sent_ids.insert(source.device_id, newest_id);
+                }
+                Err(e) => {
+                    debug!(
+                        "Failed to get the trigger logs of {}: {e}",
+                        source.device_id
+                    );
+                    if tx.send(Err(e)).await.is_err() {
+                        return;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// A hub child device that reports trigger logs.
+struct EventSource {
+    device_id: String,
+    nickname: String,
+    model: String,
+    handler: EventSourceHandler,
+}
+
+enum EventSourceHandler {
+    S200(S200Handler),
+    T100(T100Handler),
+    T110(T110Handler),
+    T300(T300Handler),
+}
+
+impl EventSource {
+    fn new(hub: &HubHandler, child: &ChildDeviceHubResult) -> Option<Self> {
+        let device_id = child.device_id().to_string();
+
+        let handler = match child {
+            ChildDeviceHubResult::S200(_) => {
+                EventSourceHandler::S200(hub.s200_unchecked(device_id.clone()))
+            }
+            ChildDeviceHubResult::T100(_) => {
+                EventSourceHandler::T100(hub.t100_unchecked(device_id.clone()))
+            }
+            ChildDeviceHubResult::T110(_) => {
+                EventSourceHandler::T110(hub.t110_unchecked(device_id.clone()))
+            }
+            ChildDeviceHubResult::T300(_) => {
+                EventSourceHandler::T300(hub.t300_unchecked(device_id.clone()))
+            }
+            _ => return None,
+        };
+
+        Some(Self {
+            device_id,
+            nickname: child.nickname().to_string(),
+            model: child.model().to_string(),
+            handler,
+        })
+    }
+
+    /// Returns the newest log `id` and the events that are newer than `last_seen_id`,
+    /// in chronological order.
+    ///
+    /// When the device hasn't been seen before, only its newest log `id` is returned,
+    /// so that past events aren't reported.
+    /// The same applies when the newest log `id` is lower than `last_seen_id`,
+    /// which means that the logs were cleared, e.g. after the device was paired again.
+    async fn get_new_events(
+        &self,
+        last_seen_id: Option<u64>,
+    ) -> Result<(u64, Vec<HubEvent>), Error> {
+        let mut page = self.get_trigger_logs(0).await?;
+        let newest_id = page.first().map_or(0, |(id, _, _)| *id);
+
+        let last_seen_id = match last_seen_id {
+            Some(last_seen_id) if last_seen_id <= newest_id => last_seen_id,
+            _ => return Ok((newest_id, Vec::new())),
+        };
+
+        let mut events = Vec::new();
+
+        loop {
+            let page_len = page.len() as u64;
+            let oldest_id = page.last().map(|(id, _, _)| *id);
+
+            let mut reached_last_seen = false;
+            for (id, timestamp, kind) in page {
+                if id <= last_seen_id {
+                    reached_last_seen = true;
+                    break;
+                }
+
+                events.push(HubEvent {
+                    device_id: self.device_id.clone(),
+                    nickname: self.nickname.clone(),
+                    model: self.model.clone(),
+                    id,
+                    timestamp,
+                    kind,
+                });
+            }
+
+            match oldest_id {
+                Some(oldest_id)
+                    if !reached_last_seen
+                        && page_len == TRIGGER_LOGS_PAGE_SIZE
+                        && oldest_id > 1 =>
+                {
+                    page = self.get_trigger_logs(oldest_id - 1).await?;
+                }
+                _ => break,
+            }
+        }
+
+        events.reverse();
+
+        Ok((newest_id, events))
+    }
+
+    /// Returns a page of trigger log items, as `(id, timestamp, kind)` tuples, newest first.
+    async fn get_trigger_logs(
+        &self,
+        start_id: u64,
+    ) -> Result<Vec<(u64, u64, HubEventKind)>, Error> {
+        let page_size = TRIGGER_LOGS_PAGE_SIZE;
+
+        let logs = match &self.handler {
+            EventSourceHandler::S200(handler) => handler
+                .get_trigger_logs(page_size, start_id)
+                .await?
+                .logs
+                .into_iter()
+                .map(|log| match log {
+                    S200Log::Rotation {
+                        id,
+                        timestamp,
+                        params,
+                    } => (
+                        id,
+                        timestamp,
+                        HubEventKind::Rotation {
+                            rotation_degrees: params.rotation_degrees,
+                        },
+                    ),
+                    S200Log::SingleClick { id, timestamp } => {
+                        (id, timestamp, HubEventKind::SingleClick)
+                    }
+                    S200Log::DoubleClick { id, timestamp } => {
+                        (id, timestamp, HubEventKind::DoubleClick)
+                    }
+                    S200Log::LowBattery { id, timestamp } => {
+                        (id, timestamp, HubEventKind::LowBattery)
+                    }
+                })
+                .collect(),
+            EventSourceHandler::T100(handler) => handler
+                .get_trigger_logs(page_size, start_id)
+                .await?
+                .logs
+                .into_iter()
+                .map(|log| match log {
+                    T100Log::Motion { id, timestamp } => (id, timestamp, HubEventKind::Motion),
+                })
+                .collect(),
+            EventSourceHandler::T110(handler) => handler
+                .get_trigger_logs(page_size, start_id)
+                .await?
+                .logs
+                .into_iter()
+                .map(|log| match log {
+                    T110Log::Open { id, timestamp } => (id, timestamp, HubEventKind::Open),
+                    T110Log::Close { id, timestamp } => (id, timestamp, HubEventKind::Close),
+                    T110Log::KeepOpen { id, timestamp } => (id, timestamp, HubEventKind::KeepOpen),
+                })
+                .collect(),
+            EventSourceHandler::T300(handler) => handler
+                .get_trigger_logs(page_size, start_id)
+                .await?
+                .logs
+                .into_iter()
+                .map(|log| match log {
+                    T300Log::WaterLeak { id, timestamp } => {
+                        (id, timestamp, HubEventKind::WaterLeak)
+                    }
+                    T300Log::WaterDry { id, timestamp } => (id, timestamp, HubEventKind::WaterDry),
+                })
+                .collect(),
+        };
+
+        Ok(logs)
+    }
+}
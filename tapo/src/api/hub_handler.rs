@@ -1,11 +1,22 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use tokio_stream::StreamExt as _;
+
 use crate::error::Error;
-use crate::requests::{AlarmDuration, AlarmRingtone, AlarmVolume, PlayAlarmParams};
+use crate::requests::{
+    AlarmDuration, AlarmRingtone, AlarmVolume, ChildDeviceListParams, PlayAlarmParams,
+};
 #[cfg(feature = "debug")]
 use crate::responses::ChildDeviceComponentList;
-use crate::responses::{ChildDeviceHubResult, ChildDeviceListHubResult, DeviceInfoHubResult};
+use crate::responses::{
+    ChildDeviceHubResult, ChildDeviceListHubResult, DeviceInfoHubResult, ScanChildDeviceListResult,
+    ScannedChildDevice,
+};
 
 use super::{
-    KE100Handler, S200Handler, S210Handler, T31XHandler, T100Handler, T110Handler, T300Handler,
+    ChildDeviceListDiff, ChildDeviceListStream, HubEvents, KE100Handler, S200Handler, S210Handler,
+    T31XHandler, T100Handler, T110Handler, T300Handler,
 };
 
 macro_rules! get_device_id {
@@ -39,6 +50,8 @@ tapo_handler! {
     device_management,
 }
 
+impl_watch!(HubHandler);
+
 /// Hub handler methods.
 impl HubHandler {
     /// Returns *child device list* as [`ChildDeviceHubResult`].
@@ -46,25 +59,69 @@ impl HubHandler {
     /// or to support all the possible devices connected to the hub.
     /// If the deserialization fails, or if a property that you care about it's not present, try [`HubHandler::get_child_device_list_json`].
     pub async fn get_child_device_list(&self) -> Result<Vec<ChildDeviceHubResult>, Error> {
-        let mut results = Vec::new();
-        let mut start_index = 0;
-        let mut fetch = true;
-
-        while fetch {
-            let devices = self
-                .client
-                .read()
-                .await
-                .get_child_device_list::<ChildDeviceListHubResult>(start_index)
-                .await
-                .map(|r| r.devices)?;
-
-            fetch = devices.len() == 10;
-            start_index += 10;
-            results.extend(devices);
-        }
+        self.get_child_device_list_stream().collect().await
+    }
+
+    /// Returns a [`ChildDeviceListStream`] of the *child devices* as [`ChildDeviceHubResult`].
+    /// Unlike [`HubHandler::get_child_device_list`], the pages of the child device list
+    /// are requested lazily, as the stream is consumed.
+    pub fn get_child_device_list_stream(&self) -> ChildDeviceListStream {
+        ChildDeviceListStream::new(self.clone())
+    }
+
+    /// Returns the child devices that were added, removed or renamed since `previous`
+    /// was retrieved, as [`ChildDeviceListDiff`].
+    ///
+    /// # Arguments
+    ///
+    /// * `previous` - the previous child device list, e.g. [`ChildDeviceListDiff::current`]
+    ///   of the previous call
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use tapo::ApiClient;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let hub = ApiClient::new("tapo-username@example.com", "tapo-password")
+    ///     .h100("192.168.1.100")
+    ///     .await?;
+    ///
+    /// let mut children = hub.get_child_device_list().await?;
+    ///
+    /// // Later on...
+    /// let diff = hub.child_device_list_diff(&children).await?;
+    /// for child in &diff.added {
+    ///     println!("Added: {}", child.nickname());
+    /// }
+    /// for child in &diff.removed {
+    ///     println!("Removed: {}", child.nickname());
+    /// }
+    /// for rename in &diff.renamed {
+    ///     println!("Renamed: {} -> {}", rename.old_nickname, rename.new_nickname);
+    /// }
+    /// children = diff.current;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn child_device_list_diff(
+        &self,
+        previous: &[ChildDeviceHubResult],
+    ) -> Result<ChildDeviceListDiff, Error> {
+        let current = self.get_child_device_list().await?;
+        Ok(ChildDeviceListDiff::new(previous, current))
+    }
 
-        Ok(results)
+    pub(crate) async fn get_child_device_list_page(
+        &self,
+        start_index: u64,
+    ) -> Result<Vec<ChildDeviceHubResult>, Error> {
+        self.client
+            .read()
+            .await
+            .get_child_device_list::<ChildDeviceListHubResult>(start_index)
+            .await
+            .map(|r| r.devices)
     }
 
     /// Returns *child device list* as [`serde_json::Value`].
@@ -129,6 +186,135 @@ impl HubHandler {
     pub async fn stop_alarm(&self) -> Result<(), Error> {
         self.client.read().await.stop_alarm().await
     }
+
+    /// Starts scanning for child devices that are in pairing mode.
+    /// The devices that are found are returned by [`HubHandler::get_scan_child_device_list`]
+    /// and can be paired with [`HubHandler::add_child_device_list`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use std::time::Duration;
+    /// # use tapo::ApiClient;
+    /// # use tapo::responses::ScanStatus;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let hub = ApiClient::new("tapo-username@example.com", "tapo-password")
+    ///     .h100("192.168.1.100")
+    ///     .await?;
+    ///
+    /// hub.begin_scanning_child_device().await?;
+    ///
+    /// let devices = loop {
+    ///     tokio::time::sleep(Duration::from_secs(5)).await;
+    ///
+    ///     let scan = hub.get_scan_child_device_list().await?;
+    ///     if scan.scan_status != ScanStatus::Scanning {
+    ///         break scan.devices;
+    ///     }
+    /// };
+    ///
+    /// let sensors = devices
+    ///     .into_iter()
+    ///     .filter(|device| device.model == "T310")
+    ///     .collect::<Vec<_>>();
+    /// hub.add_child_device_list(&sensors).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn begin_scanning_child_device(&self) -> Result<(), Error> {
+        self.client.read().await.begin_scanning_child_device().await
+    }
+
+    /// Stops scanning for child devices, if a scan is in progress.
+    pub async fn end_scanning_child_device(&self) -> Result<(), Error> {
+        self.client.read().await.end_scanning_child_device().await
+    }
+
+    /// Returns the status of the child device scan started by
+    /// [`HubHandler::begin_scanning_child_device`], and the devices found so far,
+    /// as [`ScanChildDeviceListResult`].
+    pub async fn get_scan_child_device_list(&self) -> Result<ScanChildDeviceListResult, Error> {
+        self.client.read().await.get_scan_child_device_list().await
+    }
+
+    /// Pairs the given child devices with the hub.
+    ///
+    /// # Arguments
+    ///
+    /// * `devices` - devices returned by [`HubHandler::get_scan_child_device_list`]
+    pub async fn add_child_device_list(&self, devices: &[ScannedChildDevice]) -> Result<(), Error> {
+        self.client
+            .read()
+            .await
+            .add_child_device_list(ChildDeviceListParams::add(devices))
+            .await
+    }
+
+    /// Unpairs the child devices with the given device IDs from the hub.
+    ///
+    /// # Arguments
+    ///
+    /// * `device_ids` - the device IDs of the child devices to remove
+    pub async fn remove_child_device_list(&self, device_ids: Vec<String>) -> Result<(), Error> {
+        self.client
+            .read()
+            .await
+            .remove_child_device_list(ChildDeviceListParams::remove(device_ids))
+            .await
+    }
+
+    /// Returns a [`HubEvents`] stream of the events reported by the child devices
+    /// that keep trigger logs: S200B, S200D, T100, T110 and T300.
+    ///
+    /// The trigger logs are polled every `interval`.
+    /// Events that occurred before the stream was created are not reported;
+    /// use [`HubHandler::events_from`] to resume from previously seen events.
+    ///
+    /// # Arguments
+    ///
+    /// * `interval` - how often the trigger logs are polled
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use std::time::Duration;
+    /// # use tapo::{ApiClient, StreamExt as _};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let hub = ApiClient::new("tapo-username@example.com", "tapo-password")
+    ///     .h100("192.168.1.100")
+    ///     .await?;
+    ///
+    /// let mut events = hub.events(Duration::from_secs(5));
+    /// while let Some(event) = events.next().await {
+    ///     match event {
+    ///         Ok(event) => println!("{}: {:?}", event.nickname, event.kind),
+    ///         Err(e) => eprintln!("Failed to poll the hub: {e}"),
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn events(&self, interval: Duration) -> HubEvents {
+        self.events_from(interval, HashMap::new())
+    }
+
+    /// Same as [`HubHandler::events`], but resumes from the given last seen log `id`s,
+    /// as returned by [`HubEvents::last_seen_ids`].
+    /// Events that are newer than the last seen ones are reported on the first poll.
+    ///
+    /// # Arguments
+    ///
+    /// * `interval` - how often the trigger logs are polled
+    /// * `last_seen_ids` - the `id` of the last seen trigger log item of each child device, keyed by device ID
+    pub fn events_from(
+        &self,
+        interval: Duration,
+        last_seen_ids: HashMap<String, u64>,
+    ) -> HubEvents {
+        HubEvents::new(self.clone(), interval, last_seen_ids)
+    }
 }
 
 /// Child device handler builders.
@@ -2,7 +2,9 @@ use tokio::sync::RwLockReadGuard;
 
 use crate::error::Error;
 use crate::requests::LightSetDeviceInfoParams;
-use crate::responses::{DeviceInfoLightResult, DeviceUsageEnergyMonitoringResult};
+use crate::responses::{
+    DefaultLightState, DeviceInfoLightResult, DeviceUsageEnergyMonitoringResult,
+};
 
 use super::{ApiClient, ApiClientExt};
 
@@ -16,6 +18,14 @@ tapo_handler! {
     device_management,
 }
 
+impl_watch!(LightHandler);
+impl_schedule_rules!(LightHandler);
+impl_antitheft_rules!(LightHandler);
+impl_device_settings!(LightHandler, DefaultLightState);
+impl_on_off_gradually!(LightHandler);
+
+impl_dimmable!(LightHandler);
+
 impl LightHandler {
     /// Sets the *brightness* and turns *on* the device.
     ///
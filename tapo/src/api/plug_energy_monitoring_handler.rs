@@ -3,8 +3,9 @@ use std::time::Duration;
 use crate::error::Error;
 use crate::requests::{EnergyDataInterval, PowerDataInterval};
 use crate::responses::{
-    CurrentPowerResult, DeviceInfoPlugEnergyMonitoringResult, DeviceUsageEnergyMonitoringResult,
-    EnergyDataResult, EnergyUsageResult, PowerDataResult, PowerState, Timer,
+    CurrentPowerResult, DefaultPlugState, DeviceInfoPlugEnergyMonitoringResult,
+    DeviceUsageEnergyMonitoringResult, EnergyDataResult, EnergyUsageResult, MaxPowerResult,
+    PowerDataResult, PowerProtectionResult, PowerState, Timer,
 };
 
 tapo_handler! {
@@ -17,6 +18,12 @@ tapo_handler! {
     device_management,
 }
 
+impl_energy_meter!(PlugEnergyMonitoringHandler);
+impl_watch!(PlugEnergyMonitoringHandler, current_power);
+impl_schedule_rules!(PlugEnergyMonitoringHandler);
+impl_antitheft_rules!(PlugEnergyMonitoringHandler);
+impl_device_settings!(PlugEnergyMonitoringHandler, DefaultPlugState);
+
 impl PlugEnergyMonitoringHandler {
     /// Returns *current power* as [`CurrentPowerResult`].
     pub async fn get_current_power(&self) -> Result<CurrentPowerResult, Error> {
@@ -74,4 +81,46 @@ impl PlugEnergyMonitoringHandler {
     pub async fn clear_timer(&self) -> Result<(), Error> {
         self.client.read().await.clear_timer().await
     }
+
+    /// Returns the *power protection* settings as [`PowerProtectionResult`].
+    pub async fn get_power_protection(&self) -> Result<PowerProtectionResult, Error> {
+        self.client.read().await.get_power_protection(None).await
+    }
+
+    /// Returns the *maximum power* the plug supports as [`MaxPowerResult`].
+    pub async fn get_max_power(&self) -> Result<MaxPowerResult, Error> {
+        self.client.read().await.get_max_power(None).await
+    }
+
+    /// Enables power protection: the plug turns itself *off* once the load exceeds `protection_power`.
+    ///
+    /// # Arguments
+    ///
+    /// * `protection_power` - the power limit in Watts (W), between 1 and the plug's *maximum power*
+    pub async fn set_power_protection(&self, protection_power: u16) -> Result<(), Error> {
+        self.client
+            .read()
+            .await
+            .set_power_protection(None, Some(protection_power))
+            .await
+    }
+
+    /// Disables power protection.
+    pub async fn disable_power_protection(&self) -> Result<(), Error> {
+        self.client
+            .read()
+            .await
+            .set_power_protection(None, None)
+            .await
+    }
+
+    /// Resets a tripped `power_protection_status` from `overloaded` back to `normal`,
+    /// so that the plug can be turned *on* again.
+    pub async fn clear_power_protection_status(&self) -> Result<(), Error> {
+        self.client
+            .read()
+            .await
+            .clear_power_protection_status(None)
+            .await
+    }
 }
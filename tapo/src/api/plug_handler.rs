@@ -1,7 +1,9 @@
 use std::time::Duration;
 
 use crate::error::Error;
-use crate::responses::{DeviceInfoPlugResult, DeviceUsageResult, PowerState, Timer};
+use crate::responses::{
+    DefaultPlugState, DeviceInfoPlugResult, DeviceUsageResult, PowerState, Timer,
+};
 
 tapo_handler! {
     /// Handler for the [P100](https://www.tapo.com/en/search/?q=P100) and
@@ -12,6 +14,11 @@ tapo_handler! {
     device_management,
 }
 
+impl_watch!(PlugHandler);
+impl_schedule_rules!(PlugHandler);
+impl_antitheft_rules!(PlugHandler);
+impl_device_settings!(PlugHandler, DefaultPlugState);
+
 impl PlugHandler {
     /// Arms the plug's countdown timer (the "Timer" feature in the
     /// Tapo app), replacing any timer that is currently armed.
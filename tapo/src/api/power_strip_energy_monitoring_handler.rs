@@ -15,6 +15,8 @@ tapo_handler! {
     device_management,
 }
 
+impl_watch!(PowerStripEnergyMonitoringHandler);
+
 impl PowerStripEnergyMonitoringHandler {
     /// Returns *child device list* as [`Vec<PowerStripPlugEnergyMonitoringResult>`].
     /// It is not guaranteed to contain all the properties returned from the Tapo API.
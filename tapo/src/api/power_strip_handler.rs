@@ -14,6 +14,8 @@ tapo_handler! {
     device_management,
 }
 
+impl_watch!(PowerStripHandler);
+
 impl PowerStripHandler {
     /// Returns *child device list* as [`Vec<PowerStripPlugResult>`].
     /// It is not guaranteed to contain all the properties returned from the Tapo API.
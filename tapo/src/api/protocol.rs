@@ -2,9 +2,16 @@ mod aes_cipher;
 mod aes_protocol;
 mod aes_ssl_cipher;
 mod aes_ssl_protocol;
-mod crypto;
+pub(crate) mod crypto;
 mod klap_cipher;
 mod klap_protocol;
 mod tapo_protocol;
 
 pub(crate) use tapo_protocol::*;
+
+#[cfg(feature = "testing")]
+pub(crate) use aes_cipher::AesCipher;
+#[cfg(feature = "testing")]
+pub(crate) use aes_ssl_cipher::{AesSslCipher, compute_password_digest, generate_nonce};
+#[cfg(feature = "testing")]
+pub(crate) use klap_cipher::KlapCipher;
@@ -60,7 +60,7 @@ impl AesProtocol {
         self.login(url, username, password).await
     }
 
-    pub async fn execute_request<R>(&self, request: TapoRequest) -> Result<Option<R>, Error>
+    pub async fn execute_request<R>(&self, request: &TapoRequest) -> Result<Option<R>, Error>
     where
         R: fmt::Debug + DeserializeOwned + TapoResponseExt,
     {
@@ -70,7 +70,7 @@ impl AesProtocol {
             None => session.url.clone(),
         };
 
-        let request_string = serde_json::to_string(&request)?;
+        let request_string = serde_json::to_string(request)?;
         debug!("Request to passthrough: {request_string}");
 
         let request_encrypted = session.cipher.encrypt(&request_string)?;
@@ -174,7 +174,7 @@ impl AesProtocol {
         let request = TapoRequest::LoginDevice(params);
 
         let result = self
-            .execute_request::<TokenResult>(request)
+            .execute_request::<TokenResult>(&request)
             .await?
             .ok_or_else(|| Error::Tapo(TapoResponseError::EmptyResult))?;
 
@@ -3,7 +3,7 @@ use std::sync::atomic::{AtomicI32, Ordering};
 use super::crypto;
 
 #[derive(Debug)]
-pub(super) struct AesSslCipher {
+pub(crate) struct AesSslCipher {
     key: Vec<u8>,
     iv: Vec<u8>,
     tag_prefix: String,
@@ -37,6 +37,11 @@ impl AesSslCipher {
         crypto::aes128_cbc_encrypt(&self.key, &self.iv, data)
     }
 
+    #[cfg(any(test, feature = "testing"))]
+    pub fn decrypt(&self, cipher_base64: &str) -> anyhow::Result<String> {
+        crypto::aes128_cbc_decrypt(&self.key, &self.iv, cipher_base64)
+    }
+
     pub fn generate_tag(&self, request_body: &str, sequence: i32) -> String {
         crypto::sha256_hex(format!("{}{request_body}{sequence}", self.tag_prefix).as_bytes())
     }
@@ -46,7 +51,7 @@ impl AesSslCipher {
     }
 }
 
-pub(super) fn generate_nonce() -> String {
+pub(crate) fn generate_nonce() -> String {
     use rand::RngExt as _;
     let bytes: [u8; 8] = rand::rng().random();
     base16ct::upper::encode_string(&bytes)
@@ -64,7 +69,7 @@ pub(super) fn validate_device_confirm(
     expected == device_confirm
 }
 
-pub(super) fn compute_password_digest(
+pub(crate) fn compute_password_digest(
     local_nonce: &str,
     server_nonce: &str,
     password_hash: &str,
@@ -78,12 +83,6 @@ pub(super) fn compute_password_digest(
 mod tests {
     use super::*;
 
-    impl AesSslCipher {
-        fn decrypt(&self, cipher_base64: &str) -> anyhow::Result<String> {
-            crypto::aes128_cbc_decrypt(&self.key, &self.iv, cipher_base64)
-        }
-    }
-
     #[test]
     fn test_nonce_generation() {
         let nonce = generate_nonce();
@@ -53,13 +53,13 @@ impl AesSslProtocol {
         self.handshake(url, username, password).await
     }
 
-    pub async fn execute_request<R>(&self, request: TapoRequest) -> Result<Option<R>, Error>
+    pub async fn execute_request<R>(&self, request: &TapoRequest) -> Result<Option<R>, Error>
     where
         R: fmt::Debug + DeserializeOwned + TapoResponseExt,
     {
         let session = self.session()?;
 
-        let request_string = serde_json::to_string(&request)?;
+        let request_string = serde_json::to_string(request)?;
         debug!("Request: {request_string}");
 
         let request_encrypted = session.cipher.encrypt(&request_string)?;
@@ -7,7 +7,7 @@ use cbc::{Decryptor, Encryptor};
 use super::crypto;
 
 #[derive(Debug)]
-pub(super) struct KlapCipher {
+pub(crate) struct KlapCipher {
     key: Vec<u8>,
     iv: Vec<u8>,
     seq: AtomicI32,
@@ -34,6 +34,14 @@ impl KlapCipher {
 
     pub fn encrypt(&self, data: String) -> anyhow::Result<(Vec<u8>, i32)> {
         let seq = self.seq.fetch_add(1, Ordering::Relaxed) + 1;
+        let result = self.encrypt_with_seq(data, seq)?;
+
+        Ok((result, seq))
+    }
+
+    /// Encrypts `data` with the given sequence number instead of the next one.
+    /// The device side of the protocol replies with the sequence number of the request.
+    pub fn encrypt_with_seq(&self, data: String, seq: i32) -> anyhow::Result<Vec<u8>> {
         let encryptor = Encryptor::<Aes128>::new_from_slices(&self.key, &self.iv_seq(seq))?;
 
         let cipher_bytes = encryptor.encrypt_padded_vec::<block_padding::Pkcs7>(data.as_bytes());
@@ -47,9 +55,7 @@ impl KlapCipher {
             .concat(),
         );
 
-        let result = [&signature, cipher_bytes.as_slice()].concat();
-
-        Ok((result, seq))
+        Ok([&signature, cipher_bytes.as_slice()].concat())
     }
 
     pub fn decrypt(&self, seq: i32, cipher_bytes: Vec<u8>) -> anyhow::Result<String> {
@@ -1,9 +1,11 @@
 use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
 
 use log::debug;
 use reqwest::Client;
 use reqwest::cookie::Cookie;
 use serde::de::DeserializeOwned;
+use tokio::sync::RwLock;
 
 use crate::Error;
 use crate::TapoResponseError;
@@ -47,7 +49,13 @@ enum ActiveProtocol {
 pub(crate) struct TapoProtocol {
     client: Client,
     device_family: DeviceFamily,
-    active: Option<ActiveProtocol>,
+    // Behind a lock so that an expired session can be refreshed while
+    // the owning `ApiClient` is only borrowed immutably.
+    active: RwLock<Option<ActiveProtocol>>,
+    // Incremented every time a new session is established. Used to make sure
+    // that concurrent requests failing on the same expired session only
+    // trigger a single re-login.
+    session_generation: AtomicU64,
 }
 
 impl Clone for TapoProtocol {
@@ -56,7 +64,8 @@ impl Clone for TapoProtocol {
         Self {
             client: self.client.clone(),
             device_family: self.device_family,
-            active: None,
+            active: RwLock::new(None),
+            session_generation: AtomicU64::new(0),
         }
     }
 }
@@ -68,7 +77,8 @@ impl TapoProtocol {
             // Overwritten by login() before any caller can read it,
             // because ApiClient::protocol() guards against pre-login access.
             device_family: DeviceFamily::Smart,
-            active: None,
+            active: RwLock::new(None),
+            session_generation: AtomicU64::new(0),
         }
     }
 
@@ -76,6 +86,12 @@ impl TapoProtocol {
         self.device_family
     }
 
+    /// Returns the generation of the current session.
+    /// It changes every time the session is (re)established.
+    pub fn session_generation(&self) -> u64 {
+        self.session_generation.load(Ordering::Acquire)
+    }
+
     pub async fn login(
         &mut self,
         ip_address: impl Into<String>,
@@ -92,8 +108,10 @@ impl TapoProtocol {
         };
         debug!("Device url: {url}");
 
-        if self.active.is_none() {
-            self.active = Some(match auth_protocol {
+        let active = self.active.get_mut();
+
+        if active.is_none() {
+            *active = Some(match auth_protocol {
                 AuthProtocol::Aes => {
                     debug!("Using AES protocol (from discovery hint)...");
                     ActiveProtocol::Aes(AesProtocol::new(self.client.clone())?)
@@ -106,39 +124,56 @@ impl TapoProtocol {
                     debug!("Using KLAP protocol (from discovery hint)...");
                     ActiveProtocol::Klap(KlapProtocol::new(self.client.clone()))
                 }
-                AuthProtocol::Unknown => self.discover_protocol_type(&url).await?,
+                AuthProtocol::Unknown => Self::discover_protocol_type(&self.client, &url).await?,
             });
         }
 
-        match &mut self.active {
-            Some(ActiveProtocol::Aes(p)) => p.login(url, username, password).await,
-            Some(ActiveProtocol::AesSsl(p)) => p.login(url, username, password).await,
-            Some(ActiveProtocol::Klap(p)) => p.login(url, username, password).await,
+        match active {
+            Some(ActiveProtocol::Aes(p)) => p.login(url, username, password).await?,
+            Some(ActiveProtocol::AesSsl(p)) => p.login(url, username, password).await?,
+            Some(ActiveProtocol::Klap(p)) => p.login(url, username, password).await?,
             None => unreachable!(),
-        }
+        };
+
+        self.session_generation.fetch_add(1, Ordering::AcqRel);
+
+        Ok(())
     }
 
-    pub async fn refresh_session(
-        &mut self,
+    pub async fn refresh_session(&self, username: String, password: String) -> Result<(), Error> {
+        let mut active = self.active.write().await;
+        Self::refresh_active_session(&mut active, username, password).await?;
+        self.session_generation.fetch_add(1, Ordering::AcqRel);
+
+        Ok(())
+    }
+
+    /// Refreshes the session unless it has already been refreshed since `generation`
+    /// was read, e.g. by a concurrent request that hit the same expired session.
+    pub async fn refresh_expired_session(
+        &self,
+        generation: u64,
         username: String,
         password: String,
     ) -> Result<(), Error> {
-        match &mut self.active {
-            Some(ActiveProtocol::Aes(p)) => p.refresh_session(username, password).await,
-            Some(ActiveProtocol::AesSsl(p)) => p.refresh_session(username, password).await,
-            Some(ActiveProtocol::Klap(p)) => p.refresh_session(username, password).await,
-            None => Err(anyhow::anyhow!(
-                "Cannot refresh session: protocol not yet initialized (login first)"
-            )
-            .into()),
+        let mut active = self.active.write().await;
+
+        if self.session_generation() != generation {
+            debug!("Session already refreshed by a concurrent request");
+            return Ok(());
         }
+
+        Self::refresh_active_session(&mut active, username, password).await?;
+        self.session_generation.fetch_add(1, Ordering::AcqRel);
+
+        Ok(())
     }
 
-    pub async fn execute_request<R>(&self, request: TapoRequest) -> Result<Option<R>, Error>
+    pub async fn execute_request<R>(&self, request: &TapoRequest) -> Result<Option<R>, Error>
     where
         R: fmt::Debug + DeserializeOwned + TapoResponseExt,
     {
-        match &self.active {
+        match &*self.active.read().await {
             Some(ActiveProtocol::Aes(p)) => p.execute_request(request).await,
             Some(ActiveProtocol::AesSsl(p)) => p.execute_request(request).await,
             Some(ActiveProtocol::Klap(p)) => p.execute_request(request).await,
@@ -160,32 +195,47 @@ impl TapoProtocol {
         }
     }
 
-    async fn discover_protocol_type(&self, url: &str) -> Result<ActiveProtocol, Error> {
+    async fn refresh_active_session(
+        active: &mut Option<ActiveProtocol>,
+        username: String,
+        password: String,
+    ) -> Result<(), Error> {
+        match active {
+            Some(ActiveProtocol::Aes(p)) => p.refresh_session(username, password).await,
+            Some(ActiveProtocol::AesSsl(p)) => p.refresh_session(username, password).await,
+            Some(ActiveProtocol::Klap(p)) => p.refresh_session(username, password).await,
+            None => Err(anyhow::anyhow!(
+                "Cannot refresh session: protocol not yet initialized (login first)"
+            )
+            .into()),
+        }
+    }
+
+    async fn discover_protocol_type(client: &Client, url: &str) -> Result<ActiveProtocol, Error> {
         debug!("Testing the AES protocol...");
-        if self.is_aes_supported(url).await? {
+        if Self::is_aes_supported(client, url).await? {
             debug!("Supported. Setting up the AES protocol...");
-            Ok(ActiveProtocol::Aes(AesProtocol::new(self.client.clone())?))
+            Ok(ActiveProtocol::Aes(AesProtocol::new(client.clone())?))
         } else {
             debug!("Not supported. Setting up the KLAP protocol...");
-            Ok(ActiveProtocol::Klap(KlapProtocol::new(self.client.clone())))
+            Ok(ActiveProtocol::Klap(KlapProtocol::new(client.clone())))
         }
     }
 
-    async fn is_aes_supported(&self, url: &str) -> Result<bool, Error> {
-        match self.test_aes(url).await {
+    async fn is_aes_supported(client: &Client, url: &str) -> Result<bool, Error> {
+        match Self::test_aes(client, url).await {
             Err(Error::Tapo(TapoResponseError::DeviceError { code, .. })) => Ok(code != 1003),
             Err(err) => Err(err),
             Ok(_) => Ok(true),
         }
     }
 
-    async fn test_aes(&self, url: &str) -> Result<(), Error> {
+    async fn test_aes(client: &Client, url: &str) -> Result<(), Error> {
         let request = TapoRequest::ComponentNegotiation(TapoParams::new(EmptyParams));
         let request_string = serde_json::to_string(&request)?;
         debug!("Component negotiation request: {request_string}");
 
-        let response = self
-            .client
+        let response = client
             .post(url)
             .body(request_string)
             .send()
@@ -1,6 +1,11 @@
+use std::ops::RangeInclusive;
+
+use crate::api::ApiClientExt as _;
 use crate::error::Error;
-use crate::requests::{Color, ColorLightSetDeviceInfoParams};
-use crate::responses::{DeviceInfoRgbLightStripResult, DeviceUsageEnergyMonitoringResult};
+use crate::requests::{Color, ColorLightSetDeviceInfoParams, DEFAULT_COLOR_TEMPERATURE_RANGE};
+use crate::responses::{
+    DefaultRgbLightStripState, DeviceInfoRgbLightStripResult, DeviceUsageEnergyMonitoringResult,
+};
 
 tapo_handler! {
     /// Handler for the [L900](https://www.tapo.com/en/search/?q=L900) devices.
@@ -10,6 +15,13 @@ tapo_handler! {
     device_management,
 }
 
+impl_color_control!(RgbLightStripHandler);
+impl_watch!(RgbLightStripHandler);
+impl_schedule_rules!(RgbLightStripHandler);
+impl_antitheft_rules!(RgbLightStripHandler);
+impl_device_settings!(RgbLightStripHandler, DefaultRgbLightStripState);
+impl_on_off_gradually!(RgbLightStripHandler);
+
 impl RgbLightStripHandler {
     /// Returns a [`ColorLightSetDeviceInfoParams`] builder that allows multiple properties to be set in a single request.
     /// [`ColorLightSetDeviceInfoParams::send`] must be called at the end to apply the changes.
@@ -85,4 +97,18 @@ impl RgbLightStripHandler {
             .send(self)
             .await
     }
+
+    /// Returns the range of *color temperatures* supported by the device, in Kelvin.
+    /// Pass it to [`ColorLightSetDeviceInfoParams::color_temperature_range`] to validate or clamp the color temperature.
+    /// The range is read from the device info once per session, [`ColorLightSetDeviceInfoParams::kelvin`] uses it as well.
+    pub async fn get_color_temperature_range(&self) -> Result<RangeInclusive<u16>, Error> {
+        let range = self
+            .client
+            .read()
+            .await
+            .get_color_temperature_range()
+            .await?;
+
+        Ok(range.unwrap_or(DEFAULT_COLOR_TEMPERATURE_RANGE))
+    }
 }
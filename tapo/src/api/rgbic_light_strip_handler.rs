@@ -1,6 +1,15 @@
+use std::ops::RangeInclusive;
+
+use crate::api::ApiClientExt as _;
 use crate::error::Error;
-use crate::requests::{Color, ColorLightSetDeviceInfoParams, LightingEffect, SegmentEffect};
-use crate::responses::{DeviceInfoRgbicLightStripResult, DeviceUsageEnergyMonitoringResult};
+use crate::requests::{
+    Color, ColorLightSetDeviceInfoParams, DEFAULT_COLOR_TEMPERATURE_RANGE, LightingEffect,
+    SegmentEffect, segment_gradient, segments_lighting_effect,
+};
+use crate::responses::{
+    DefaultRgbicLightStripState, DeviceInfoRgbicLightStripResult,
+    DeviceUsageEnergyMonitoringResult, LightingEffectInfo,
+};
 
 tapo_handler! {
     /// Handler for the [L920](https://www.tapo.com/en/search/?q=L920) and
@@ -11,6 +20,13 @@ tapo_handler! {
     device_management,
 }
 
+impl_color_control!(RgbicLightStripHandler);
+impl_watch!(RgbicLightStripHandler);
+impl_schedule_rules!(RgbicLightStripHandler);
+impl_antitheft_rules!(RgbicLightStripHandler);
+impl_device_settings!(RgbicLightStripHandler, DefaultRgbicLightStripState);
+impl_on_off_gradually!(RgbicLightStripHandler);
+
 impl RgbicLightStripHandler {
     /// Returns a [`ColorLightSetDeviceInfoParams`] builder that allows multiple properties to be set in a single request.
     /// [`ColorLightSetDeviceInfoParams::send`] must be called at the end to apply the changes.
@@ -92,6 +108,20 @@ impl RgbicLightStripHandler {
             .await
     }
 
+    /// Returns the range of *color temperatures* supported by the device, in Kelvin.
+    /// Pass it to [`ColorLightSetDeviceInfoParams::color_temperature_range`] to validate or clamp the color temperature.
+    /// The range is read from the device info once per session, [`ColorLightSetDeviceInfoParams::kelvin`] uses it as well.
+    pub async fn get_color_temperature_range(&self) -> Result<RangeInclusive<u16>, Error> {
+        let range = self
+            .client
+            .read()
+            .await
+            .get_color_temperature_range()
+            .await?;
+
+        Ok(range.unwrap_or(DEFAULT_COLOR_TEMPERATURE_RANGE))
+    }
+
     /// Sets a *lighting effect* and turns *on* the device.
     ///
     /// # Arguments
@@ -101,13 +131,24 @@ impl RgbicLightStripHandler {
         &self,
         lighting_effect: impl Into<LightingEffect>,
     ) -> Result<(), Error> {
+        let lighting_effect = lighting_effect.into();
+        lighting_effect.validate_ranges()?;
+
         self.client
             .read()
             .await
-            .set_lighting_effect(lighting_effect.into())
+            .set_lighting_effect(lighting_effect)
             .await
     }
 
+    /// Returns the running *lighting effect* as [`LightingEffectInfo`], or `None` if no effect is running.
+    /// The device only reports the identity of the effect, use [`LightingEffectInfo::preset`] to get the full
+    /// definition of a built-in effect.
+    pub async fn get_lighting_effect(&self) -> Result<Option<LightingEffectInfo>, Error> {
+        let lighting_effect = self.get_device_info().await?.lighting_effect;
+        Ok(lighting_effect.filter(|lighting_effect| lighting_effect.enabled))
+    }
+
     /// Sets a *segment effect* and turns *on* the device.
     ///
     /// This is used for the newer app-defined RGBIC strip effects that cannot be set by
@@ -129,4 +170,73 @@ impl RgbicLightStripHandler {
             .set_segment_effect(segment_effect)
             .await
     }
+
+    /// Returns the number of segments, the individually colored zones of the strip.
+    /// `None` if the firmware doesn't report it.
+    /// The count is read from the device info once per session.
+    pub async fn get_segment_count(&self) -> Result<Option<u8>, Error> {
+        self.client.read().await.get_segment_count().await
+    }
+
+    /// Paints individual segments with static colors and turns *on* the device.
+    /// Pre-existing *lighting effect* will be replaced. Segments that aren't listed are switched off.
+    ///
+    /// # Arguments
+    ///
+    /// * `segments` - the index, between 0 and [`RgbicLightStripHandler::get_segment_count`] excluded,
+    ///   and the `[hue, saturation, brightness]` color of each segment to paint.
+    ///   The indexes can't be checked on firmware versions that don't report the segment count.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use tapo::ApiClient;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let device = ApiClient::new("tapo-username@example.com", "tapo-password")
+    /// #     .l930("192.168.1.100")
+    /// #     .await?;
+    /// // The first two segments red, the third one dim blue.
+    /// device
+    ///     .set_segments(&[(0, [0, 100, 100]), (1, [0, 100, 100]), (2, [240, 100, 30])])
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn set_segments(&self, segments: &[(u8, [u16; 3])]) -> Result<(), Error> {
+        let lighting_effect = segments_lighting_effect(segments)?;
+
+        if let Some(segment_count) = self.get_segment_count().await?
+            && segments.iter().any(|(index, _)| *index >= segment_count)
+        {
+            return Err(Error::Validation {
+                field: "segments".to_string(),
+                message: format!(
+                    "The indexes must be lower than the segment count, {segment_count}"
+                ),
+            });
+        }
+
+        self.set_lighting_effect(lighting_effect).await
+    }
+
+    /// Spreads a gradient over all the segments with [`crate::requests::segment_gradient`] and turns *on* the device.
+    /// Pre-existing *lighting effect* will be replaced.
+    ///
+    /// Fails on firmware versions that don't report the segment count, use [`RgbicLightStripHandler::set_segments`]
+    /// with [`crate::requests::segment_gradient`] and the known segment count instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `stops` - the `[hue, saturation, brightness]` colors of the gradient, from the first to the last segment
+    pub async fn set_segment_gradient(&self, stops: &[[u16; 3]]) -> Result<(), Error> {
+        let segment_count = self.get_segment_count().await?.ok_or_else(|| {
+            Error::Other(anyhow::anyhow!(
+                "The device doesn't report its segment count"
+            ))
+        })?;
+
+        let lighting_effect = segments_lighting_effect(&segment_gradient(stops, segment_count))?;
+        self.set_lighting_effect(lighting_effect).await
+    }
 }
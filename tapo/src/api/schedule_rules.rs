@@ -0,0 +1,97 @@
+/// Implements the `schedule_rules` methods, on the device itself,
+/// or on the child device identified by `self.device_id` if `child` is specified.
+macro_rules! impl_schedule_rules {
+    ($name:ident) => {
+        impl_schedule_rules!(@impl $name, |_handler| None);
+    };
+    ($name:ident, child) => {
+        impl_schedule_rules!(@impl $name, |handler| Some(handler.device_id.as_str()));
+    };
+    (@impl $name:ident, |$handler:ident| $child_device_id:expr) => {
+        impl $name {
+            /// Returns the *schedule rules* (the "Schedule" feature in the Tapo app)
+            /// as [`ScheduleRules`](crate::responses::ScheduleRules).
+            pub async fn get_schedule_rules(
+                &self,
+            ) -> Result<crate::responses::ScheduleRules, crate::error::Error> {
+                let $handler = self;
+                self.client
+                    .read()
+                    .await
+                    .get_schedule_rules($child_device_id)
+                    .await
+            }
+
+            /// Adds an enabled schedule rule and returns it, including its device-assigned `id`.
+            ///
+            /// # Arguments
+            ///
+            /// * `time` - when the rule fires during the day
+            /// * `recurrence` - on which days the rule fires
+            /// * `desired_state` - the state the device transitions to when the rule fires
+            pub async fn add_schedule_rule(
+                &self,
+                time: crate::requests::ScheduleTime,
+                recurrence: crate::requests::ScheduleRecurrence,
+                desired_state: crate::responses::PowerState,
+            ) -> Result<crate::responses::ScheduleRule, crate::error::Error> {
+                let $handler = self;
+                self.client
+                    .read()
+                    .await
+                    .add_schedule_rule($child_device_id, time, recurrence, desired_state)
+                    .await
+            }
+
+            /// Replaces the schedule rule with the same `id`, e.g. to enable or disable it.
+            ///
+            /// # Arguments
+            ///
+            /// * `rule` - a rule returned by `get_schedule_rules` or `add_schedule_rule`, with the changes applied
+            pub async fn edit_schedule_rule(
+                &self,
+                rule: &crate::responses::ScheduleRule,
+            ) -> Result<(), crate::error::Error> {
+                let $handler = self;
+                self.client
+                    .read()
+                    .await
+                    .edit_schedule_rule($child_device_id, rule)
+                    .await
+            }
+
+            /// Removes the schedule rules with the given `ids`.
+            ///
+            /// # Arguments
+            ///
+            /// * `ids` - the `id`s of the rules to remove
+            pub async fn remove_schedule_rules(
+                &self,
+                ids: Vec<String>,
+            ) -> Result<(), crate::error::Error> {
+                let $handler = self;
+                self.client
+                    .read()
+                    .await
+                    .remove_schedule_rules(
+                        $child_device_id,
+                        crate::requests::RemoveRulesParams::remove(ids),
+                    )
+                    .await
+            }
+
+            /// Removes all the schedule rules.
+            pub async fn remove_all_schedule_rules(&self) -> Result<(), crate::error::Error> {
+                let $handler = self;
+                self.client
+                    .read()
+                    .await
+                    .remove_schedule_rules(
+                        $child_device_id,
+                        crate::requests::RemoveRulesParams::remove_all(),
+                    )
+                    .await
+            }
+        }
+    };
+}
@@ -16,15 +16,18 @@
 ///
 /// # Generated code
 ///
-/// * `#[derive(Debug)]` struct with `client: Arc<RwLock<ApiClient>>` field
+/// * `#[derive(Debug, Clone)]` struct with `client: Arc<RwLock<ApiClient>>` field
 ///   (and `ip_address: String` if `ip_address` specified)
 /// * `new(client)` constructor (`new(client, ip_address)` if `ip_address` specified)
-/// * `refresh_session()` method
+/// * `refresh_session()` and `set_session_refresh()` methods
 /// * `get_device_info()` method (typed)
 /// * `get_device_info_json()` method
-/// * `on()` and `off()` methods (if `on_off` specified)
+/// * `batch()` method, and the matching [`Batch`](crate::Batch) methods for every generated request
+///   (unless `ip_address` specified)
+/// * `on()` and `off()` methods and an [`OnOff`](crate::OnOff) impl (if `on_off` specified)
 /// * `get_device_usage()` method (if `device_usage = Type` specified)
-/// * `device_reboot()` and `device_reset()` methods (if `device_management` specified)
+/// * `device_reboot()`, `device_reset()` and firmware methods, and a [`Firmware`](crate::Firmware) impl
+///   (if `device_management` specified)
 /// * `impl HandlerExt` with `get_client()`
 macro_rules! tapo_handler {
     // With on_off + device_usage + device_management
@@ -138,7 +141,7 @@ macro_rules! tapo_handler {
     // Internal: base struct + core methods + HandlerExt (with ip)
     (@base_with_ip $(#[$meta:meta])* $name:ident($device_info:ty)) => {
         $(#[$meta])*
-        #[derive(Debug)]
+        #[derive(Debug, Clone)]
         pub struct $name {
             client: std::sync::Arc<tokio::sync::RwLock<crate::api::ApiClient>>,
             ip_address: String,
@@ -160,7 +163,7 @@ macro_rules! tapo_handler {
     // Internal: base struct + core methods + HandlerExt
     (@base $(#[$meta:meta])* $name:ident($device_info:ty)) => {
         $(#[$meta])*
-        #[derive(Debug)]
+        #[derive(Debug, Clone)]
         pub struct $name {
             client: std::sync::Arc<tokio::sync::RwLock<crate::api::ApiClient>>,
         }
@@ -174,6 +177,7 @@ macro_rules! tapo_handler {
         }
 
         tapo_handler!(@methods $name($device_info));
+        tapo_handler!(@batch $name($device_info));
         tapo_handler!(@handler_ext $name);
     };
 
@@ -181,11 +185,25 @@ macro_rules! tapo_handler {
     (@methods $name:ident($device_info:ty)) => {
         impl $name {
             /// Refreshes the authentication session.
+            ///
+            /// Expired sessions are refreshed automatically, unless disabled with
+            #[doc = concat!("[`", stringify!($name), "::set_session_refresh`].")]
+            /// This method is useful to force a new session regardless.
             pub async fn refresh_session(&mut self) -> Result<&mut Self, crate::error::Error> {
                 self.client.write().await.refresh_session().await?;
                 Ok(self)
             }
 
+            /// Enables or disables the automatic session refresh.
+            /// See [`crate::ApiClient::with_session_refresh`] for details.
+            ///
+            /// # Arguments
+            ///
+            /// * `enabled` - whether expired sessions should be refreshed automatically.
+            pub async fn set_session_refresh(&self, enabled: bool) {
+                self.client.write().await.set_session_refresh(enabled);
+            }
+
             #[doc = concat!(
                 "Returns *device info* as [`", stringify!($device_info), "`].\n",
                 "It is not guaranteed to contain all the properties returned from the Tapo API.\n",
@@ -215,6 +233,30 @@ macro_rules! tapo_handler {
         }
     };
 
+    // Internal: batch builder + get_device_info in batches
+    (@batch $name:ident($device_info:ty)) => {
+        impl $name {
+            /// Returns a [`Batch`](crate::Batch) that sends several requests in a single round trip.
+            pub fn batch(&self) -> crate::Batch<'_, $name> {
+                crate::Batch::new(&self.client)
+            }
+        }
+
+        impl<'h, T> crate::Batch<'h, $name, T> {
+            #[doc = concat!("Adds a request for the *device info* as [`", stringify!($device_info), "`].")]
+            pub fn get_device_info(
+                self,
+            ) -> crate::Batch<'h, $name, <T as crate::api::batch::private::Append<$device_info>>::Output>
+            where
+                T: crate::api::batch::private::Append<$device_info>,
+            {
+                self.push::<$device_info>(crate::requests::TapoRequest::GetDeviceInfo(
+                    crate::requests::TapoParams::new(crate::requests::EmptyParams),
+                ))
+            }
+        }
+    };
+
     // Internal: HandlerExt impl
     (@handler_ext $name:ident) => {
         #[async_trait::async_trait]
@@ -249,6 +291,44 @@ macro_rules! tapo_handler {
                 crate::api::ApiClientExt::set_device_info(&*self.client.read().await, json).await
             }
         }
+
+        impl<'h, T> crate::Batch<'h, $name, T> {
+            /// Adds a request that turns *on* the device.
+            pub fn on(
+                self,
+            ) -> crate::Batch<'h, $name, <T as crate::api::batch::private::Append<()>>::Output>
+            where
+                T: crate::api::batch::private::Append<()>,
+            {
+                self.push_set_device_info::<()>(serde_json::json!({ "device_on": true }))
+            }
+
+            /// Adds a request that turns *off* the device.
+            pub fn off(
+                self,
+            ) -> crate::Batch<'h, $name, <T as crate::api::batch::private::Append<()>>::Output>
+            where
+                T: crate::api::batch::private::Append<()>,
+            {
+                self.push_set_device_info::<()>(serde_json::json!({ "device_on": false }))
+            }
+        }
+
+        tapo_handler!(@on_off_capability $name);
+    };
+
+    // Internal: OnOff capability, delegating to the inherent methods
+    (@on_off_capability $name:ident) => {
+        #[async_trait::async_trait]
+        impl crate::api::OnOff for $name {
+            async fn on(&self) -> Result<(), crate::error::Error> {
+                $name::on(self).await
+            }
+
+            async fn off(&self) -> Result<(), crate::error::Error> {
+                $name::off(self).await
+            }
+        }
     };
 
     // Internal: device_usage
@@ -259,6 +339,20 @@ macro_rules! tapo_handler {
                 self.client.read().await.get_device_usage().await
             }
         }
+
+        impl<'h, T> crate::Batch<'h, $name, T> {
+            #[doc = concat!("Adds a request for the *device usage* as [`", stringify!($device_usage), "`].")]
+            pub fn get_device_usage(
+                self,
+            ) -> crate::Batch<'h, $name, <T as crate::api::batch::private::Append<$device_usage>>::Output>
+            where
+                T: crate::api::batch::private::Append<$device_usage>,
+            {
+                self.push::<$device_usage>(crate::requests::TapoRequest::GetDeviceUsage(
+                    crate::requests::TapoParams::new(crate::requests::EmptyParams),
+                ))
+            }
+        }
     };
 
     // Internal: device_management
@@ -290,6 +384,50 @@ macro_rules! tapo_handler {
             pub async fn device_reset(&self) -> Result<(), crate::error::Error> {
                 crate::api::ApiClientExt::device_reset(&*self.client.read().await).await
             }
+
+            /// Returns the *latest firmware* available for the device
+            /// as [`LatestFirmwareResult`](crate::responses::LatestFirmwareResult).
+            pub async fn get_latest_firmware(
+                &self,
+            ) -> Result<crate::responses::LatestFirmwareResult, crate::error::Error> {
+                self.client.read().await.get_latest_firmware().await
+            }
+
+            /// Returns the progress of the firmware update
+            /// as [`FirmwareDownloadStateResult`](crate::responses::FirmwareDownloadStateResult).
+            pub async fn get_fw_download_state(
+                &self,
+            ) -> Result<crate::responses::FirmwareDownloadStateResult, crate::error::Error> {
+                self.client.read().await.get_fw_download_state().await
+            }
+
+            /// Starts downloading and installing the latest firmware.
+            /// Follow the progress with `get_fw_download_state`.
+            ///
+            /// The device reboots once the firmware is installed,
+            /// so requests fail until it reconnects to the network.
+            pub async fn fw_download(&self) -> Result<(), crate::error::Error> {
+                self.client.read().await.fw_download().await
+            }
+        }
+
+        #[async_trait::async_trait]
+        impl crate::api::Firmware for $name {
+            async fn get_latest_firmware(
+                &self,
+            ) -> Result<crate::responses::LatestFirmwareResult, crate::error::Error> {
+                $name::get_latest_firmware(self).await
+            }
+
+            async fn get_fw_download_state(
+                &self,
+            ) -> Result<crate::responses::FirmwareDownloadStateResult, crate::error::Error> {
+                $name::get_fw_download_state(self).await
+            }
+
+            async fn fw_download(&self) -> Result<(), crate::error::Error> {
+                $name::fw_download(self).await
+            }
         }
     };
 }
@@ -311,11 +449,11 @@ macro_rules! tapo_handler {
 ///
 /// # Generated code
 ///
-/// * Struct with `client: Arc<RwLock<ApiClient>>` and `device_id: String` fields
+/// * `#[derive(Clone)]` struct with `client: Arc<RwLock<ApiClient>>` and `device_id: String` fields
 /// * `new(client, device_id)` constructor
 /// * `get_device_info()` method (typed)
 /// * `get_device_info_json()` method
-/// * `on()` and `off()` methods (if `on_off` specified)
+/// * `on()` and `off()` methods and an [`OnOff`](crate::OnOff) impl (if `on_off` specified)
 macro_rules! tapo_child_handler {
     // With on_off
     (
@@ -338,6 +476,7 @@ macro_rules! tapo_child_handler {
     // Internal: base struct + core methods
     (@base $(#[$meta:meta])* $name:ident($device_info:ty)) => {
         $(#[$meta])*
+        #[derive(Clone)]
         pub struct $name {
             client: std::sync::Arc<tokio::sync::RwLock<crate::api::ApiClient>>,
             device_id: String,
@@ -456,5 +595,7 @@ macro_rules! tapo_child_handler {
                 Ok(())
             }
         }
+
+        tapo_handler!(@on_off_capability $name);
     };
 }
@@ -66,6 +66,20 @@ pub enum Error {
     Other(#[from] anyhow::Error),
 }
 
+impl Error {
+    /// Whether the error indicates that the device session is no longer valid
+    /// and a new login is required.
+    pub(crate) fn is_session_expired(&self) -> bool {
+        matches!(
+            self,
+            Error::Tapo(TapoResponseError::Unauthorized {
+                kind: "SESSION_TIMEOUT" | "SESSION_EXPIRED" | "LOGIN",
+                ..
+            })
+        )
+    }
+}
+
 #[cfg(feature = "python")]
 impl From<Error> for pyo3::PyErr {
     fn from(err: Error) -> pyo3::PyErr {
@@ -89,3 +103,14 @@ impl From<DiscoveryError> for pyo3::PyErr {
         pyo3::exceptions::PyException::new_err(format!("{:?}", err))
     }
 }
+
+/// Firmware Error. Wraps an error that occurred while discovering a specific device
+/// or checking its firmware, see [`crate::FirmwareRollout::check`].
+#[derive(thiserror::Error, Debug)]
+#[error("Failed to check the firmware of the device at {ip}: {source}")]
+pub struct FirmwareError {
+    /// The IP address of the device.
+    pub ip: String,
+    /// The underlying error.
+    pub source: Error,
+}
@@ -0,0 +1,509 @@
+//! Export of energy monitoring results to CSV and to the InfluxDB line protocol.
+//!
+//! Requires the `export` feature flag.
+//!
+//! [`EnergyDataResult`], [`PowerDataResult`] and [`EnergyUsageResult`] implement [`Export`],
+//! which turns them into CSV rows or InfluxDB line protocol points,
+//! tagged with the `device_id`, `nickname` and `model` of the [`DeviceTags`] they are exported with.
+//!
+//! All timestamps are exported in UTC. The entries of [`EnergyDataResult`] and [`PowerDataResult`]
+//! are already in UTC, while the `local_time` of [`EnergyUsageResult`] is converted using the
+//! [`DeviceTags::utc_offset`] of the device. Without it, the local time zone of the machine
+//! running the export is used instead, which is only correct if it matches the device's.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! # use chrono::Utc;
+//! # use tapo::ApiClient;
+//! # use tapo::export::{DeviceTags, Export as _};
+//! # use tapo::requests::EnergyDataInterval;
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let device = ApiClient::new("tapo-username@example.com", "tapo-password")
+//!     .p110("192.168.1.100")
+//!     .await?;
+//!
+//! let tags = DeviceTags::from(&device.get_device_info().await?);
+//! let today = Utc::now().date_naive();
+//!
+//! let energy_data = device
+//!     .get_energy_data(EnergyDataInterval::Hourly {
+//!         start_date: today,
+//!         end_date: today,
+//!     })
+//!     .await?;
+//!
+//! println!("{}", energy_data.to_csv(&tags));
+//! print!("{}", energy_data.to_line_protocol(&tags));
+//! # Ok(())
+//! # }
+//! ```
+
+use std::borrow::Cow;
+use std::fmt::Write as _;
+
+use chrono::{DateTime, FixedOffset, Local, NaiveDateTime, SecondsFormat, TimeZone as _, Utc};
+
+use crate::responses::{
+    DeviceInfoPlugEnergyMonitoringResult, EnergyDataResult, EnergyUsageResult, PowerDataResult,
+    PowerStripPlugEnergyMonitoringResult,
+};
+
+/// The device an exported result belongs to.
+/// Each CSV row and line protocol point is tagged with these values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeviceTags {
+    /// The device ID.
+    pub device_id: String,
+    /// The nickname of the device.
+    pub nickname: String,
+    /// The model of the device, e.g. `P110`.
+    pub model: String,
+    /// The offset of the device's local time from UTC, used to convert its local times to UTC.
+    /// `None` falls back to the local time zone.
+    pub utc_offset: Option<FixedOffset>,
+}
+
+impl DeviceTags {
+    /// Creates the tags of a device.
+    ///
+    /// # Arguments
+    ///
+    /// * `device_id` - the device ID
+    /// * `nickname` - the nickname of the device
+    /// * `model` - the model of the device
+    pub fn new(
+        device_id: impl Into<String>,
+        nickname: impl Into<String>,
+        model: impl Into<String>,
+    ) -> Self {
+        Self {
+            device_id: device_id.into(),
+            nickname: nickname.into(),
+            model: model.into(),
+            utc_offset: None,
+        }
+    }
+
+    /// Sets the offset of the device's local time from UTC.
+    ///
+    /// # Arguments
+    ///
+    /// * `utc_offset` - the offset, e.g. from the `time_diff` of the device info
+    pub fn with_utc_offset(mut self, utc_offset: FixedOffset) -> Self {
+        self.utc_offset = Some(utc_offset);
+        self
+    }
+}
+
+/// The device info reports `time_diff` in minutes.
+fn utc_offset_from_time_diff(time_diff: Option<i64>) -> Option<FixedOffset> {
+    time_diff
+        .and_then(|minutes| i32::try_from(minutes * 60).ok())
+        .and_then(FixedOffset::east_opt)
+}
+
+impl From<&DeviceInfoPlugEnergyMonitoringResult> for DeviceTags {
+    fn from(device_info: &DeviceInfoPlugEnergyMonitoringResult) -> Self {
+        Self {
+            utc_offset: utc_offset_from_time_diff(device_info.time_diff),
+            ..Self::new(
+                &device_info.device_id,
+                &device_info.nickname,
+                &device_info.model,
+            )
+        }
+    }
+}
+
+/// The child plugs don't report `time_diff`, use [`DeviceTags::with_utc_offset`]
+/// with the offset of the power strip.
+impl From<&PowerStripPlugEnergyMonitoringResult> for DeviceTags {
+    fn from(device_info: &PowerStripPlugEnergyMonitoringResult) -> Self {
+        Self::new(
+            &device_info.device_id,
+            &device_info.nickname,
+            &device_info.model,
+        )
+    }
+}
+
+/// Exports a result to CSV and to the InfluxDB line protocol.
+pub trait Export {
+    /// The CSV header row.
+    const CSV_HEADER: &'static str;
+
+    /// Returns the CSV rows, one per line, without the header row.
+    /// Useful for appending to an existing file.
+    ///
+    /// # Arguments
+    ///
+    /// * `tags` - the device the result belongs to
+    fn to_csv_rows(&self, tags: &DeviceTags) -> String;
+
+    /// Returns the InfluxDB line protocol points, one per line, with nanosecond precision timestamps.
+    ///
+    /// # Arguments
+    ///
+    /// * `tags` - the device the result belongs to
+    fn to_line_protocol(&self, tags: &DeviceTags) -> String;
+
+    /// Returns the CSV header row followed by the CSV rows.
+    ///
+    /// # Arguments
+    ///
+    /// * `tags` - the device the result belongs to
+    fn to_csv(&self, tags: &DeviceTags) -> String {
+        format!("{}\n{}", Self::CSV_HEADER, self.to_csv_rows(tags))
+    }
+}
+
+impl Export for EnergyDataResult {
+    const CSV_HEADER: &'static str =
+        "device_id,nickname,model,start_date_time,interval_length,energy";
+
+    fn to_csv_rows(&self, tags: &DeviceTags) -> String {
+        let mut csv = String::new();
+
+        for entry in &self.entries {
+            // safe: writing to a String never fails.
+            writeln!(
+                csv,
+                "{},{},{}",
+                csv_tags(tags),
+                csv_datetime(&entry.start_date_time),
+                csv_values(&[&self.interval_length, &entry.energy]),
+            )
+            .unwrap();
+        }
+
+        csv
+    }
+
+    fn to_line_protocol(&self, tags: &DeviceTags) -> String {
+        let interval_length = self.interval_length.to_string();
+        let mut lines = String::new();
+
+        for entry in &self.entries {
+            // safe: writing to a String never fails.
+            writeln!(
+                lines,
+                "tapo_energy_data,{} energy={}i {}",
+                line_protocol_tags(tags, &[("interval_length", &interval_length)]),
+                entry.energy,
+                line_protocol_timestamp(&entry.start_date_time),
+            )
+            .unwrap();
+        }
+
+        lines
+    }
+}
+
+impl Export for PowerDataResult {
+    const CSV_HEADER: &'static str =
+        "device_id,nickname,model,start_date_time,interval_length,power";
+
+    /// Entries without data have an empty `power` column.
+    fn to_csv_rows(&self, tags: &DeviceTags) -> String {
+        let mut csv = String::new();
+
+        for entry in &self.entries {
+            let power = entry
+                .power
+                .map(|power| power.to_string())
+                .unwrap_or_default();
+
+            // safe: writing to a String never fails.
+            writeln!(
+                csv,
+                "{},{},{}",
+                csv_tags(tags),
+                csv_datetime(&entry.start_date_time),
+                csv_values(&[&self.interval_length, &power]),
+            )
+            .unwrap();
+        }
+
+        csv
+    }
+
+    /// Entries without data are skipped.
+    fn to_line_protocol(&self, tags: &DeviceTags) -> String {
+        let interval_length = self.interval_length.to_string();
+        let mut lines = String::new();
+
+        for entry in &self.entries {
+            let Some(power) = entry.power else {
+                continue;
+            };
+
+            // safe: writing to a String never fails.
+            writeln!(
+                lines,
+                "tapo_power_data,{} power={}i {}",
+                line_protocol_tags(tags, &[("interval_length", &interval_length)]),
+                power,
+                line_protocol_timestamp(&entry.start_date_time),
+            )
+            .unwrap();
+        }
+
+        lines
+    }
+}
+
+impl Export for EnergyUsageResult {
+    const CSV_HEADER: &'static str = "device_id,nickname,model,local_time,current_power,today_energy,today_runtime,month_energy,month_runtime";
+
+    /// The `local_time` column is in UTC. `current_power` is empty if the device doesn't report it.
+    fn to_csv_rows(&self, tags: &DeviceTags) -> String {
+        let current_power = self
+            .current_power
+            .map(|power| power.to_string())
+            .unwrap_or_default();
+
+        format!(
+            "{},{},{}\n",
+            csv_tags(tags),
+            csv_datetime(&local_to_utc(&self.local_time, tags.utc_offset)),
+            csv_values(&[
+                &current_power,
+                &self.today_energy,
+                &self.today_runtime,
+                &self.month_energy,
+                &self.month_runtime,
+            ]),
+        )
+    }
+
+    fn to_line_protocol(&self, tags: &DeviceTags) -> String {
+        let mut fields = Vec::with_capacity(5);
+        if let Some(current_power) = self.current_power {
+            fields.push(format!("current_power={current_power}i"));
+        }
+        fields.push(format!("today_energy={}i", self.today_energy));
+        fields.push(format!("today_runtime={}i", self.today_runtime));
+        fields.push(format!("month_energy={}i", self.month_energy));
+        fields.push(format!("month_runtime={}i", self.month_runtime));
+
+        format!(
+            "tapo_energy_usage,{} {} {}\n",
+            line_protocol_tags(tags, &[]),
+            fields.join(","),
+            line_protocol_timestamp(&local_to_utc(&self.local_time, tags.utc_offset)),
+        )
+    }
+}
+
+/// Converts a device's local time to UTC, using its offset, or the local time zone if it's unknown.
+/// Ambiguous times, at the end of daylight saving time, resolve to the earliest one.
+fn local_to_utc(local_time: &NaiveDateTime, utc_offset: Option<FixedOffset>) -> DateTime<Utc> {
+    if let Some(utc_offset) = utc_offset {
+        return (*local_time - utc_offset).and_utc();
+    }
+
+    Local
+        .from_local_datetime(local_time)
+        .earliest()
+        .map(|local_time| local_time.to_utc())
+        .unwrap_or_else(|| local_time.and_utc())
+}
+
+fn csv_tags(tags: &DeviceTags) -> String {
+    [&tags.device_id, &tags.nickname, &tags.model]
+        .map(|tag| csv_field(tag))
+        .join(",")
+}
+
+fn csv_datetime(datetime: &DateTime<Utc>) -> String {
+    datetime.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+fn csv_values(values: &[&dyn std::fmt::Display]) -> String {
+    values
+        .iter()
+        .map(|value| value.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Quotes a CSV field that contains a delimiter, a quote or a line break.
+fn csv_field(value: &str) -> Cow<'_, str> {
+    if value.contains([',', '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", value.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(value)
+    }
+}
+
+/// Returns the tag set of the device tags and the `extra` tags, in key order.
+/// Tags with an empty value are omitted, since the line protocol doesn't allow them.
+fn line_protocol_tags(tags: &DeviceTags, extra: &[(&str, &str)]) -> String {
+    let mut tag_set = vec![
+        ("device_id", tags.device_id.as_str()),
+        ("model", tags.model.as_str()),
+        ("nickname", tags.nickname.as_str()),
+    ];
+    tag_set.extend_from_slice(extra);
+    tag_set.sort_by_key(|(key, _)| *key);
+
+    tag_set
+        .into_iter()
+        .filter(|(_, value)| !value.is_empty())
+        .map(|(key, value)| format!("{key}={}", line_protocol_tag_value(value)))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Escapes the backslashes, commas, equal signs and spaces of a tag value.
+/// Line breaks aren't allowed in tag values, so they are replaced by escaped spaces.
+fn line_protocol_tag_value(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+
+    for c in value.chars() {
+        match c {
+            '\\' | ',' | '=' | ' ' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\n' | '\r' => escaped.push_str("\\ "),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+fn line_protocol_timestamp(datetime: &DateTime<Utc>) -> i64 {
+    datetime
+        .timestamp_nanos_opt()
+        .unwrap_or_else(|| datetime.timestamp() * 1_000_000_000)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use crate::responses::{EnergyDataIntervalResult, PowerDataIntervalResult};
+
+    use super::*;
+
+    fn tags() -> DeviceTags {
+        DeviceTags::new("802D", "Desk, \"left\" plug", "P110")
+    }
+
+    fn datetime(hour: u32) -> DateTime<Utc> {
+        NaiveDate::from_ymd_opt(2026, 3, 9)
+            .unwrap()
+            .and_hms_opt(hour, 0, 0)
+            .unwrap()
+            .and_utc()
+    }
+
+    #[test]
+    fn energy_data_is_exported() {
+        let result = EnergyDataResult {
+            local_time: datetime(3).naive_utc(),
+            start_date_time: datetime(0),
+            entries: vec![
+                EnergyDataIntervalResult {
+                    start_date_time: datetime(0),
+                    energy: 12,
+                },
+                EnergyDataIntervalResult {
+                    start_date_time: datetime(1),
+                    energy: 7,
+                },
+            ],
+            interval_length: 60,
+        };
+
+        assert_eq!(
+            result.to_csv(&tags()),
+            "device_id,nickname,model,start_date_time,interval_length,energy\n\
+             802D,\"Desk, \"\"left\"\" plug\",P110,2026-03-09T00:00:00Z,60,12\n\
+             802D,\"Desk, \"\"left\"\" plug\",P110,2026-03-09T01:00:00Z,60,7\n"
+        );
+        assert_eq!(
+            result.to_line_protocol(&tags()),
+            "tapo_energy_data,device_id=802D,interval_length=60,model=P110,nickname=Desk\\,\\ \"left\"\\ plug energy=12i 1773014400000000000\n\
+             tapo_energy_data,device_id=802D,interval_length=60,model=P110,nickname=Desk\\,\\ \"left\"\\ plug energy=7i 1773018000000000000\n"
+        );
+    }
+
+    #[test]
+    fn power_data_entries_without_data_are_empty_or_skipped() {
+        let result = PowerDataResult {
+            start_date_time: datetime(0),
+            end_date_time: datetime(2),
+            entries: vec![
+                PowerDataIntervalResult {
+                    start_date_time: datetime(0),
+                    power: None,
+                },
+                PowerDataIntervalResult {
+                    start_date_time: datetime(1),
+                    power: Some(35),
+                },
+            ],
+            interval_length: 60,
+        };
+        let tags = DeviceTags::new("802D", "", "P110");
+
+        assert_eq!(
+            result.to_csv_rows(&tags),
+            "802D,,P110,2026-03-09T00:00:00Z,60,\n802D,,P110,2026-03-09T01:00:00Z,60,35\n"
+        );
+        assert_eq!(
+            result.to_line_protocol(&tags),
+            "tapo_power_data,device_id=802D,interval_length=60,model=P110 power=35i 1773018000000000000\n"
+        );
+    }
+
+    #[test]
+    fn energy_usage_local_time_is_converted_to_utc() {
+        let local_time = datetime(3).naive_utc();
+        let result = EnergyUsageResult {
+            current_power: None,
+            electricity_charge: None,
+            local_time,
+            month_energy: 1200,
+            month_runtime: 6000,
+            today_energy: 80,
+            today_runtime: 180,
+        };
+        // UTC+2, so 03:00 local time is 01:00 UTC.
+        let tags = DeviceTags::new("802D", "Desk", "P110")
+            .with_utc_offset(utc_offset_from_time_diff(Some(120)).unwrap());
+
+        assert_eq!(
+            result.to_csv_rows(&tags),
+            "802D,Desk,P110,2026-03-09T01:00:00Z,,80,180,1200,6000\n"
+        );
+        assert_eq!(
+            result.to_line_protocol(&tags),
+            "tapo_energy_usage,device_id=802D,model=P110,nickname=Desk today_energy=80i,today_runtime=180i,month_energy=1200i,month_runtime=6000i 1773018000000000000\n"
+        );
+    }
+
+    #[test]
+    fn tag_values_are_escaped() {
+        assert_eq!(
+            line_protocol_tag_value("a\\b,c=d e\r\nf"),
+            "a\\\\b\\,c\\=d\\ e\\ \\ f"
+        );
+    }
+
+    #[test]
+    fn negative_utc_offsets_are_converted() {
+        let utc_offset = utc_offset_from_time_diff(Some(-300));
+
+        assert_eq!(
+            local_to_utc(&datetime(3).naive_utc(), utc_offset),
+            datetime(8)
+        );
+        assert_eq!(utc_offset_from_time_diff(None), None);
+    }
+}
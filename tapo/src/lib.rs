@@ -14,6 +14,7 @@
 //! - `debug` — exposes additional methods that are valuable in debug scenarios or when investigating new device functionality.
 //! - `json-schema` — derives [`schemars::JsonSchema`](https://docs.rs/schemars) on public types so they can be serialized into a JSON Schema.
 //! - `python` — enables [PyO3](https://pyo3.rs) bindings used by the `tapo-py` crate. Not intended for direct use from Rust.
+//! - `testing` — exposes the `testing` module, with mock devices that serve the device API on localhost.
 //!
 //! # Example with L530
 //! ```rust,no_run
@@ -85,11 +86,16 @@ mod api;
 mod error;
 mod utils;
 
+#[cfg(feature = "export")]
+pub mod export;
 #[cfg(feature = "python")]
 pub mod python;
 
 pub mod requests;
 pub mod responses;
+pub mod scenes;
+#[cfg(feature = "testing")]
+pub mod testing;
 
 pub use api::*;
 pub use error::*;
@@ -1,5 +1,6 @@
 //! Tapo request objects.
 
+mod child_device_pairing;
 mod control_child;
 mod device_reboot;
 mod energy_data_interval;
@@ -12,6 +13,8 @@ mod login_device;
 mod multiple_request;
 mod play_alarm;
 mod power_data_interval;
+mod power_protection;
+mod rule_list;
 mod secure_passthrough;
 mod set_device_info;
 mod smart_cam;
@@ -19,11 +22,13 @@ mod tapo_request;
 mod timer;
 
 pub use crate::responses::TemperatureUnitKE100;
+pub use crate::responses::{ScheduleRecurrence, ScheduleTime};
 pub use energy_data_interval::*;
 pub use play_alarm::*;
 pub use power_data_interval::*;
 pub use set_device_info::*;
 
+pub(crate) use child_device_pairing::*;
 pub(crate) use control_child::*;
 pub(crate) use device_reboot::*;
 pub(crate) use get_child_device_list::*;
@@ -33,6 +38,8 @@ pub(crate) use get_trigger_logs::*;
 pub(crate) use handshake::*;
 pub(crate) use login_device::*;
 pub(crate) use multiple_request::*;
+pub(crate) use power_protection::*;
+pub(crate) use rule_list::*;
 pub(crate) use secure_passthrough::*;
 pub(crate) use smart_cam::*;
 pub(crate) use tapo_request::*;
@@ -0,0 +1,41 @@
+use serde::Serialize;
+
+use crate::responses::ScannedChildDevice;
+
+#[derive(Debug, Serialize)]
+pub(crate) struct ChildDeviceListParams {
+    child_device_list: Vec<ChildDeviceParams>,
+}
+
+#[derive(Debug, Serialize)]
+struct ChildDeviceParams {
+    device_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    category: Option<String>,
+}
+
+impl ChildDeviceListParams {
+    pub(crate) fn add(devices: &[ScannedChildDevice]) -> Self {
+        Self {
+            child_device_list: devices
+                .iter()
+                .map(|device| ChildDeviceParams {
+                    device_id: device.device_id.clone(),
+                    category: Some(device.category.clone()),
+                })
+                .collect(),
+        }
+    }
+
+    pub(crate) fn remove(device_ids: Vec<String>) -> Self {
+        Self {
+            child_device_list: device_ids
+                .into_iter()
+                .map(|device_id| ChildDeviceParams {
+                    device_id,
+                    category: None,
+                })
+                .collect(),
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct SetProtectionPowerParams {
+    enabled: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    protection_power: Option<u16>,
+}
+
+impl SetProtectionPowerParams {
+    pub(crate) fn enable(protection_power: u16) -> Self {
+        Self {
+            enabled: true,
+            protection_power: Some(protection_power),
+        }
+    }
+
+    pub(crate) fn disable() -> Self {
+        Self {
+            enabled: false,
+            protection_power: None,
+        }
+    }
+}
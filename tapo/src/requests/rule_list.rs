@@ -0,0 +1,42 @@
+use serde::Serialize;
+
+/// Parameters of the paginated `get_*_rules` requests, e.g. `get_schedule_rules`.
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GetRulesParams {
+    start_index: u64,
+}
+
+impl GetRulesParams {
+    pub(crate) fn new(start_index: u64) -> Self {
+        Self { start_index }
+    }
+}
+
+/// Parameters of the `remove_*_rules` requests, e.g. `remove_schedule_rules`.
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct RemoveRulesParams {
+    remove_all: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    rule_list: Vec<RuleId>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct RuleId {
+    id: String,
+}
+
+impl RemoveRulesParams {
+    pub(crate) fn remove_all() -> Self {
+        Self {
+            remove_all: true,
+            rule_list: Vec::new(),
+        }
+    }
+
+    pub(crate) fn remove(ids: Vec<String>) -> Self {
+        Self {
+            remove_all: false,
+            rule_list: ids.into_iter().map(|id| RuleId { id }).collect(),
+        }
+    }
+}
@@ -3,12 +3,16 @@ mod color_light;
 mod generic_device;
 mod light;
 mod lighting_effect;
+mod rgb_color;
+mod segment_colors;
 mod segment_effect;
 mod trv;
 
 pub use color::*;
 pub use color_light::*;
 pub use lighting_effect::*;
+pub use rgb_color::*;
+pub use segment_colors::*;
 pub use segment_effect::*;
 
 pub(crate) use generic_device::*;
@@ -4,7 +4,10 @@ use serde::Serialize;
 
 use crate::HandlerExt;
 use crate::error::Error;
-use crate::requests::Color;
+use crate::requests::{Color, RgbColor};
+
+/// The *color temperature* range of the devices that don't report their own.
+pub(crate) const DEFAULT_COLOR_TEMPERATURE_RANGE: RangeInclusive<u16> = 2500..=6500;
 
 /// Builder that is used by the [`crate::ColorLightHandler::set`] API to set multiple properties in a single request.
 #[derive(Debug, Clone, Default, Serialize)]
@@ -20,6 +23,14 @@ pub struct ColorLightSetDeviceInfoParams {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "color_temp")]
     color_temperature: Option<u16>,
+    #[serde(skip)]
+    brightness_from_color: bool,
+    #[serde(skip)]
+    clamp_color_temperature: bool,
+    #[serde(skip)]
+    color_temperature_range: Option<RangeInclusive<u16>>,
+    #[serde(skip)]
+    invalid_color: Option<(String, String)>,
 }
 
 impl ColorLightSetDeviceInfoParams {
@@ -43,6 +54,7 @@ impl ColorLightSetDeviceInfoParams {
     /// * `brightness` - between 1 and 100
     pub fn brightness(mut self, value: u8) -> Self {
         self.brightness = Some(value);
+        self.brightness_from_color = false;
         self
     }
 
@@ -58,6 +70,8 @@ impl ColorLightSetDeviceInfoParams {
         self.hue = Some(hue);
         self.saturation = Some(saturation);
         self.color_temperature = Some(color_temperature);
+        self.clamp_color_temperature = false;
+        self.invalid_color = None;
 
         self
     }
@@ -73,10 +87,64 @@ impl ColorLightSetDeviceInfoParams {
         self.hue = Some(hue);
         self.saturation = Some(saturation);
         self.color_temperature = Some(0);
+        self.clamp_color_temperature = false;
+        self.invalid_color = None;
 
         self
     }
 
+    /// Sets the *hue* and *saturation* of an RGB color.
+    /// [`ColorLightSetDeviceInfoParams::send`] must be called at the end to apply the changes.
+    /// The device will also be turned *on*, unless [`ColorLightSetDeviceInfoParams::off`] is called.
+    ///
+    /// The *value* of the color is used as the *brightness*, unless [`ColorLightSetDeviceInfoParams::brightness`] is called.
+    /// Grays are set with the lowest *saturation* supported by the device.
+    ///
+    /// # Arguments
+    ///
+    /// * `r`, `g`, `b` - the red, green and blue channels, between 0 and 255
+    pub fn rgb(self, r: u8, g: u8, b: u8) -> Self {
+        self.rgb_color(RgbColor::new(r, g, b))
+    }
+
+    /// Sets the *hue* and *saturation* of a CSS color, like [`ColorLightSetDeviceInfoParams::rgb`].
+    /// [`ColorLightSetDeviceInfoParams::send`] must be called at the end to apply the changes.
+    /// The device will also be turned *on*, unless [`ColorLightSetDeviceInfoParams::off`] is called.
+    ///
+    /// An invalid color is reported by [`ColorLightSetDeviceInfoParams::send`].
+    /// Named colors, e.g. `orange`, aren't supported.
+    ///
+    /// # Arguments
+    ///
+    /// * `color` - in the `#rgb`, `#rrggbb` or `rgb(r, g, b)` notation, e.g. `#ff8800`
+    pub fn hex(self, color: &str) -> Self {
+        match color.parse::<RgbColor>() {
+            Ok(color) => self.rgb_color(color),
+            Err(e) => self.invalid_color(e),
+        }
+    }
+
+    /// Sets the *hue* and *saturation* of a CIE 1931 chromaticity, as used by Zigbee and Philips Hue.
+    /// [`ColorLightSetDeviceInfoParams::send`] must be called at the end to apply the changes.
+    /// The device will also be turned *on*, unless [`ColorLightSetDeviceInfoParams::off`] is called.
+    ///
+    /// Chromaticities outside of the sRGB gamut are mapped to the nearest color the device can display.
+    /// The *brightness* is left unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - between 0 and 1
+    /// * `y` - between 0 and 1, excluding 0
+    pub fn xy(self, x: f64, y: f64) -> Self {
+        match RgbColor::from_xy(x, y) {
+            Ok(color) => {
+                let (hue, saturation, _) = color.to_hsv();
+                self.hue_saturation(hue, saturation.max(1))
+            }
+            Err(e) => self.invalid_color(e),
+        }
+    }
+
     /// Sets the *color temperature*. [`ColorLightSetDeviceInfoParams::send`] must be called at the end to apply the changes.
     /// The device will also be turned *on*, unless [`ColorLightSetDeviceInfoParams::off`] is called.
     ///
@@ -87,7 +155,39 @@ impl ColorLightSetDeviceInfoParams {
         self.hue = None;
         self.saturation = None;
         self.color_temperature = Some(value);
+        self.clamp_color_temperature = false;
+        self.invalid_color = None;
+
+        self
+    }
 
+    /// Sets the *color temperature*, clamped to the range supported by the device.
+    /// [`ColorLightSetDeviceInfoParams::send`] must be called at the end to apply the changes.
+    /// The device will also be turned *on*, unless [`ColorLightSetDeviceInfoParams::off`] is called.
+    ///
+    /// The range is read from the device info once per session, unless
+    /// [`ColorLightSetDeviceInfoParams::color_temperature_range`] is called.
+    /// Devices that don't report their range are clamped to 2500 to 6500.
+    ///
+    /// # Arguments
+    ///
+    /// * `kelvin` - the color temperature in Kelvin
+    pub fn kelvin(self, kelvin: u16) -> Self {
+        let mut params = self.color_temperature(kelvin);
+        params.clamp_color_temperature = true;
+        params
+    }
+
+    /// Sets the *color temperature* range supported by the device, used to validate
+    /// [`ColorLightSetDeviceInfoParams::color_temperature`] and clamp [`ColorLightSetDeviceInfoParams::kelvin`].
+    ///
+    /// The range of a device is returned by the `get_color_temperature_range` method of its handler.
+    ///
+    /// # Arguments
+    ///
+    /// * `range` - the lowest and highest color temperature in Kelvin, e.g. `2500..=6500`
+    pub fn color_temperature_range(mut self, range: RangeInclusive<u16>) -> Self {
+        self.color_temperature_range = Some(range);
         self
     }
 
@@ -96,10 +196,17 @@ impl ColorLightSetDeviceInfoParams {
     /// # Arguments
     ///
     /// * `handler` - `ColorLightHandler`, `RgbLightStripHandler`, or `RgbicLightStripHandler` instance
-    pub async fn send(self, handler: &impl HandlerExt) -> Result<(), Error> {
-        self.validate()?;
-        let json = serde_json::to_value(&self)?;
-        handler.get_client().await.set_device_info(json).await
+    pub async fn send(mut self, handler: &impl HandlerExt) -> Result<(), Error> {
+        let client = handler.get_client().await;
+
+        if self.clamp_color_temperature && self.color_temperature_range.is_none() {
+            self.color_temperature_range = client.get_color_temperature_range().await?;
+        }
+
+        let params = self.clamped();
+        params.validate()?;
+        let json = serde_json::to_value(&params)?;
+        client.set_device_info(json).await
     }
 }
 
@@ -109,7 +216,50 @@ impl ColorLightSetDeviceInfoParams {
         Self::default()
     }
 
+    fn rgb_color(self, color: RgbColor) -> Self {
+        let (hue, saturation, value) = color.to_hsv();
+        let mut params = self.hue_saturation(hue, saturation.max(1));
+
+        if params.brightness.is_none() || params.brightness_from_color {
+            params.brightness = Some(value.max(1));
+            params.brightness_from_color = true;
+        }
+
+        params
+    }
+
+    fn invalid_color(mut self, error: Error) -> Self {
+        if let Error::Validation { field, message } = error {
+            self.invalid_color = Some((field, message));
+        }
+        self
+    }
+
+    fn clamped(mut self) -> Self {
+        if self.clamp_color_temperature
+            && let Some(color_temperature) = self.color_temperature
+        {
+            let range = self.get_color_temperature_range();
+            self.color_temperature = Some(color_temperature.clamp(*range.start(), *range.end()));
+        }
+
+        self
+    }
+
+    fn get_color_temperature_range(&self) -> RangeInclusive<u16> {
+        self.color_temperature_range
+            .clone()
+            .unwrap_or(DEFAULT_COLOR_TEMPERATURE_RANGE)
+    }
+
     fn validate(&self) -> Result<(), Error> {
+        if let Some((field, message)) = &self.invalid_color {
+            return Err(Error::Validation {
+                field: field.clone(),
+                message: message.clone(),
+            });
+        }
+
         if self.device_on.is_none()
             && self.brightness.is_none()
             && self.hue.is_none()
@@ -158,15 +308,15 @@ impl ColorLightSetDeviceInfoParams {
             });
         }
 
-        const COLOR_TEMPERATURE_RANGE: RangeInclusive<u16> = 2500..=6500;
+        let range = self.get_color_temperature_range();
         if let Some(color_temperature) = self.color_temperature
             && self.hue.is_none()
             && self.saturation.is_none()
-            && !COLOR_TEMPERATURE_RANGE.contains(&color_temperature)
+            && !range.contains(&color_temperature)
         {
             return Err(Error::Validation {
                 field: "color_temperature".to_string(),
-                message: "Must be between 2500 and 6500".to_string(),
+                message: format!("Must be between {} and {}", range.start(), range.end()),
             });
         }
 
@@ -243,6 +393,94 @@ mod tests {
         assert!(params.send(&MockHandler).await.is_ok())
     }
 
+    #[tokio::test]
+    async fn rgb_sets_hue_saturation_and_brightness() {
+        let params = ColorLightSetDeviceInfoParams::new()
+            .color_temperature(3000)
+            .rgb(255, 136, 0);
+        assert_eq!(params.hue, Some(32));
+        assert_eq!(params.saturation, Some(100));
+        assert_eq!(params.brightness, Some(100));
+        assert_eq!(params.color_temperature, Some(0));
+
+        let params = ColorLightSetDeviceInfoParams::new()
+            .rgb(0, 0, 0)
+            .rgb(0, 128, 0);
+        assert_eq!(params.hue, Some(120));
+        assert_eq!(params.brightness, Some(50));
+
+        let params = ColorLightSetDeviceInfoParams::new()
+            .brightness(40)
+            .hex("#808080");
+        assert_eq!(params.saturation, Some(1));
+        assert_eq!(params.brightness, Some(40));
+
+        assert!(params.send(&MockHandler).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn invalid_color_validation() {
+        let result = ColorLightSetDeviceInfoParams::new()
+            .hex("orange")
+            .send(&MockHandler)
+            .await;
+        assert!(matches!(
+            result.err(),
+            Some(Error::Validation { field, .. }) if field == "color"
+        ));
+
+        let result = ColorLightSetDeviceInfoParams::new()
+            .xy(0.5, 0.0)
+            .send(&MockHandler)
+            .await;
+        assert!(matches!(
+            result.err(),
+            Some(Error::Validation { field, .. }) if field == "xy"
+        ));
+
+        let params = ColorLightSetDeviceInfoParams::new()
+            .hex("orange")
+            .xy(0.64, 0.33);
+        assert_eq!(params.hue, Some(0));
+        assert!(params.send(&MockHandler).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn kelvin_is_clamped_to_the_color_temperature_range() {
+        let params = ColorLightSetDeviceInfoParams::new().kelvin(9000);
+        assert_eq!(params.clamped().color_temperature, Some(6500));
+
+        let params = ColorLightSetDeviceInfoParams::new()
+            .kelvin(2000)
+            .color_temperature_range(2200..=6500);
+        assert_eq!(params.clamped().color_temperature, Some(2200));
+
+        let params = ColorLightSetDeviceInfoParams::new()
+            .kelvin(2000)
+            .color_temperature(2000);
+        assert_eq!(params.clamped().color_temperature, Some(2000));
+    }
+
+    #[tokio::test]
+    async fn color_temperature_range_validation() {
+        let result = ColorLightSetDeviceInfoParams::new()
+            .color_temperature_range(2200..=6500)
+            .color_temperature(2200)
+            .send(&MockHandler)
+            .await;
+        assert!(result.is_ok());
+
+        let result = ColorLightSetDeviceInfoParams::new()
+            .color_temperature_range(2200..=6500)
+            .color_temperature(2100)
+            .send(&MockHandler)
+            .await;
+        assert!(matches!(
+            result.err(),
+            Some(Error::Validation { field, message }) if field == "color_temperature" && message == "Must be between 2200 and 6500"
+        ));
+    }
+
     #[tokio::test]
     async fn no_property_validation() {
         let params = ColorLightSetDeviceInfoParams::new();
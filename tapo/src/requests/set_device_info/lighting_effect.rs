@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use serde_with::{BoolFromInt, serde_as};
 
+use crate::error::Error;
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 #[cfg_attr(
@@ -15,8 +17,35 @@ pub enum LightingEffectType {
     Static,
 }
 
+/// Parameters for the `set_lighting_effect` request, built from a [`LightingEffectPreset`] or custom.
+///
+/// Effects can be saved to and loaded from JSON or TOML files with `serde`,
+/// and checked with [`LightingEffect::validate`] before they're sent.
+///
+/// # Example
+///
+/// ```rust
+/// use tapo::requests::{LightingEffect, LightingEffectType};
+///
+/// let effect = LightingEffect::new(
+///     "Warm Static",
+///     LightingEffectType::Static,
+///     true,
+///     true,
+///     80,
+///     vec![[30, 80, 100]],
+/// )
+/// .with_segments(vec![0])
+/// .with_sequence(vec![[30, 80, 100]]);
+///
+/// let json = serde_json::to_string_pretty(&effect)?;
+/// let loaded: LightingEffect = serde_json::from_str(&json)?;
+/// loaded.validate()?;
+/// assert_eq!(loaded, effect);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
 #[serde_as]
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[cfg_attr(feature = "python", pyo3::prelude::pyclass(from_py_object, get_all))]
 #[allow(missing_docs)]
 pub struct LightingEffect {
@@ -250,6 +279,93 @@ impl LightingEffect {
         self.transition_sequence = Some(transition_sequence);
         self
     }
+
+    /// Checks that the fields are within the ranges accepted by the device,
+    /// and that custom effects list their `segments` (and a `sequence` for sequence and static effects).
+    /// The ranges are checked before an effect is sent, this allows checking the whole effect earlier,
+    /// e.g. after loading it from a file.
+    pub fn validate(&self) -> Result<(), Error> {
+        self.validate_ranges()?;
+
+        if self.is_custom {
+            if self.segments.as_ref().is_none_or(Vec::is_empty) {
+                return Err(invalid("segments", "Required for custom lighting effects"));
+            }
+
+            let needs_sequence = matches!(
+                self.r#type,
+                LightingEffectType::Sequence | LightingEffectType::Static
+            );
+            if needs_sequence && self.sequence.as_ref().is_none_or(Vec::is_empty) {
+                return Err(invalid(
+                    "sequence",
+                    "Required for custom sequence and static lighting effects",
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks that the fields are within the ranges accepted by the device.
+    pub(crate) fn validate_ranges(&self) -> Result<(), Error> {
+        fn validate_range(field: &str, range: &[u16], max: u16) -> Result<(), Error> {
+            match range {
+                [start, end] if start <= end && *end <= max => Ok(()),
+                _ => Err(invalid(
+                    field,
+                    &format!("Must be a [min, max] range between 0 and {max}"),
+                )),
+            }
+        }
+
+        if self.brightness > 100 {
+            return Err(invalid("brightness", "Must be between 0 and 100"));
+        }
+
+        if let Some(brightness_range) = &self.brightness_range {
+            let range: Vec<u16> = brightness_range.iter().map(|&b| b.into()).collect();
+            validate_range("brightness_range", &range, 100)?;
+        }
+        if let Some(hue_range) = &self.hue_range {
+            validate_range("hue_range", hue_range, 360)?;
+        }
+        if let Some(saturation_range) = &self.saturation_range {
+            validate_range("saturation_range", &saturation_range.map(u16::from), 100)?;
+        }
+        if let Some(transition_range) = &self.transition_range {
+            validate_range("transition_range", transition_range, u16::MAX)?;
+        }
+
+        for (field, colors) in [
+            ("display_colors", Some(&self.display_colors)),
+            ("backgrounds", self.backgrounds.as_ref()),
+            ("init_states", self.init_states.as_ref()),
+            ("sequence", self.sequence.as_ref()),
+        ] {
+            let valid = colors
+                .into_iter()
+                .flatten()
+                .all(|[hue, saturation, brightness]| {
+                    *hue <= 360 && *saturation <= 100 && *brightness <= 100
+                });
+            if !valid {
+                return Err(invalid(
+                    field,
+                    "Colors must have a hue between 0 and 360, and a saturation and brightness between 0 and 100",
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn invalid(field: &str, message: &str) -> Error {
+    Error::Validation {
+        field: field.to_string(),
+        message: message.to_string(),
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -304,6 +420,37 @@ impl From<LightingEffectPreset> for LightingEffect {
 }
 
 impl LightingEffectPreset {
+    /// Every preset, in declaration order.
+    const ALL: [Self; 17] = [
+        Self::Aurora,
+        Self::BubblingCauldron,
+        Self::CandyCane,
+        Self::Christmas,
+        Self::Flicker,
+        Self::GrandmasChristmasLights,
+        Self::Hanukkah,
+        Self::HauntedMansion,
+        Self::Icicle,
+        Self::Lightning,
+        Self::Ocean,
+        Self::Rainbow,
+        Self::Raindrop,
+        Self::Spring,
+        Self::Sunrise,
+        Self::Sunset,
+        Self::Valentines,
+    ];
+
+    /// Returns every preset, in declaration order.
+    pub(crate) fn all() -> impl Iterator<Item = Self> {
+        Self::ALL.into_iter()
+    }
+
+    /// Returns the preset whose [`LightingEffect`] has the given identifier.
+    pub(crate) fn from_id(id: &str) -> Option<Self> {
+        Self::all().find(|preset| LightingEffect::from(preset.clone()).id == id)
+    }
+
     fn aurora(self) -> LightingEffect {
         LightingEffect::new(
             "Aurora",
@@ -779,3 +926,109 @@ impl LightingEffectPreset {
         .with_transition(2000)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn custom_effect() -> LightingEffect {
+        LightingEffect::new(
+            "Custom",
+            LightingEffectType::Random,
+            true,
+            true,
+            100,
+            vec![[200, 80, 100]],
+        )
+        .with_segments(vec![0])
+        .with_brightness_range([20, 100])
+        .with_hue_range([180, 220])
+        .with_saturation_range([60, 100])
+        .with_transition_range([500, 1500])
+    }
+
+    #[test]
+    fn presets_are_valid_and_found_by_id() {
+        assert_eq!(LightingEffectPreset::all().count(), 17);
+
+        for preset in LightingEffectPreset::all() {
+            let effect = LightingEffect::from(preset.clone());
+
+            assert!(effect.validate().is_ok(), "{preset:?}");
+            assert_eq!(LightingEffectPreset::from_id(&effect.id), Some(preset));
+        }
+
+        assert_eq!(LightingEffectPreset::from_id("custom-effect"), None);
+    }
+
+    #[test]
+    fn validation_checks_field_ranges() {
+        assert!(custom_effect().validate().is_ok());
+
+        let invalid_effects = [
+            ("brightness", custom_effect().with_brightness(101)),
+            (
+                "brightness_range",
+                custom_effect().with_brightness_range([90, 20]),
+            ),
+            (
+                "brightness_range",
+                custom_effect().with_brightness_range([0, 101]),
+            ),
+            ("hue_range", custom_effect().with_hue_range([0, 361])),
+            (
+                "saturation_range",
+                custom_effect().with_saturation_range([100, 0]),
+            ),
+            (
+                "transition_range",
+                custom_effect().with_transition_range([1500, 500]),
+            ),
+            (
+                "display_colors",
+                custom_effect().with_display_colors(vec![[361, 0, 0]]),
+            ),
+            (
+                "backgrounds",
+                custom_effect().with_backgrounds(vec![[0, 101, 0]]),
+            ),
+            ("segments", custom_effect().with_segments(vec![])),
+            (
+                "sequence",
+                custom_effect().with_type(LightingEffectType::Sequence),
+            ),
+        ];
+
+        for (expected_field, effect) in invalid_effects {
+            assert!(
+                matches!(effect.validate(), Err(Error::Validation { field, .. }) if field == expected_field),
+                "{expected_field}"
+            );
+        }
+
+        assert!(
+            custom_effect()
+                .with_segments(vec![])
+                .validate_ranges()
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn effects_round_trip_through_json_and_toml() {
+        for effect in [
+            custom_effect(),
+            LightingEffectPreset::CandyCane.into(),
+            LightingEffectPreset::Christmas.into(),
+        ] {
+            let json = serde_json::to_string(&effect).unwrap();
+            assert_eq!(
+                serde_json::from_str::<LightingEffect>(&json).unwrap(),
+                effect
+            );
+
+            let toml = toml::to_string(&effect).unwrap();
+            assert_eq!(toml::from_str::<LightingEffect>(&toml).unwrap(), effect);
+        }
+    }
+}
@@ -0,0 +1,213 @@
+use std::str::FromStr;
+
+use crate::error::Error;
+
+/// A color in the sRGB color space.
+///
+/// Can be parsed from the CSS `#rgb`, `#rrggbb` and `rgb(r, g, b)` notations.
+///
+/// # Example
+///
+/// ```rust
+/// use tapo::requests::RgbColor;
+///
+/// let color: RgbColor = "#ff8800".parse()?;
+/// assert_eq!(color, RgbColor::new(255, 136, 0));
+/// assert_eq!(color.to_hsv(), (32, 100, 100));
+/// # Ok::<(), tapo::Error>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RgbColor {
+    /// The red channel, between 0 and 255.
+    pub r: u8,
+    /// The green channel, between 0 and 255.
+    pub g: u8,
+    /// The blue channel, between 0 and 255.
+    pub b: u8,
+}
+
+impl RgbColor {
+    /// Creates a new [`RgbColor`].
+    pub fn new(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+
+    /// Converts a CIE 1931 `x`, `y` chromaticity to the most saturated [`RgbColor`] of that chromaticity.
+    ///
+    /// Chromaticities outside of the sRGB gamut are mapped to the nearest color the gamut can display.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - between 0 and 1
+    /// * `y` - between 0 and 1, excluding 0
+    pub fn from_xy(x: f64, y: f64) -> Result<Self, Error> {
+        if !(0.0..=1.0).contains(&x) || !(0.0..=1.0).contains(&y) || y == 0.0 || x + y > 1.0 {
+            return Err(Error::Validation {
+                field: "xy".to_string(),
+                message: "x and y must be between 0 and 1, with y above 0 and x + y at most 1"
+                    .to_string(),
+            });
+        }
+
+        // XYZ with a luminance of 1, then linear sRGB with the D65 white point.
+        let (x, y, z) = (x / y, 1.0, (1.0 - x - y) / y);
+        let linear = [
+            3.240_454_2 * x - 1.537_138_5 * y - 0.498_531_4 * z,
+            -0.969_266_0 * x + 1.876_010_8 * y + 0.041_556_0 * z,
+            0.055_643_4 * x - 0.204_025_9 * y + 1.057_225_2 * z,
+        ];
+
+        // Negative channels are outside of the gamut, clip them and scale the result to full intensity.
+        let linear = linear.map(|channel| channel.max(0.0));
+        let max = linear.iter().cloned().fold(0.0, f64::max);
+        let [r, g, b] = linear.map(|channel| {
+            let channel = if max > 0.0 { channel / max } else { 0.0 };
+            let encoded = if channel <= 0.003_130_8 {
+                12.92 * channel
+            } else {
+                1.055 * channel.powf(1.0 / 2.4) - 0.055
+            };
+            (encoded * 255.0).round().clamp(0.0, 255.0) as u8
+        });
+
+        Ok(Self { r, g, b })
+    }
+
+    /// Returns the `hue` (between 0 and 359), `saturation` (between 0 and 100)
+    /// and `value` (between 0 and 100) of the color.
+    pub fn to_hsv(&self) -> (u16, u8, u8) {
+        let [r, g, b] = [self.r, self.g, self.b].map(|channel| channel as f64 / 255.0);
+        let max = r.max(g).max(b);
+        let delta = max - r.min(g).min(b);
+
+        let hue = if delta == 0.0 {
+            0.0
+        } else if max == r {
+            60.0 * ((g - b) / delta).rem_euclid(6.0)
+        } else if max == g {
+            60.0 * ((b - r) / delta + 2.0)
+        } else {
+            60.0 * ((r - g) / delta + 4.0)
+        };
+        let saturation = if max == 0.0 { 0.0 } else { delta / max };
+
+        (
+            hue.round() as u16 % 360,
+            (saturation * 100.0).round() as u8,
+            (max * 100.0).round() as u8,
+        )
+    }
+}
+
+impl FromStr for RgbColor {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::Validation {
+            field: "color".to_string(),
+            message: format!(
+                "Invalid color `{value}`, expected `#rgb`, `#rrggbb` or `rgb(r, g, b)`"
+            ),
+        };
+        let trimmed = value.trim();
+
+        if let Some(hex) = trimmed.strip_prefix('#') {
+            if !hex.is_ascii() {
+                return Err(invalid());
+            }
+            let channel = |digits: &str| u8::from_str_radix(digits, 16).map_err(|_| invalid());
+
+            return match hex.len() {
+                3 => {
+                    let [r, g, b] = [0, 1, 2].map(|i| channel(&hex[i..=i]).map(|c| c * 17));
+                    Ok(Self::new(r?, g?, b?))
+                }
+                6 => Ok(Self::new(
+                    channel(&hex[0..2])?,
+                    channel(&hex[2..4])?,
+                    channel(&hex[4..6])?,
+                )),
+                _ => Err(invalid()),
+            };
+        }
+
+        let channels = trimmed
+            .strip_prefix("rgb(")
+            .and_then(|rest| rest.strip_suffix(')'))
+            .ok_or_else(invalid)?
+            .split(',')
+            .map(|channel| channel.trim().parse::<u8>().map_err(|_| invalid()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        match channels[..] {
+            [r, g, b] => Ok(Self::new(r, g, b)),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_css_notations() {
+        assert_eq!(
+            "#ff8800".parse::<RgbColor>().unwrap(),
+            RgbColor::new(255, 136, 0)
+        );
+        assert_eq!(
+            "#F80".parse::<RgbColor>().unwrap(),
+            RgbColor::new(255, 136, 0)
+        );
+        assert_eq!(
+            " rgb(255, 136,0) ".parse::<RgbColor>().unwrap(),
+            RgbColor::new(255, 136, 0)
+        );
+
+        for value in [
+            "ff8800",
+            "#ff880",
+            "#gg8800",
+            "#ff88é",
+            "rgb(256, 0, 0)",
+            "rgb(1, 2)",
+        ] {
+            assert!(
+                matches!(value.parse::<RgbColor>(), Err(Error::Validation { field, .. }) if field == "color"),
+                "{value}"
+            );
+        }
+    }
+
+    #[test]
+    fn converts_to_hsv() {
+        assert_eq!(RgbColor::new(255, 0, 0).to_hsv(), (0, 100, 100));
+        assert_eq!(RgbColor::new(0, 128, 0).to_hsv(), (120, 100, 50));
+        assert_eq!(RgbColor::new(0, 0, 255).to_hsv(), (240, 100, 100));
+        assert_eq!(RgbColor::new(255, 0, 128).to_hsv(), (330, 100, 100));
+        assert_eq!(RgbColor::new(255, 128, 128).to_hsv(), (0, 50, 100));
+        assert_eq!(RgbColor::new(128, 128, 128).to_hsv(), (0, 0, 50));
+        assert_eq!(RgbColor::new(0, 0, 0).to_hsv(), (0, 0, 0));
+    }
+
+    #[test]
+    fn converts_from_xy() {
+        assert_eq!(
+            RgbColor::from_xy(0.64, 0.33).unwrap(),
+            RgbColor::new(255, 0, 0)
+        );
+        assert_eq!(
+            RgbColor::from_xy(0.3127, 0.329).unwrap(),
+            RgbColor::new(255, 255, 255)
+        );
+
+        // Outside of the sRGB gamut, beyond its green primary.
+        let (hue, saturation, _) = RgbColor::from_xy(0.17, 0.7).unwrap().to_hsv();
+        assert!((110..=130).contains(&hue), "{hue}");
+        assert_eq!(saturation, 100);
+
+        assert!(RgbColor::from_xy(0.5, 0.0).is_err());
+        assert!(RgbColor::from_xy(0.7, 0.5).is_err());
+    }
+}
@@ -0,0 +1,149 @@
+use std::collections::HashSet;
+
+use crate::error::Error;
+use crate::requests::{LightingEffect, LightingEffectType};
+use crate::utils::{lerp, lerp_hue};
+
+/// Spreads a gradient evenly over `segment_count` segments, for [`crate::RgbicLightStripHandler::set_segments`].
+///
+/// The first stop is painted on the first segment and the last stop on the last segment.
+/// The segments in between are interpolated linearly, with the hue taking the shortest way around the color wheel.
+///
+/// # Arguments
+///
+/// * `stops` - the `[hue, saturation, brightness]` colors of the gradient, in order
+/// * `segment_count` - the number of segments, as returned by [`crate::RgbicLightStripHandler::get_segment_count`]
+///
+/// # Example
+///
+/// ```rust
+/// use tapo::requests::segment_gradient;
+///
+/// let segments = segment_gradient(&[[0, 100, 100], [120, 100, 50]], 3);
+/// assert_eq!(
+///     segments,
+///     [(0, [0, 100, 100]), (1, [60, 100, 75]), (2, [120, 100, 50])]
+/// );
+/// ```
+pub fn segment_gradient(stops: &[[u16; 3]], segment_count: u8) -> Vec<(u8, [u16; 3])> {
+    let (Some(first), Some(last_index)) = (stops.first(), stops.len().checked_sub(1)) else {
+        return Vec::new();
+    };
+
+    (0..segment_count)
+        .map(|segment| {
+            if segment_count == 1 || last_index == 0 {
+                return (segment, *first);
+            }
+
+            let position = f64::from(segment) / f64::from(segment_count - 1) * last_index as f64;
+            let index = (position.floor() as usize).min(last_index - 1);
+            let progress = position - index as f64;
+            let [from, to] = [stops[index], stops[index + 1]];
+
+            let color = [
+                lerp_hue(from[0] % 360, to[0] % 360, progress),
+                lerp(from[1].into(), to[1].into(), progress) as u16,
+                lerp(from[2].into(), to[2].into(), progress) as u16,
+            ];
+            (segment, color)
+        })
+        .collect()
+}
+
+/// Builds the static [`LightingEffect`] that paints each segment with its color.
+pub(crate) fn segments_lighting_effect(
+    segments: &[(u8, [u16; 3])],
+) -> Result<LightingEffect, Error> {
+    if segments.is_empty() {
+        return Err(Error::Validation {
+            field: "segments".to_string(),
+            message: "Requires at least one segment".to_string(),
+        });
+    }
+
+    let mut indexes = HashSet::new();
+    for (index, [hue, saturation, brightness]) in segments {
+        if !indexes.insert(index) {
+            return Err(Error::Validation {
+                field: "segments".to_string(),
+                message: format!("Segment {index} is set more than once"),
+            });
+        }
+
+        if *hue > 360 || *saturation > 100 || *brightness > 100 {
+            return Err(Error::Validation {
+                field: "segments".to_string(),
+                message: format!(
+                    "The color of segment {index} must have a hue between 0 and 360, and a saturation and brightness between 0 and 100"
+                ),
+            });
+        }
+    }
+
+    let colors: Vec<[u16; 3]> = segments.iter().map(|(_, color)| *color).collect();
+    let mut display_colors = colors.clone();
+    display_colors.dedup();
+
+    Ok(LightingEffect::new(
+        "Segments",
+        LightingEffectType::Static,
+        true,
+        true,
+        100,
+        display_colors,
+    )
+    .with_expansion_strategy(1)
+    .with_segments(segments.iter().map(|(index, _)| *index).collect())
+    .with_sequence(colors))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gradient_interpolates_between_stops() {
+        assert_eq!(
+            segment_gradient(&[[350, 100, 100], [10, 50, 100], [10, 50, 0]], 5),
+            [
+                (0, [350, 100, 100]),
+                (1, [0, 75, 100]),
+                (2, [10, 50, 100]),
+                (3, [10, 50, 50]),
+                (4, [10, 50, 0]),
+            ]
+        );
+        assert_eq!(
+            segment_gradient(&[[200, 100, 100]], 2),
+            [(0, [200, 100, 100]), (1, [200, 100, 100])]
+        );
+        assert_eq!(
+            segment_gradient(&[[0, 100, 100], [120, 100, 100]], 1),
+            [(0, [0, 100, 100])]
+        );
+        assert!(segment_gradient(&[], 10).is_empty());
+    }
+
+    #[test]
+    fn segments_validation() {
+        let effect = segments_lighting_effect(&[(0, [0, 100, 100]), (5, [240, 100, 50])]).unwrap();
+        assert_eq!(effect.segments, Some(vec![0, 5]));
+        assert_eq!(effect.sequence, Some(vec![[0, 100, 100], [240, 100, 50]]));
+
+        for segments in [
+            vec![],
+            vec![(1, [0, 100, 100]), (1, [120, 100, 100])],
+            vec![(1, [361, 100, 100])],
+            vec![(1, [0, 101, 100])],
+        ] {
+            assert!(
+                matches!(
+                    segments_lighting_effect(&segments),
+                    Err(Error::Validation { field, .. }) if field == "segments"
+                ),
+                "{segments:?}"
+            );
+        }
+    }
+}
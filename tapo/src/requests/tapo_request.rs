@@ -3,10 +3,15 @@ use std::time::{SystemTime, UNIX_EPOCH};
 use serde::Serialize;
 
 use super::{
-    AddTimerParams, ControlChildParams, DeviceRebootParams, GetChildDeviceListParams,
-    GetEnergyDataParams, GetPowerDataParams, GetTriggerLogsParams, HandshakeParams, LightingEffect,
-    LoginDeviceParams, MultipleRequestParams, PlayAlarmParams, RemoveTimersParams,
-    SecurePassthroughParams, SegmentEffect, SmartCamDoParams, SmartCamGetParams,
+    AddTimerParams, ChildDeviceListParams, ControlChildParams, DeviceRebootParams,
+    GetChildDeviceListParams, GetEnergyDataParams, GetPowerDataParams, GetRulesParams,
+    GetTriggerLogsParams, HandshakeParams, LightingEffect, LoginDeviceParams,
+    MultipleRequestParams, PlayAlarmParams, RemoveRulesParams, RemoveTimersParams,
+    SecurePassthroughParams, SegmentEffect, SetProtectionPowerParams, SmartCamDoParams,
+    SmartCamGetParams,
+};
+use crate::responses::{
+    AntitheftRuleRaw, LedInfoResult, OnOffGraduallyInfoResult, ScheduleRuleRaw,
 };
 
 #[derive(Debug, Serialize)]
@@ -45,6 +50,12 @@ pub(crate) enum TapoRequest {
     #[cfg(feature = "debug")]
     #[serde(rename = "get_support_alarm_type_list")]
     GetSupportedAlarmTypeList(TapoParams<EmptyParams>),
+    // Hub child pairing requests
+    BeginScanningChildDevice(TapoParams<EmptyParams>),
+    EndScanningChildDevice(TapoParams<EmptyParams>),
+    GetScanChildDeviceList(TapoParams<EmptyParams>),
+    AddChildDeviceList(Box<TapoParams<ChildDeviceListParams>>),
+    RemoveChildDeviceList(Box<TapoParams<ChildDeviceListParams>>),
     // Smart Camera requests
     #[serde(rename = "get")]
     SmartCamGet(SmartCamGetParams),
@@ -54,6 +65,30 @@ pub(crate) enum TapoRequest {
     AddCountdownRule(TapoParams<AddTimerParams>),
     GetCountdownRules(TapoParams<EmptyObjectParams>),
     RemoveCountdownRules(TapoParams<RemoveTimersParams>),
+    // Schedule requests
+    GetScheduleRules(TapoParams<GetRulesParams>),
+    AddScheduleRule(Box<TapoParams<ScheduleRuleRaw>>),
+    EditScheduleRule(Box<TapoParams<serde_json::Value>>),
+    RemoveScheduleRules(TapoParams<RemoveRulesParams>),
+    // Firmware requests
+    GetLatestFw(TapoParams<EmptyParams>),
+    GetFwDownloadState(TapoParams<EmptyParams>),
+    FwDownload(TapoParams<EmptyParams>),
+    // LED requests
+    GetLedInfo(TapoParams<EmptyParams>),
+    SetLedInfo(Box<TapoParams<LedInfoResult>>),
+    // Gradual on/off requests
+    GetOnOffGraduallyInfo(TapoParams<EmptyParams>),
+    SetOnOffGraduallyInfo(Box<TapoParams<OnOffGraduallyInfoResult>>),
+    // Power protection requests
+    GetProtectionPower(TapoParams<EmptyParams>),
+    SetProtectionPower(TapoParams<SetProtectionPowerParams>),
+    GetMaxPower(TapoParams<EmptyParams>),
+    // Away mode requests
+    GetAntitheftRules(TapoParams<GetRulesParams>),
+    AddAntitheftRule(Box<TapoParams<AntitheftRuleRaw>>),
+    EditAntitheftRule(Box<TapoParams<AntitheftRuleRaw>>),
+    RemoveAntitheftRules(TapoParams<RemoveRulesParams>),
 }
 
 #[derive(Debug, Serialize)]
@@ -1,5 +1,6 @@
 //! Tapo response objects.
 
+mod antitheft_rule_result;
 #[cfg(feature = "debug")]
 mod child_device_component_list_result;
 mod child_device_list_hub_result;
@@ -14,10 +15,16 @@ mod device_usage_energy_monitoring_result;
 mod device_usage_result;
 mod energy_data_result;
 mod energy_usage_result;
+mod firmware_result;
+mod led_info_result;
+mod on_off_gradually_info_result;
 mod power_data_result;
+mod power_protection_result;
 mod power_state;
 mod preset;
 mod rtsp_stream_url;
+mod scan_child_device_list_result;
+mod schedule_rule_result;
 mod snapshot_result;
 mod supported_alarm_type_list_result;
 mod tapo_response;
@@ -28,6 +35,7 @@ mod trigger_logs_result;
 
 pub use crate::requests::{LightingEffect, LightingEffectType};
 
+pub use antitheft_rule_result::*;
 #[cfg(feature = "debug")]
 pub use child_device_component_list_result::*;
 pub use child_device_list_hub_result::*;
@@ -40,10 +48,16 @@ pub use device_usage_energy_monitoring_result::*;
 pub use device_usage_result::*;
 pub use energy_data_result::*;
 pub use energy_usage_result::*;
+pub use firmware_result::*;
+pub use led_info_result::*;
+pub use on_off_gradually_info_result::*;
 pub use power_data_result::*;
+pub use power_protection_result::*;
 pub use power_state::*;
 pub use preset::*;
 pub use rtsp_stream_url::*;
+pub use scan_child_device_list_result::*;
+pub use schedule_rule_result::*;
 pub use snapshot_result::*;
 pub use timer_result::*;
 pub use trigger_logs_result::*;
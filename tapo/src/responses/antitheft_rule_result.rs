@@ -0,0 +1,180 @@
+use chrono::Datelike as _;
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+use crate::responses::{ScheduleRecurrence, ScheduleTime, TapoResponseExt};
+
+/// The away mode rules of a device (the "Away Mode" feature in the Tapo app).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AntitheftRules {
+    /// Whether away mode is enabled on the device.
+    pub enabled: bool,
+    /// The maximum number of rules the device can store.
+    pub max_count: u32,
+    /// The away mode rules.
+    pub rules: Vec<AntitheftRule>,
+}
+
+/// An away mode rule. While it's active, the device is randomly turned *on* and *off*
+/// between `start` and `end`, to make the home look occupied.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AntitheftRule {
+    /// Device-assigned id, e.g. `A1`.
+    pub id: String,
+    /// Whether the rule is enabled.
+    pub enabled: bool,
+    /// When the time window starts.
+    pub start: ScheduleTime,
+    /// When the time window ends.
+    pub end: ScheduleTime,
+    /// On which days the rule is active.
+    pub recurrence: ScheduleRecurrence,
+}
+
+/// The away mode rule payload exchanged with the device.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct AntitheftRuleRaw {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub enable: bool,
+    pub mode: String,
+    #[serde(default)]
+    pub week_day: u8,
+    pub s_type: String,
+    #[serde(default)]
+    pub s_min: u16,
+    #[serde(default)]
+    pub time_offset: i16,
+    pub e_type: String,
+    #[serde(default)]
+    pub e_min: u16,
+    #[serde(default)]
+    pub e_time_offset: i16,
+    #[serde(default)]
+    pub year: i32,
+    #[serde(default)]
+    pub month: u32,
+    #[serde(default)]
+    pub day: u32,
+}
+
+impl AntitheftRuleRaw {
+    pub(crate) fn new(
+        id: Option<String>,
+        enabled: bool,
+        start: ScheduleTime,
+        end: ScheduleTime,
+        recurrence: &ScheduleRecurrence,
+    ) -> Result<Self, Error> {
+        let (s_type, s_min, time_offset) = start.to_raw("start")?;
+        let (e_type, e_min, e_time_offset) = end.to_raw("end")?;
+        let (mode, week_day, date) = recurrence.to_raw()?;
+
+        if start == end {
+            return Err(Error::Validation {
+                field: "end".to_string(),
+                message: "Must be different from start".to_string(),
+            });
+        }
+
+        Ok(Self {
+            id,
+            enable: enabled,
+            mode: mode.to_string(),
+            week_day,
+            s_type: s_type.to_string(),
+            s_min,
+            time_offset,
+            e_type: e_type.to_string(),
+            e_min,
+            e_time_offset,
+            year: date.year(),
+            month: date.month(),
+            day: date.day(),
+        })
+    }
+
+    pub(crate) fn into_rule(self) -> Option<AntitheftRule> {
+        Some(AntitheftRule {
+            start: ScheduleTime::from_raw(&self.s_type, self.s_min, self.time_offset)?,
+            end: ScheduleTime::from_raw(&self.e_type, self.e_min, self.e_time_offset)?,
+            recurrence: ScheduleRecurrence::from_raw(
+                &self.mode,
+                self.week_day,
+                self.year,
+                self.month,
+                self.day,
+            )?,
+            id: self.id?,
+            enabled: self.enable,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct AntitheftRuleListResultRaw {
+    #[serde(default)]
+    pub enable: bool,
+    #[serde(default)]
+    pub antitheft_rule_max_count: u32,
+    #[serde(default)]
+    pub sum: u64,
+    #[serde(default)]
+    pub rule_list: Vec<AntitheftRuleRaw>,
+}
+
+impl TapoResponseExt for AntitheftRuleListResultRaw {}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct AddAntitheftRuleResult {
+    pub id: String,
+}
+
+impl TapoResponseExt for AddAntitheftRuleResult {}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Weekday;
+
+    use super::*;
+
+    #[test]
+    fn rules_round_trip_through_the_device_payload() {
+        let rule = AntitheftRule {
+            id: "A1".to_string(),
+            enabled: true,
+            start: ScheduleTime::Sunset { offset_minutes: 0 },
+            end: ScheduleTime::at(23, 0),
+            recurrence: ScheduleRecurrence::Weekly(vec![Weekday::Fri, Weekday::Sat]),
+        };
+
+        let raw = AntitheftRuleRaw::new(
+            Some(rule.id.clone()),
+            rule.enabled,
+            rule.start,
+            rule.end,
+            &rule.recurrence,
+        )
+        .unwrap();
+
+        assert_eq!(raw.s_type, "sunset");
+        assert_eq!(raw.e_type, "normal");
+        assert_eq!(raw.e_min, 23 * 60);
+        assert_eq!(raw.week_day, 0b110_0000);
+        assert_eq!(raw.into_rule(), Some(rule));
+    }
+
+    #[test]
+    fn empty_windows_are_rejected() {
+        assert!(
+            AntitheftRuleRaw::new(
+                None,
+                true,
+                ScheduleTime::at(20, 0),
+                ScheduleTime::at(20, 0),
+                &ScheduleRecurrence::daily(),
+            )
+            .is_err()
+        );
+    }
+}
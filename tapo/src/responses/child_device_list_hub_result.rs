@@ -5,12 +5,14 @@ mod s210_result;
 mod t100_result;
 mod t110_result;
 mod t300_result;
+mod t31x_history_result;
 mod t31x_result;
 
 pub use ke100_result::*;
 pub use other_result::*;
 pub use s200_result::*;
 pub use s210_result::*;
+pub use t31x_history_result::*;
 pub use t31x_result::*;
 pub use t100_result::*;
 pub use t110_result::*;
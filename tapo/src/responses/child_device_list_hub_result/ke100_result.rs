@@ -16,6 +16,15 @@ pub enum TemperatureUnitKE100 {
     Celsius,
 }
 
+impl TemperatureUnitKE100 {
+    /// Converts a temperature from this unit to `to`.
+    pub(crate) fn convert(&self, value: f32, to: &TemperatureUnitKE100) -> f32 {
+        match (self, to) {
+            (TemperatureUnitKE100::Celsius, TemperatureUnitKE100::Celsius) => value,
+        }
+    }
+}
+
 /// Device info of Tapo KE100 thermostatic radiator valve (TRV).
 ///
 /// Specific properties: `temperature_unit`, `current_temperature`, `target_temperature`,
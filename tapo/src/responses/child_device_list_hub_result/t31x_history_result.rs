@@ -0,0 +1,371 @@
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+
+use chrono::{DateTime, Datelike as _, Days, SecondsFormat, Timelike as _, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::responses::{TemperatureHumidityRecord, TemperatureHumidityRecords, TemperatureUnit};
+
+/// The period that [`TemperatureHumidityStats`] are computed over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TemperatureHumidityPeriod {
+    /// An hour, starting on the hour.
+    Hour,
+    /// A day, starting at midnight.
+    Day,
+    /// A week, starting on Monday at midnight.
+    ///
+    /// The records only cover the last 24 hours, so they span at most two partial weeks.
+    Week,
+}
+
+impl TemperatureHumidityPeriod {
+    /// Returns the start of the period that contains `datetime`.
+    fn start(&self, datetime: DateTime<Utc>) -> DateTime<Utc> {
+        let hour = datetime
+            .with_minute(0)
+            .and_then(|d| d.with_second(0))
+            .and_then(|d| d.with_nanosecond(0))
+            .unwrap_or(datetime);
+
+        match self {
+            TemperatureHumidityPeriod::Hour => hour,
+            TemperatureHumidityPeriod::Day => hour.with_hour(0).unwrap_or(hour),
+            TemperatureHumidityPeriod::Week => {
+                let day = hour.with_hour(0).unwrap_or(hour);
+                day - Days::new(day.weekday().num_days_from_monday() as u64)
+            }
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            TemperatureHumidityPeriod::Hour => "hour",
+            TemperatureHumidityPeriod::Day => "day",
+            TemperatureHumidityPeriod::Week => "week",
+        }
+    }
+}
+
+/// Minimum, maximum and average temperature and humidity over a [`TemperatureHumidityPeriod`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TemperatureHumidityStats {
+    /// The start of the period, in the same time reference as [`TemperatureHumidityRecord::datetime`].
+    pub start: DateTime<Utc>,
+    /// Minimum temperature.
+    pub temperature_min: f32,
+    /// Maximum temperature.
+    pub temperature_max: f32,
+    /// Average temperature.
+    pub temperature_avg: f32,
+    /// Minimum humidity in percent.
+    pub humidity_min: u8,
+    /// Maximum humidity in percent.
+    pub humidity_max: u8,
+    /// Average humidity in percent.
+    pub humidity_avg: f32,
+}
+
+impl TemperatureHumidityStats {
+    fn convert(&self, from: &TemperatureUnit, to: &TemperatureUnit) -> Self {
+        Self {
+            temperature_min: from.convert(self.temperature_min, to),
+            temperature_max: from.convert(self.temperature_max, to),
+            temperature_avg: from.convert(self.temperature_avg, to),
+            ..self.clone()
+        }
+    }
+}
+
+/// Temperature and humidity statistics computed from
+/// [`TemperatureHumidityRecords`] by [`TemperatureHumidityRecords::aggregate`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemperatureHumidityHistory {
+    /// The datetime in UTC of when the underlying records were generated.
+    pub datetime: DateTime<Utc>,
+    /// The period each entry of `stats` covers.
+    pub period: TemperatureHumidityPeriod,
+    /// The statistics of each period, oldest first. Periods without any data are omitted.
+    pub stats: Vec<TemperatureHumidityStats>,
+    /// The unit of the temperatures.
+    pub temperature_unit: TemperatureUnit,
+}
+
+impl TemperatureHumidityHistory {
+    /// Returns a copy of the history with the temperatures converted to `unit`.
+    ///
+    /// # Arguments
+    ///
+    /// * `unit` - the temperature unit to convert to
+    pub fn to_unit(&self, unit: TemperatureUnit) -> Self {
+        Self {
+            datetime: self.datetime,
+            period: self.period,
+            stats: self
+                .stats
+                .iter()
+                .map(|stats| stats.convert(&self.temperature_unit, &unit))
+                .collect(),
+            temperature_unit: unit,
+        }
+    }
+
+    /// Returns the history as CSV, with a header row and one row per period.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(
+            "start,period,temperature_min,temperature_max,temperature_avg,humidity_min,humidity_max,humidity_avg,temperature_unit\n",
+        );
+
+        for stats in &self.stats {
+            // safe: writing to a String never fails.
+            writeln!(
+                csv,
+                "{},{},{},{},{},{},{},{},{}",
+                stats.start.to_rfc3339_opts(SecondsFormat::Secs, true),
+                self.period.as_str(),
+                stats.temperature_min,
+                stats.temperature_max,
+                stats.temperature_avg,
+                stats.humidity_min,
+                stats.humidity_max,
+                stats.humidity_avg,
+                self.temperature_unit.as_str(),
+            )
+            .unwrap();
+        }
+
+        csv
+    }
+}
+
+impl TemperatureHumidityRecords {
+    /// Computes the minimum, maximum and average temperature and humidity
+    /// of the records in each `period`.
+    ///
+    /// Only the records returned by `get_temperature_humidity_records` are aggregated,
+    /// i.e. the last 24 hours. The device's longer-range history isn't supported.
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - the period to group the records by
+    pub fn aggregate(&self, period: TemperatureHumidityPeriod) -> TemperatureHumidityHistory {
+        let mut groups = BTreeMap::<DateTime<Utc>, Vec<&TemperatureHumidityRecord>>::new();
+        for record in &self.records {
+            groups
+                .entry(period.start(record.datetime))
+                .or_default()
+                .push(record);
+        }
+
+        let stats = groups
+            .into_iter()
+            .map(|(start, records)| {
+                let count = records.len() as f64;
+
+                TemperatureHumidityStats {
+                    start,
+                    temperature_min: records
+                        .iter()
+                        .map(|r| r.temperature)
+                        .fold(f32::INFINITY, f32::min),
+                    temperature_max: records
+                        .iter()
+                        .map(|r| r.temperature)
+                        .fold(f32::NEG_INFINITY, f32::max),
+                    temperature_avg: (records.iter().map(|r| r.temperature as f64).sum::<f64>()
+                        / count) as f32,
+                    humidity_min: records.iter().map(|r| r.humidity).min().unwrap_or_default(),
+                    humidity_max: records.iter().map(|r| r.humidity).max().unwrap_or_default(),
+                    humidity_avg: (records.iter().map(|r| r.humidity as f64).sum::<f64>() / count)
+                        as f32,
+                }
+            })
+            .collect();
+
+        TemperatureHumidityHistory {
+            datetime: self.datetime,
+            period,
+            stats,
+            temperature_unit: self.temperature_unit.clone(),
+        }
+    }
+
+    /// Returns a copy of the records with the temperatures converted to `unit`.
+    ///
+    /// # Arguments
+    ///
+    /// * `unit` - the temperature unit to convert to
+    pub fn to_unit(&self, unit: TemperatureUnit) -> Self {
+        let from = &self.temperature_unit;
+
+        Self {
+            datetime: self.datetime,
+            records: self
+                .records
+                .iter()
+                .map(|record| TemperatureHumidityRecord {
+                    temperature: from.convert(record.temperature, &unit),
+                    temperature_exception: from
+                        .convert_difference(record.temperature_exception, &unit),
+                    ..record.clone()
+                })
+                .collect(),
+            temperature_unit: unit,
+        }
+    }
+
+    /// Returns the records as CSV, with a header row and one row per record.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(
+            "datetime,temperature,temperature_exception,humidity,humidity_exception,temperature_unit\n",
+        );
+
+        for record in &self.records {
+            // safe: writing to a String never fails.
+            writeln!(
+                csv,
+                "{},{},{},{},{},{}",
+                record.datetime.to_rfc3339_opts(SecondsFormat::Secs, true),
+                record.temperature,
+                record.temperature_exception,
+                record.humidity,
+                record.humidity_exception,
+                self.temperature_unit.as_str(),
+            )
+            .unwrap();
+        }
+
+        csv
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDateTime;
+
+    use super::*;
+
+    fn datetime(value: &str) -> DateTime<Utc> {
+        NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S")
+            .unwrap()
+            .and_utc()
+    }
+
+    fn record(value: &str, temperature: f32, humidity: u8) -> TemperatureHumidityRecord {
+        TemperatureHumidityRecord {
+            datetime: datetime(value),
+            humidity_exception: 0,
+            humidity,
+            temperature_exception: 0.0,
+            temperature,
+        }
+    }
+
+    fn records() -> TemperatureHumidityRecords {
+        TemperatureHumidityRecords {
+            datetime: datetime("2026-03-10 01:52:24"),
+            records: vec![
+                record("2026-03-08 23:30:00", 19.0, 50),
+                record("2026-03-08 23:45:00", 20.0, 52),
+                record("2026-03-09 00:00:00", 21.0, 54),
+                record("2026-03-09 00:15:00", 22.0, 56),
+                record("2026-03-10 01:45:00", 18.0, 40),
+            ],
+            temperature_unit: TemperatureUnit::Celsius,
+        }
+    }
+
+    #[test]
+    fn test_aggregate_per_hour_day_and_week() {
+        let records = records();
+
+        let hourly = records.aggregate(TemperatureHumidityPeriod::Hour);
+        assert_eq!(hourly.stats.len(), 3);
+        assert_eq!(
+            hourly.stats[0],
+            TemperatureHumidityStats {
+                start: datetime("2026-03-08 23:00:00"),
+                temperature_min: 19.0,
+                temperature_max: 20.0,
+                temperature_avg: 19.5,
+                humidity_min: 50,
+                humidity_max: 52,
+                humidity_avg: 51.0,
+            }
+        );
+
+        let daily = records.aggregate(TemperatureHumidityPeriod::Day);
+        let starts = daily.stats.iter().map(|s| s.start).collect::<Vec<_>>();
+        assert_eq!(
+            starts,
+            vec![
+                datetime("2026-03-08 00:00:00"),
+                datetime("2026-03-09 00:00:00"),
+                datetime("2026-03-10 00:00:00"),
+            ]
+        );
+
+        // 2026-03-08 is a Sunday, so it belongs to the previous week.
+        let weekly = records.aggregate(TemperatureHumidityPeriod::Week);
+        assert_eq!(weekly.stats.len(), 2);
+        assert_eq!(weekly.stats[0].start, datetime("2026-03-02 00:00:00"));
+        assert_eq!(
+            weekly.stats[1],
+            TemperatureHumidityStats {
+                start: datetime("2026-03-09 00:00:00"),
+                temperature_min: 18.0,
+                temperature_max: 22.0,
+                temperature_avg: 20.333334,
+                humidity_min: 40,
+                humidity_max: 56,
+                humidity_avg: 50.0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_temperature_unit_conversions() {
+        let mut records = records();
+        records.records[0].temperature_exception = -2.5;
+
+        let fahrenheit = records.to_unit(TemperatureUnit::Fahrenheit);
+        assert_eq!(fahrenheit.temperature_unit, TemperatureUnit::Fahrenheit);
+        assert_eq!(fahrenheit.records[0].temperature, 66.2);
+        assert_eq!(fahrenheit.records[0].temperature_exception, -4.5);
+        assert_eq!(fahrenheit.records[0].humidity, 50);
+
+        let history = records.aggregate(TemperatureHumidityPeriod::Day);
+        let converted = history.to_unit(TemperatureUnit::Fahrenheit);
+        assert_eq!(converted.stats[0].temperature_max, 68.0);
+        assert_eq!(
+            converted.stats[0].humidity_avg,
+            history.stats[0].humidity_avg
+        );
+
+        let back = converted.to_unit(TemperatureUnit::Celsius);
+        assert!((back.stats[0].temperature_avg - 19.5).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_csv_export() {
+        let records = records();
+
+        let csv = records.to_csv();
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next(),
+            Some(
+                "datetime,temperature,temperature_exception,humidity,humidity_exception,temperature_unit"
+            )
+        );
+        assert_eq!(lines.next(), Some("2026-03-08T23:30:00Z,19,0,50,0,celsius"));
+        assert_eq!(lines.count(), 4);
+
+        let csv = records.aggregate(TemperatureHumidityPeriod::Day).to_csv();
+        assert_eq!(
+            csv.lines().nth(1),
+            Some("2026-03-08T00:00:00Z,day,19,20,19.5,50,52,51,celsius")
+        );
+    }
+}
@@ -18,6 +18,39 @@ pub enum TemperatureUnit {
     Fahrenheit,
 }
 
+impl TemperatureUnit {
+    /// Converts a temperature from this unit to the `to` unit.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - the temperature in this unit
+    /// * `to` - the unit to convert to
+    pub fn convert(&self, value: f32, to: &TemperatureUnit) -> f32 {
+        match (self, to) {
+            (TemperatureUnit::Celsius, TemperatureUnit::Fahrenheit) => value * 9.0 / 5.0 + 32.0,
+            (TemperatureUnit::Fahrenheit, TemperatureUnit::Celsius) => (value - 32.0) * 5.0 / 9.0,
+            _ => value,
+        }
+    }
+
+    /// Converts a temperature *difference*, e.g. a comfort zone exception,
+    /// which only scales between the units.
+    pub(crate) fn convert_difference(&self, value: f32, to: &TemperatureUnit) -> f32 {
+        match (self, to) {
+            (TemperatureUnit::Celsius, TemperatureUnit::Fahrenheit) => value * 9.0 / 5.0,
+            (TemperatureUnit::Fahrenheit, TemperatureUnit::Celsius) => value * 5.0 / 9.0,
+            _ => value,
+        }
+    }
+
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            TemperatureUnit::Celsius => "celsius",
+            TemperatureUnit::Fahrenheit => "fahrenheit",
+        }
+    }
+}
+
 /// Device info of Tapo T310 and T315 temperature and humidity sensors.
 ///
 /// Specific properties: `current_temperature`, `temperature_unit`,
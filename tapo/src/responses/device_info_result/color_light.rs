@@ -36,6 +36,9 @@ pub struct DeviceInfoColorLightResult {
     // Unique to this device
     //
     pub brightness: u8,
+    /// The lowest and highest color temperature supported by the device, in Kelvin.
+    /// Not reported by older firmware.
+    pub color_temp_range: Option<[u16; 2]>,
     pub color_temp: u16,
     /// The default state of a device to be used when internet connectivity is lost after a power cut.
     pub default_states: DefaultColorLightState,
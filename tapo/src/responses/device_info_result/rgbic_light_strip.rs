@@ -1,7 +1,8 @@
 use serde::{Deserialize, Serialize};
+use serde_with::{BoolFromInt, serde_as};
 
 use crate::error::Error;
-use crate::requests::LightingEffect;
+use crate::requests::{LightingEffect, LightingEffectPreset};
 use crate::responses::{DecodableResultExt, DefaultStateType, TapoResponseExt, decode_value};
 
 /// Device info of Tapo L920 and L930.
@@ -43,9 +44,13 @@ pub struct DeviceInfoRgbicLightStripResult {
     pub default_states: DefaultRgbicLightStripState,
     pub device_on: bool,
     pub hue: Option<u16>,
+    /// The *lighting effect* last set on the device. Not reported by older firmware.
+    pub lighting_effect: Option<LightingEffectInfo>,
     pub nickname: String,
     pub overheated: bool,
     pub saturation: Option<u16>,
+    /// The number of segments, the individually colored zones of the strip. Not reported by older firmware.
+    pub segment_count: Option<u8>,
 }
 
 #[cfg(feature = "python")]
@@ -88,3 +93,42 @@ pub struct RgbicLightStripState {
 
 #[cfg(feature = "python")]
 crate::impl_to_dict!(RgbicLightStripState);
+
+/// The *lighting effect* of an RGB IC Light Strip, as reported by its device info.
+///
+/// The device only reports the identity of the effect, not its full definition.
+/// For the built-in effects, [`LightingEffectInfo::preset`] returns the matching [`LightingEffectPreset`].
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "python", pyo3::prelude::pyclass(from_py_object, get_all))]
+pub struct LightingEffectInfo {
+    /// The brightness of the effect, between 0 and 100.
+    #[serde(default)]
+    pub brightness: u8,
+    /// Whether the effect was created by the user instead of being built-in.
+    #[serde_as(as = "BoolFromInt")]
+    #[serde(rename = "custom")]
+    pub is_custom: bool,
+    /// The colors displayed in the Tapo app.
+    #[serde(default)]
+    pub display_colors: Vec<[u16; 3]>,
+    /// Whether the effect is currently running.
+    #[serde_as(as = "BoolFromInt")]
+    #[serde(rename = "enable")]
+    pub enabled: bool,
+    /// The effect identifier.
+    pub id: String,
+    /// The effect name shown in the Tapo app.
+    pub name: String,
+}
+
+#[cfg(feature = "python")]
+crate::impl_to_dict!(LightingEffectInfo);
+
+#[cfg_attr(feature = "python", pyo3::pymethods)]
+impl LightingEffectInfo {
+    /// Returns the [`LightingEffectPreset`] with the same identifier, if this is a built-in effect.
+    pub fn preset(&self) -> Option<LightingEffectPreset> {
+        LightingEffectPreset::from_id(&self.id)
+    }
+}
@@ -0,0 +1,85 @@
+use serde::{Deserialize, Serialize};
+
+use crate::responses::TapoResponseExt;
+
+/// The latest firmware available for a device.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LatestFirmwareResult {
+    /// Whether the latest firmware is newer than the installed one.
+    pub need_to_upgrade: bool,
+    /// The version of the latest firmware, e.g. `1.2.0 Build 240101 Rel.120000`.
+    /// Empty if the installed firmware is the latest.
+    #[serde(default)]
+    pub fw_ver: String,
+    /// The size of the latest firmware in bytes.
+    #[serde(default)]
+    pub fw_size: u64,
+    /// The release date of the latest firmware.
+    #[serde(default)]
+    pub release_date: String,
+    /// The release notes of the latest firmware.
+    #[serde(default)]
+    pub release_note: String,
+}
+impl TapoResponseExt for LatestFirmwareResult {}
+
+/// The progress of a firmware update started by `fw_download`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FirmwareDownloadStateResult {
+    /// The stage of the firmware update.
+    pub status: FirmwareDownloadStatus,
+    /// The download progress, between 0 and 100.
+    #[serde(default)]
+    pub download_progress: u8,
+    /// The estimated reboot time in seconds, once the firmware is installed.
+    #[serde(default)]
+    pub reboot_time: u16,
+    /// The estimated installation time in seconds.
+    #[serde(default)]
+    pub upgrade_time: u16,
+    /// Whether the device installs firmware updates automatically.
+    #[serde(default)]
+    pub auto_upgrade: bool,
+}
+impl TapoResponseExt for FirmwareDownloadStateResult {}
+
+/// The stage of a firmware update, as reported by [`FirmwareDownloadStateResult`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "i32", into = "i32")]
+pub enum FirmwareDownloadStatus {
+    /// No firmware update is in progress.
+    Idle,
+    /// The firmware is being downloaded.
+    Downloading,
+    /// The firmware is being installed. The device reboots afterwards.
+    Installing,
+    /// The firmware update failed with the given error code.
+    Failed(i32),
+    /// A status code that isn't known to this library.
+    Other(i32),
+}
+
+impl From<i32> for FirmwareDownloadStatus {
+    fn from(status: i32) -> Self {
+        match status {
+            0 => Self::Idle,
+            2 => Self::Downloading,
+            3 => Self::Installing,
+            status if status < 0 => Self::Failed(status),
+            status => Self::Other(status),
+        }
+    }
+}
+
+impl From<FirmwareDownloadStatus> for i32 {
+    fn from(status: FirmwareDownloadStatus) -> Self {
+        match status {
+            FirmwareDownloadStatus::Idle => 0,
+            FirmwareDownloadStatus::Downloading => 2,
+            FirmwareDownloadStatus::Installing => 3,
+            FirmwareDownloadStatus::Failed(status) | FirmwareDownloadStatus::Other(status) => {
+                status
+            }
+        }
+    }
+}
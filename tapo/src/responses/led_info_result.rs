@@ -0,0 +1,89 @@
+use serde::{Deserialize, Serialize};
+
+use crate::responses::TapoResponseExt;
+
+/// The status LED settings of a device.
+///
+/// To change them, modify the result of `get_led_info` and pass it to `set_led_info`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LedInfoResult {
+    /// When the LED is lit.
+    pub led_rule: LedRule,
+    /// Whether the LED is currently lit. Ignored by `set_led_info`.
+    #[serde(default)]
+    pub led_status: bool,
+    /// When the LED is turned off if `led_rule` is [`LedRule::NightMode`].
+    pub night_mode: LedNightMode,
+}
+impl TapoResponseExt for LedInfoResult {}
+
+/// When the status LED is lit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LedRule {
+    /// The LED is lit while the device is *on*.
+    Always,
+    /// The LED is never lit.
+    Never,
+    /// The LED is lit while the device is *on*, except at night as configured by [`LedNightMode`].
+    #[serde(rename = "auto", alias = "night_mode")]
+    NightMode,
+}
+
+/// When the status LED is turned off at night.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LedNightMode {
+    /// Whether the night follows the sunset and sunrise, or the custom start and end times.
+    pub night_mode_type: LedNightModeType,
+    /// Minutes after sunset, negative for minutes before sunset.
+    #[serde(default)]
+    pub sunset_offset: i16,
+    /// Minutes after sunrise, negative for minutes before sunrise.
+    #[serde(default)]
+    pub sunrise_offset: i16,
+    /// The custom start of the night, in minutes after midnight.
+    #[serde(default)]
+    pub start_time: u16,
+    /// The custom end of the night, in minutes after midnight.
+    #[serde(default)]
+    pub end_time: u16,
+}
+
+impl LedNightMode {
+    /// Returns a [`LedNightMode`] that lasts from sunset to sunrise at the device's location.
+    pub fn sunset_to_sunrise() -> Self {
+        Self {
+            night_mode_type: LedNightModeType::SunriseSunset,
+            sunset_offset: 0,
+            sunrise_offset: 0,
+            start_time: 0,
+            end_time: 0,
+        }
+    }
+
+    /// Returns a [`LedNightMode`] that lasts from `start_time` to `end_time`.
+    ///
+    /// # Arguments
+    ///
+    /// * `start_time` - the start of the night, in minutes after midnight
+    /// * `end_time` - the end of the night, in minutes after midnight
+    pub fn custom(start_time: u16, end_time: u16) -> Self {
+        Self {
+            night_mode_type: LedNightModeType::Custom,
+            sunset_offset: 0,
+            sunrise_offset: 0,
+            start_time,
+            end_time,
+        }
+    }
+}
+
+/// How the night of [`LedNightMode`] is determined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LedNightModeType {
+    /// From sunset to sunrise at the device's location.
+    SunriseSunset,
+    /// From `start_time` to `end_time`.
+    Custom,
+}
@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+use crate::responses::TapoResponseExt;
+
+/// The power protection settings of an energy monitoring plug.
+/// When enabled, the plug turns itself *off* once the load exceeds `protection_power`,
+/// and reports `power_protection_status` as `overloaded` in the device info.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PowerProtectionResult {
+    /// Whether power protection is enabled.
+    pub enabled: bool,
+    /// The power limit in Watts (W).
+    pub protection_power: u16,
+}
+impl TapoResponseExt for PowerProtectionResult {}
+
+/// The maximum power an energy monitoring plug supports,
+/// which is also the highest power protection limit it accepts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MaxPowerResult {
+    /// The maximum power in Watts (W).
+    pub max_power: u16,
+}
+impl TapoResponseExt for MaxPowerResult {}
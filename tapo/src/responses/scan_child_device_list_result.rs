@@ -0,0 +1,67 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+use crate::responses::{DecodableResultExt, TapoResponseExt, decode_value};
+
+/// The child devices found by the hub since `begin_scanning_child_device`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanChildDeviceListResult {
+    /// Whether the hub is still scanning.
+    pub scan_status: ScanStatus,
+    /// The remaining scan time in seconds.
+    #[serde(default)]
+    pub scan_wait_time: u64,
+    /// The devices that are in pairing mode and aren't paired with the hub yet.
+    #[serde(rename = "child_device_list", default)]
+    pub devices: Vec<ScannedChildDevice>,
+}
+impl TapoResponseExt for ScanChildDeviceListResult {}
+
+impl DecodableResultExt for ScanChildDeviceListResult {
+    fn decode(self) -> Result<Self, Error> {
+        Ok(Self {
+            devices: self
+                .devices
+                .into_iter()
+                .map(|device| device.decode())
+                .collect::<Result<Vec<_>, _>>()?,
+            ..self
+        })
+    }
+}
+
+/// The status of the hub's child device scan.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScanStatus {
+    /// The hub is scanning for child devices.
+    Scanning,
+    /// The scan has finished, or hasn't been started.
+    Idle,
+    /// A status that isn't known to this library.
+    #[serde(other)]
+    Other,
+}
+
+/// A child device found by the hub's scan, which can be paired with
+/// [`crate::HubHandler::add_child_device_list`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScannedChildDevice {
+    /// The device ID of the child device.
+    pub device_id: String,
+    /// The category of the child device, e.g. `subg.trigger.temp-hmdt-sensor`.
+    pub category: String,
+    /// The model of the child device, e.g. `T310`.
+    #[serde(rename = "device_model", default)]
+    pub model: String,
+    /// The name of the child device.
+    #[serde(rename = "name", default)]
+    pub nickname: String,
+}
+
+impl DecodableResultExt for ScannedChildDevice {
+    fn decode(mut self) -> Result<Self, Error> {
+        self.nickname = decode_value(&self.nickname)?;
+        Ok(self)
+    }
+}
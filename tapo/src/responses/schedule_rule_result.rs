@@ -0,0 +1,676 @@
+use chrono::{Datelike as _, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::error::Error;
+use crate::responses::{PowerState, TapoResponseExt, TemperatureUnitKE100};
+
+/// The weekly schedules of a device (the "Schedule" feature in the Tapo app).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduleRules {
+    /// Whether the schedules are enabled on the device.
+    pub enabled: bool,
+    /// The maximum number of rules the device can store.
+    pub max_count: u32,
+    /// The schedule rules.
+    pub rules: Vec<ScheduleRule>,
+    /// The rules that can't be represented as a [`ScheduleRule`], e.g. because of an unknown `mode`,
+    /// as returned by the device.
+    pub unsupported_rules: Vec<Value>,
+}
+
+/// A schedule rule that turns the device *on* or *off* at a given time.
+///
+/// Rules returned by the device keep the fields that aren't modelled here, e.g. an end time,
+/// so that editing a rule doesn't reset them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduleRule {
+    /// Device-assigned id, e.g. `S1`.
+    pub id: String,
+    /// Whether the rule is enabled.
+    pub enabled: bool,
+    /// When the rule fires during the day.
+    pub time: ScheduleTime,
+    /// On which days the rule fires.
+    pub recurrence: ScheduleRecurrence,
+    /// The state the device transitions to when the rule fires.
+    pub desired_state: PowerState,
+    /// The rule as returned by the device.
+    #[serde(skip)]
+    pub(crate) raw: Option<Value>,
+}
+
+impl PartialEq for ScheduleRule {
+    /// Compares the modelled fields, the fields that are only kept for editing are ignored.
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+            && self.enabled == other.enabled
+            && self.time == other.time
+            && self.recurrence == other.recurrence
+            && self.desired_state == other.desired_state
+    }
+}
+
+impl Eq for ScheduleRule {}
+
+/// The weekly heating schedule of a KE100 thermostatic radiator valve (TRV).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrvScheduleRules {
+    /// Whether the schedule is enabled on the device.
+    pub enabled: bool,
+    /// The maximum number of rules the device can store.
+    pub max_count: u32,
+    /// The schedule rules.
+    pub rules: Vec<TrvScheduleRule>,
+    /// The rules that can't be represented as a [`TrvScheduleRule`], e.g. without a target temperature,
+    /// as returned by the device.
+    pub unsupported_rules: Vec<Value>,
+}
+
+/// A schedule rule that changes the target temperature of a KE100 at a given time.
+///
+/// Rules returned by the device keep the fields that aren't modelled here,
+/// so that editing a rule doesn't reset them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrvScheduleRule {
+    /// Device-assigned id, e.g. `S1`.
+    pub id: String,
+    /// Whether the rule is enabled.
+    pub enabled: bool,
+    /// When the rule fires during the day.
+    pub time: ScheduleTime,
+    /// On which days the rule fires.
+    pub recurrence: ScheduleRecurrence,
+    /// The target temperature the device switches to when the rule fires, e.g. `21.5`.
+    pub target_temperature: f32,
+    /// The unit of `target_temperature`.
+    pub temperature_unit: TemperatureUnitKE100,
+    /// The rule as returned by the device.
+    #[serde(skip)]
+    pub(crate) raw: Option<Value>,
+}
+
+impl PartialEq for TrvScheduleRule {
+    /// Compares the modelled fields, the fields that are only kept for editing are ignored.
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+            && self.enabled == other.enabled
+            && self.time == other.time
+            && self.recurrence == other.recurrence
+            && self.target_temperature == other.target_temperature
+            && self.temperature_unit == other.temperature_unit
+    }
+}
+
+/// When a [`ScheduleRule`] fires during the day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScheduleTime {
+    /// At a fixed time of day.
+    At {
+        /// Minutes after midnight, between 0 and 1439.
+        minutes: u16,
+    },
+    /// Relative to sunrise at the device's location.
+    Sunrise {
+        /// Minutes after sunrise, negative for minutes before sunrise.
+        offset_minutes: i16,
+    },
+    /// Relative to sunset at the device's location.
+    Sunset {
+        /// Minutes after sunset, negative for minutes before sunset.
+        offset_minutes: i16,
+    },
+}
+
+impl ScheduleTime {
+    /// Returns a [`ScheduleTime::At`] for the given time of day.
+    ///
+    /// # Arguments
+    ///
+    /// * `hour` - between 0 and 23
+    /// * `minute` - between 0 and 59
+    pub fn at(hour: u8, minute: u8) -> Self {
+        Self::At {
+            minutes: hour as u16 * 60 + minute as u16,
+        }
+    }
+
+    /// Returns the `s_type`/`e_type`, minutes after midnight and offset fields of the device payload.
+    pub(crate) fn to_raw(self, field: &str) -> Result<(&'static str, u16, i16), Error> {
+        let (kind, minutes, offset) = match self {
+            Self::At { minutes } if minutes < 24 * 60 => ("normal", minutes, 0),
+            Self::At { .. } => {
+                return Err(Error::Validation {
+                    field: field.to_string(),
+                    message: "Must be between 0 and 1439 minutes after midnight".to_string(),
+                });
+            }
+            Self::Sunrise { offset_minutes } => ("sunrise", 0, offset_minutes),
+            Self::Sunset { offset_minutes } => ("sunset", 0, offset_minutes),
+        };
+
+        if offset.unsigned_abs() >= 12 * 60 {
+            return Err(Error::Validation {
+                field: field.to_string(),
+                message: "The offset must be less than 12 hours".to_string(),
+            });
+        }
+
+        Ok((kind, minutes, offset))
+    }
+
+    pub(crate) fn from_raw(kind: &str, minutes: u16, offset: i16) -> Option<Self> {
+        match kind {
+            "normal" => Some(Self::At { minutes }),
+            "sunrise" => Some(Self::Sunrise {
+                offset_minutes: offset,
+            }),
+            "sunset" => Some(Self::Sunset {
+                offset_minutes: offset,
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// On which days a [`ScheduleRule`] fires.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScheduleRecurrence {
+    /// Every week, on the given days.
+    Weekly(Vec<Weekday>),
+    /// Once, on the given date.
+    Once(NaiveDate),
+}
+
+impl ScheduleRecurrence {
+    /// Returns a [`ScheduleRecurrence::Weekly`] that fires every day of the week.
+    pub fn daily() -> Self {
+        Self::Weekly(vec![
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+            Weekday::Sat,
+            Weekday::Sun,
+        ])
+    }
+
+    /// Returns the `mode`, `week_day` bitmask and date fields of the device payload.
+    pub(crate) fn to_raw(&self) -> Result<(&'static str, u8, NaiveDate), Error> {
+        match self {
+            Self::Weekly(days) if !days.is_empty() => {
+                let week_day = days
+                    .iter()
+                    .fold(0, |mask, day| mask | 1 << day.num_days_from_sunday());
+                Ok(("repeat", week_day, chrono::Local::now().date_naive()))
+            }
+            Self::Weekly(_) => Err(Error::Validation {
+                field: "recurrence".to_string(),
+                message: "Requires at least one day of the week".to_string(),
+            }),
+            Self::Once(date) => Ok(("once", 0, *date)),
+        }
+    }
+
+    pub(crate) fn from_raw(
+        mode: &str,
+        week_day: u8,
+        year: i32,
+        month: u32,
+        day: u32,
+    ) -> Option<Self> {
+        match mode {
+            "repeat" => Some(Self::Weekly(
+                [
+                    Weekday::Mon,
+                    Weekday::Tue,
+                    Weekday::Wed,
+                    Weekday::Thu,
+                    Weekday::Fri,
+                    Weekday::Sat,
+                    Weekday::Sun,
+                ]
+                .into_iter()
+                .filter(|day| week_day & (1 << day.num_days_from_sunday()) != 0)
+                .collect(),
+            )),
+            "once" => NaiveDate::from_ymd_opt(year, month, day).map(Self::Once),
+            _ => None,
+        }
+    }
+}
+
+/// The fields of [`ScheduleRuleRaw`] that are modelled by [`ScheduleRule`] and [`TrvScheduleRule`],
+/// besides `desired_states`.
+const MODELLED_FIELDS: [&str; 10] = [
+    "id",
+    "enable",
+    "mode",
+    "week_day",
+    "s_type",
+    "s_min",
+    "time_offset",
+    "year",
+    "month",
+    "day",
+];
+
+/// The schedule rule payload exchanged with the device.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct ScheduleRuleRaw {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub enable: bool,
+    pub mode: String,
+    #[serde(default)]
+    pub week_day: u8,
+    pub s_type: String,
+    #[serde(default)]
+    pub s_min: u16,
+    #[serde(default)]
+    pub time_offset: i16,
+    #[serde(default = "ScheduleRuleRaw::normal")]
+    pub e_type: String,
+    #[serde(default)]
+    pub e_min: u16,
+    #[serde(default = "ScheduleRuleRaw::none")]
+    pub e_action: String,
+    #[serde(default)]
+    pub year: i32,
+    #[serde(default)]
+    pub month: u32,
+    #[serde(default)]
+    pub day: u32,
+    pub desired_states: ScheduleDesiredStatesRaw,
+}
+
+/// The `desired_states` of a schedule rule: `{ "on": true }` for plugs and lights,
+/// `{ "target_temp": 21, "temp_unit": "celsius" }` for TRVs.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub(crate) struct ScheduleDesiredStatesRaw {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on: Option<bool>,
+    #[serde(
+        default,
+        rename = "target_temp",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub target_temperature: Option<f32>,
+    #[serde(default, rename = "temp_unit", skip_serializing_if = "Option::is_none")]
+    pub temperature_unit: Option<TemperatureUnitKE100>,
+}
+
+impl ScheduleRuleRaw {
+    pub(crate) fn new(
+        id: Option<String>,
+        enabled: bool,
+        time: ScheduleTime,
+        recurrence: &ScheduleRecurrence,
+        desired_state: PowerState,
+    ) -> Result<Self, Error> {
+        let desired_states = ScheduleDesiredStatesRaw {
+            on: Some(desired_state == PowerState::On),
+            ..Default::default()
+        };
+
+        Self::with_desired_states(id, enabled, time, recurrence, desired_states)
+    }
+
+    pub(crate) fn new_trv(
+        id: Option<String>,
+        enabled: bool,
+        time: ScheduleTime,
+        recurrence: &ScheduleRecurrence,
+        target_temperature: f32,
+        temperature_unit: TemperatureUnitKE100,
+    ) -> Result<Self, Error> {
+        let desired_states = ScheduleDesiredStatesRaw {
+            target_temperature: Some(target_temperature),
+            temperature_unit: Some(temperature_unit),
+            ..Default::default()
+        };
+
+        Self::with_desired_states(id, enabled, time, recurrence, desired_states)
+    }
+
+    fn with_desired_states(
+        id: Option<String>,
+        enabled: bool,
+        time: ScheduleTime,
+        recurrence: &ScheduleRecurrence,
+        desired_states: ScheduleDesiredStatesRaw,
+    ) -> Result<Self, Error> {
+        let (s_type, s_min, time_offset) = time.to_raw("time")?;
+        let (mode, week_day, date) = recurrence.to_raw()?;
+
+        Ok(Self {
+            id,
+            enable: enabled,
+            mode: mode.to_string(),
+            week_day,
+            s_type: s_type.to_string(),
+            s_min,
+            time_offset,
+            e_type: Self::normal(),
+            e_min: 0,
+            e_action: Self::none(),
+            year: date.year(),
+            month: date.month(),
+            day: date.day(),
+            desired_states,
+        })
+    }
+
+    /// Parses a rule returned by the device, keeping it as the raw rule.
+    /// Returns `None` if it can't be represented as a [`ScheduleRule`].
+    pub(crate) fn parse_rule(value: &Value) -> Option<ScheduleRule> {
+        let rule = serde_json::from_value::<Self>(value.clone())
+            .ok()?
+            .into_rule()?;
+
+        Some(ScheduleRule {
+            raw: Some(value.clone()),
+            ..rule
+        })
+    }
+
+    /// Parses a TRV rule returned by the device, keeping it as the raw rule.
+    /// Returns `None` if it can't be represented as a [`TrvScheduleRule`].
+    pub(crate) fn parse_trv_rule(value: &Value) -> Option<TrvScheduleRule> {
+        let rule = serde_json::from_value::<Self>(value.clone())
+            .ok()?
+            .into_trv_rule()?;
+
+        Some(TrvScheduleRule {
+            raw: Some(value.clone()),
+            ..rule
+        })
+    }
+
+    /// Returns the edit request payload: `raw` with the modelled fields replaced by the ones of `self`.
+    /// The fields that aren't modelled, e.g. `e_type` and `e_action`, are kept.
+    pub(crate) fn merge_into(self, raw: Option<&Value>) -> Result<Value, Error> {
+        let mut update = match serde_json::to_value(self)? {
+            Value::Object(update) => update,
+            update => return Ok(update),
+        };
+        let Some(Value::Object(raw)) = raw else {
+            return Ok(Value::Object(update));
+        };
+        let mut merged = raw.clone();
+
+        if let Some(Value::Object(desired_states)) = update.remove("desired_states") {
+            let mut merged_states = match merged.remove("desired_states") {
+                Some(Value::Object(merged_states)) => merged_states,
+                _ => Map::new(),
+            };
+            merged_states.extend(desired_states);
+            merged.insert("desired_states".to_string(), Value::Object(merged_states));
+        }
+
+        // The date of a repeating rule is only a stamp, keep the one the device has.
+        if update.get("mode").and_then(Value::as_str) == Some("repeat") {
+            for key in ["year", "month", "day"] {
+                if merged.contains_key(key) {
+                    update.remove(key);
+                }
+            }
+        }
+
+        for key in MODELLED_FIELDS {
+            if let Some(value) = update.remove(key) {
+                merged.insert(key.to_string(), value);
+            }
+        }
+
+        Ok(Value::Object(merged))
+    }
+
+    pub(crate) fn into_rule(self) -> Option<ScheduleRule> {
+        let on = self.desired_states.on?;
+
+        Some(ScheduleRule {
+            time: ScheduleTime::from_raw(&self.s_type, self.s_min, self.time_offset)?,
+            recurrence: ScheduleRecurrence::from_raw(
+                &self.mode,
+                self.week_day,
+                self.year,
+                self.month,
+                self.day,
+            )?,
+            id: self.id?,
+            enabled: self.enable,
+            desired_state: if on { PowerState::On } else { PowerState::Off },
+            raw: None,
+        })
+    }
+
+    pub(crate) fn into_trv_rule(self) -> Option<TrvScheduleRule> {
+        let target_temperature = self.desired_states.target_temperature?;
+
+        Some(TrvScheduleRule {
+            time: ScheduleTime::from_raw(&self.s_type, self.s_min, self.time_offset)?,
+            recurrence: ScheduleRecurrence::from_raw(
+                &self.mode,
+                self.week_day,
+                self.year,
+                self.month,
+                self.day,
+            )?,
+            id: self.id?,
+            enabled: self.enable,
+            target_temperature,
+            temperature_unit: self
+                .desired_states
+                .temperature_unit
+                .unwrap_or(TemperatureUnitKE100::Celsius),
+            raw: None,
+        })
+    }
+
+    fn normal() -> String {
+        "normal".to_string()
+    }
+
+    fn none() -> String {
+        "none".to_string()
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct ScheduleRuleListResultRaw {
+    #[serde(default)]
+    pub enable: bool,
+    #[serde(default)]
+    pub schedule_rule_max_count: u32,
+    #[serde(default)]
+    pub sum: u64,
+    #[serde(default)]
+    pub rule_list: Vec<Value>,
+}
+
+impl TapoResponseExt for ScheduleRuleListResultRaw {}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct AddScheduleRuleResult {
+    pub id: String,
+}
+
+impl TapoResponseExt for AddScheduleRuleResult {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rules_round_trip_through_the_device_payload() {
+        let rule = ScheduleRule {
+            id: "S1".to_string(),
+            enabled: true,
+            time: ScheduleTime::at(7, 30),
+            recurrence: ScheduleRecurrence::Weekly(vec![Weekday::Sun, Weekday::Sat]),
+            desired_state: PowerState::On,
+            raw: None,
+        };
+
+        let raw = ScheduleRuleRaw::new(
+            Some(rule.id.clone()),
+            rule.enabled,
+            rule.time,
+            &rule.recurrence,
+            rule.desired_state,
+        )
+        .unwrap();
+
+        assert_eq!(raw.mode, "repeat");
+        assert_eq!(raw.week_day, 0b100_0001);
+        assert_eq!(raw.s_type, "normal");
+        assert_eq!(raw.s_min, 450);
+
+        let mut expected = rule.clone();
+        expected.recurrence = ScheduleRecurrence::Weekly(vec![Weekday::Sat, Weekday::Sun]);
+        assert_eq!(raw.into_rule(), Some(expected));
+    }
+
+    #[test]
+    fn sun_rules_and_once_rules_are_parsed() {
+        let raw: ScheduleRuleRaw = serde_json::from_value(serde_json::json!({
+            "id": "S2",
+            "enable": false,
+            "mode": "once",
+            "week_day": 0,
+            "s_type": "sunset",
+            "s_min": 1150,
+            "time_offset": -15,
+            "e_type": "normal",
+            "e_min": 0,
+            "e_action": "none",
+            "year": 2026,
+            "month": 10,
+            "day": 18,
+            "desired_states": { "on": false },
+        }))
+        .unwrap();
+
+        assert_eq!(
+            raw.into_rule(),
+            Some(ScheduleRule {
+                id: "S2".to_string(),
+                enabled: false,
+                time: ScheduleTime::Sunset {
+                    offset_minutes: -15
+                },
+                recurrence: ScheduleRecurrence::Once(
+                    NaiveDate::from_ymd_opt(2026, 10, 18).unwrap()
+                ),
+                desired_state: PowerState::Off,
+                raw: None,
+            })
+        );
+    }
+
+    #[test]
+    fn edits_keep_the_fields_that_are_not_modelled() {
+        let device_rule = serde_json::json!({
+            "id": "S1",
+            "enable": true,
+            "mode": "repeat",
+            "week_day": 127,
+            "s_type": "normal",
+            "s_min": 420,
+            "time_offset": 0,
+            "e_type": "normal",
+            "e_min": 480,
+            "e_action": "off",
+            "year": 2026,
+            "month": 1,
+            "day": 1,
+            "desired_states": { "on": true, "brightness": 40 },
+        });
+
+        let mut rule = ScheduleRuleRaw::parse_rule(&device_rule).unwrap();
+        rule.enabled = false;
+        rule.time = ScheduleTime::at(6, 30);
+
+        let edited = ScheduleRuleRaw::new(
+            Some(rule.id.clone()),
+            rule.enabled,
+            rule.time,
+            &rule.recurrence,
+            rule.desired_state,
+        )
+        .unwrap()
+        .merge_into(rule.raw.as_ref())
+        .unwrap();
+
+        assert_eq!(edited["enable"], false);
+        assert_eq!(edited["s_min"], 390);
+        assert_eq!(edited["e_min"], 480);
+        assert_eq!(edited["e_action"], "off");
+        assert_eq!(
+            edited["desired_states"],
+            serde_json::json!({ "on": true, "brightness": 40 })
+        );
+        assert_eq!(edited["year"], 2026);
+        assert_eq!(edited["month"], 1);
+        assert_eq!(edited["day"], 1);
+    }
+
+    #[test]
+    fn trv_rules_round_trip_through_the_device_payload() {
+        let raw = ScheduleRuleRaw::new_trv(
+            Some("S3".to_string()),
+            true,
+            ScheduleTime::at(6, 0),
+            &ScheduleRecurrence::Weekly(vec![Weekday::Mon]),
+            21.5,
+            TemperatureUnitKE100::Celsius,
+        )
+        .unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&raw.desired_states).unwrap(),
+            serde_json::json!({ "target_temp": 21.5, "temp_unit": "celsius" })
+        );
+        assert!(raw.clone().into_rule().is_none());
+        assert_eq!(
+            raw.into_trv_rule(),
+            Some(TrvScheduleRule {
+                id: "S3".to_string(),
+                enabled: true,
+                time: ScheduleTime::at(6, 0),
+                recurrence: ScheduleRecurrence::Weekly(vec![Weekday::Mon]),
+                target_temperature: 21.5,
+                temperature_unit: TemperatureUnitKE100::Celsius,
+                raw: None,
+            })
+        );
+    }
+
+    #[test]
+    fn invalid_rules_are_rejected() {
+        assert!(
+            ScheduleRuleRaw::new(
+                None,
+                true,
+                ScheduleTime::At { minutes: 1440 },
+                &ScheduleRecurrence::daily(),
+                PowerState::On,
+            )
+            .is_err()
+        );
+        assert!(
+            ScheduleRuleRaw::new(
+                None,
+                true,
+                ScheduleTime::at(8, 0),
+                &ScheduleRecurrence::Weekly(Vec::new()),
+                PowerState::On,
+            )
+            .is_err()
+        );
+    }
+}
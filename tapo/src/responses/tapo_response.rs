@@ -25,6 +25,8 @@ pub(crate) struct TapoMultipleResult<T: TapoResponseExt> {
     pub responses: Vec<TapoResponse<T>>,
 }
 
+impl<T> TapoResponseExt for TapoMultipleResult<T> where T: TapoResponseExt {}
+
 pub(crate) fn validate_response(error_code: i64) -> Result<(), Error> {
     if error_code == 0 {
         return Ok(());
@@ -110,3 +112,27 @@ fn error_kind(code: i64) -> &'static str {
         _ => "UNKNOWN",
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn session_expired_error_codes() {
+        for code in [-1501, 9999, -40401] {
+            let err = validate_response(code).unwrap_err();
+            assert!(err.is_session_expired(), "{code} should expire the session");
+        }
+    }
+
+    #[test]
+    fn other_error_codes_do_not_expire_the_session() {
+        for code in [-1002, -1008, -40409, 1234] {
+            let err = validate_response(code).unwrap_err();
+            assert!(
+                !err.is_session_expired(),
+                "{code} should not expire the session"
+            );
+        }
+    }
+}
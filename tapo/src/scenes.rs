@@ -0,0 +1,457 @@
+//! Client-side scenes that fade a color light through keyframed states over time.
+//!
+//! A [`Scene`] is a sequence of [`Keyframe`]s, each one a [`SceneState`] (brightness and color)
+//! at an offset from the start of the scene. Playing a scene sends the state interpolated
+//! between the surrounding keyframes at regular intervals, through
+//! [`ColorLightSetDeviceInfoParams`], so it works the same on every color light without relying
+//! on the device's built-in lighting effects.
+//!
+//! Scenes can be played on [`ColorLightHandler`], [`RgbLightStripHandler`]
+//! and [`RgbicLightStripHandler`], see [`SceneTarget`]. [`Scene::sunrise`] is a ready-made
+//! sunrise simulation, for wake-up routines.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! # use std::time::Duration;
+//! # use tapo::{ApiClient, StreamExt as _};
+//! # use tapo::scenes::{Scene, SceneColor, SceneState};
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let device = ApiClient::new("tapo-username@example.com", "tapo-password")
+//!     .l530("192.168.1.100")
+//!     .await?;
+//!
+//! // An evening wind-down, from a bright daylight white to a dim warm white over 3 hours.
+//! let wind_down = Scene::new(SceneState::new(100, SceneColor::ColorTemperature(5000)))
+//!     .then(
+//!         Duration::from_secs(2 * 60 * 60),
+//!         SceneState::new(60, SceneColor::ColorTemperature(3500)),
+//!     )
+//!     .then(
+//!         Duration::from_secs(60 * 60),
+//!         SceneState::new(20, SceneColor::ColorTemperature(2700)),
+//!     )
+//!     .with_min_interval(Duration::from_secs(10));
+//!
+//! let mut playback = wind_down.play(&device);
+//! while let Some(step) = playback.next().await {
+//!     println!("{:?}", step?);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::pin::Pin;
+use std::task::{Context as TaskContext, Poll};
+use std::time::Duration;
+
+use log::debug;
+use tokio::sync::mpsc::error::TrySendError;
+use tokio::sync::mpsc::{self, Receiver, Sender};
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+use tokio_stream::Stream;
+
+use crate::error::Error;
+use crate::requests::ColorLightSetDeviceInfoParams;
+use crate::utils::{lerp, lerp_hue};
+use crate::{ColorLightHandler, HandlerExt, RgbLightStripHandler, RgbicLightStripHandler};
+
+/// The default minimum interval between two requests of a playing [`Scene`].
+pub const DEFAULT_MIN_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How many steps a [`ScenePlayback`] holds until they are read, newer steps are dropped.
+const PLAYBACK_BUFFER_SIZE: usize = 16;
+
+/// How many times the last keyframe is sent before the playback gives up on the device.
+const MAX_LAST_KEYFRAME_ATTEMPTS: usize = 3;
+
+/// Implemented by the handlers that a [`Scene`] can be played on.
+pub trait SceneTarget: HandlerExt + Clone + 'static {}
+
+impl SceneTarget for ColorLightHandler {}
+impl SceneTarget for RgbLightStripHandler {}
+impl SceneTarget for RgbicLightStripHandler {}
+
+/// The color of a [`SceneState`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SceneColor {
+    /// A color given by its *hue* (between 0 and 360) and *saturation* (between 1 and 100).
+    HueSaturation {
+        /// Between 0 and 360.
+        hue: u16,
+        /// Between 1 and 100.
+        saturation: u8,
+    },
+    /// A white given by its *color temperature*, between 2500 and 6500.
+    ColorTemperature(u16),
+}
+
+/// The state of the light at a point of a [`Scene`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SceneState {
+    /// Between 1 and 100.
+    pub brightness: u8,
+    /// The color of the light.
+    pub color: SceneColor,
+}
+
+impl SceneState {
+    /// Creates a new [`SceneState`].
+    ///
+    /// # Arguments
+    ///
+    /// * `brightness` - between 1 and 100
+    /// * `color` - the color of the light
+    pub fn new(brightness: u8, color: SceneColor) -> Self {
+        Self { brightness, color }
+    }
+
+    fn params(&self) -> ColorLightSetDeviceInfoParams {
+        let params = ColorLightSetDeviceInfoParams::new().brightness(self.brightness);
+
+        match self.color {
+            SceneColor::HueSaturation { hue, saturation } => params.hue_saturation(hue, saturation),
+            SceneColor::ColorTemperature(color_temperature) => {
+                params.color_temperature(color_temperature)
+            }
+        }
+    }
+}
+
+/// A [`SceneState`] at an offset from the start of a [`Scene`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Keyframe {
+    /// The offset from the start of the scene.
+    pub at: Duration,
+    /// The state of the light at `at`.
+    pub state: SceneState,
+}
+
+/// A keyframed sequence of light states, played with [`Scene::play`].
+///
+/// Between two keyframes, the brightness, the hue and saturation and the color temperature
+/// are interpolated linearly, with the hue taking the shortest way around the color wheel.
+/// A hue/saturation color and a color temperature can't be interpolated into one another,
+/// so when two consecutive keyframes use different kinds of color, the color of the first one
+/// is held until the second one is reached.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scene {
+    keyframes: Vec<Keyframe>,
+    min_interval: Duration,
+}
+
+impl Scene {
+    /// Creates a new [`Scene`] that starts at `initial`.
+    pub fn new(initial: SceneState) -> Self {
+        Self {
+            keyframes: vec![Keyframe {
+                at: Duration::ZERO,
+                state: initial,
+            }],
+            min_interval: DEFAULT_MIN_INTERVAL,
+        }
+    }
+
+    /// Returns a sunrise simulation that fades in from a dim red,
+    /// through orange, to a bright daylight white over `duration`.
+    pub fn sunrise(duration: Duration) -> Self {
+        Self::new(SceneState::new(
+            1,
+            SceneColor::HueSaturation {
+                hue: 0,
+                saturation: 100,
+            },
+        ))
+        .keyframe(
+            duration.mul_f64(0.4),
+            SceneState::new(
+                30,
+                SceneColor::HueSaturation {
+                    hue: 30,
+                    saturation: 90,
+                },
+            ),
+        )
+        .keyframe(
+            duration.mul_f64(0.7),
+            SceneState::new(70, SceneColor::ColorTemperature(2700)),
+        )
+        .keyframe(
+            duration,
+            SceneState::new(100, SceneColor::ColorTemperature(5000)),
+        )
+    }
+
+    /// Adds a keyframe at `at` from the start of the scene, replacing the keyframe at `at` if any.
+    pub fn keyframe(mut self, at: Duration, state: SceneState) -> Self {
+        self.keyframes.retain(|keyframe| keyframe.at != at);
+
+        let index = self.keyframes.partition_point(|keyframe| keyframe.at < at);
+        self.keyframes.insert(index, Keyframe { at, state });
+
+        self
+    }
+
+    /// Adds a keyframe `after` the last keyframe.
+    pub fn then(self, after: Duration, state: SceneState) -> Self {
+        let at = self.duration() + after;
+        self.keyframe(at, state)
+    }
+
+    /// Sets the minimum interval between two requests while the scene is playing,
+    /// [`DEFAULT_MIN_INTERVAL`] by default.
+    ///
+    /// Shorter intervals make the fades smoother at the cost of more requests to the device.
+    /// Intervals shorter than 1 millisecond are rounded up to 1 millisecond.
+    pub fn with_min_interval(mut self, min_interval: Duration) -> Self {
+        self.min_interval = min_interval.max(Duration::from_millis(1));
+        self
+    }
+
+    /// Returns the keyframes, ordered by their offset.
+    pub fn keyframes(&self) -> &[Keyframe] {
+        &self.keyframes
+    }
+
+    /// Returns the duration of the scene, i.e. the offset of its last keyframe.
+    pub fn duration(&self) -> Duration {
+        // safe: a scene always has its initial keyframe.
+        self.keyframes.last().unwrap().at
+    }
+
+    /// Returns the state of the light `elapsed` after the start of the scene.
+    pub fn state_at(&self, elapsed: Duration) -> SceneState {
+        let index = self
+            .keyframes
+            .partition_point(|keyframe| keyframe.at <= elapsed);
+
+        let (from, to) = match (index.checked_sub(1), self.keyframes.get(index)) {
+            (Some(from), Some(to)) => (&self.keyframes[from], to),
+            (Some(from), None) => return self.keyframes[from].state,
+            // safe: the initial keyframe is at 0, so it's always at or before `elapsed`.
+            (None, _) => unreachable!("the initial keyframe is at 0"),
+        };
+
+        let progress = (elapsed - from.at).as_secs_f64() / (to.at - from.at).as_secs_f64();
+
+        SceneState {
+            brightness: lerp(
+                from.state.brightness.into(),
+                to.state.brightness.into(),
+                progress,
+            ) as u8,
+            color: match (from.state.color, to.state.color) {
+                (
+                    SceneColor::HueSaturation {
+                        hue: from_hue,
+                        saturation: from_saturation,
+                    },
+                    SceneColor::HueSaturation {
+                        hue: to_hue,
+                        saturation: to_saturation,
+                    },
+                ) => SceneColor::HueSaturation {
+                    hue: lerp_hue(from_hue, to_hue, progress),
+                    saturation: lerp(from_saturation.into(), to_saturation.into(), progress) as u8,
+                },
+                (SceneColor::ColorTemperature(from), SceneColor::ColorTemperature(to)) => {
+                    SceneColor::ColorTemperature(lerp(from.into(), to.into(), progress) as u16)
+                }
+                (from, _) => from,
+            },
+        }
+    }
+
+    /// Starts playing the scene on `handler` and returns a [`ScenePlayback`] stream
+    /// of the states sent to the device.
+    pub fn play<H: SceneTarget>(&self, handler: &H) -> ScenePlayback {
+        let (tx, rx) = mpsc::channel(PLAYBACK_BUFFER_SIZE);
+        let task = tokio::spawn(play(self.clone(), handler.clone(), tx));
+
+        ScenePlayback { rx, task }
+    }
+}
+
+/// A state of a [`Scene`] that was sent to the device, as reported by [`ScenePlayback`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SceneStep {
+    /// The time elapsed since the start of the scene.
+    pub elapsed: Duration,
+    /// The state that was sent to the device.
+    pub state: SceneState,
+}
+
+/// A [`Stream`] of the states sent to the device by a playing [`Scene`], created by [`Scene::play`].
+///
+/// The state is sent at most once every [`Scene::with_min_interval`], and only when it changed
+/// since the last request. The stream ends once the last keyframe has been sent.
+///
+/// Request errors are reported as `Err` items and the playback carries on at the next interval
+/// from wherever the scene is by then, so a light that dropped off the network or whose session
+/// had to be refreshed catches up instead of replaying the missed steps.
+/// Once the scene has reached its last keyframe, sending it is attempted at most 3 times,
+/// so the stream of a scene played on an unreachable light still ends.
+/// A state that fails validation, e.g. a keyframe with a brightness of 0, ends the stream
+/// after its `Err` item, since it would fail again at every interval.
+/// The playback stops when the stream is dropped, leaving the light in the last state sent.
+///
+/// The scene keeps playing while the stream isn't read. Up to 16 unread steps are kept,
+/// further steps are still sent to the device but left out of the stream.
+#[derive(Debug)]
+pub struct ScenePlayback {
+    rx: Receiver<Result<SceneStep, Error>>,
+    task: JoinHandle<()>,
+}
+
+impl Stream for ScenePlayback {
+    type Item = Result<SceneStep, Error>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut TaskContext<'_>,
+    ) -> Poll<Option<Result<SceneStep, Error>>> {
+        Pin::new(&mut self.rx).poll_recv(cx)
+    }
+}
+
+impl Drop for ScenePlayback {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn play<H: SceneTarget>(scene: Scene, handler: H, tx: Sender<Result<SceneStep, Error>>) {
+    let mut interval = tokio::time::interval(scene.min_interval);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    let duration = scene.duration();
+    let mut start: Option<Instant> = None;
+    let mut last_sent: Option<SceneState> = None;
+    let mut last_keyframe_attempts = 0;
+
+    loop {
+        let tick = interval.tick().await;
+
+        let elapsed = tick
+            .duration_since(*start.get_or_insert(tick))
+            .min(duration);
+        let state = scene.state_at(elapsed);
+        let finished = elapsed >= duration;
+
+        if last_sent == Some(state) {
+            if finished {
+                return;
+            }
+            continue;
+        }
+
+        let item = match state.params().send(&handler).await {
+            Ok(()) => {
+                last_sent = Some(state);
+                Ok(SceneStep { elapsed, state })
+            }
+            Err(e) => {
+                debug!("Failed to send the scene state: {e}");
+                Err(e)
+            }
+        };
+
+        let sent = item.is_ok();
+        let invalid = matches!(item, Err(Error::Validation { .. }));
+        match tx.try_send(item) {
+            Ok(()) => {}
+            Err(TrySendError::Full(_)) => {
+                debug!("The scene playback isn't read, dropping the step")
+            }
+            Err(TrySendError::Closed(_)) => return,
+        }
+
+        if finished {
+            last_keyframe_attempts += 1;
+        }
+
+        if invalid || (finished && (sent || last_keyframe_attempts >= MAX_LAST_KEYFRAME_ATTEMPTS)) {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hue_saturation(hue: u16, saturation: u8) -> SceneColor {
+        SceneColor::HueSaturation { hue, saturation }
+    }
+
+    #[test]
+    fn state_at_interpolates_between_keyframes() {
+        let scene = Scene::new(SceneState::new(10, SceneColor::ColorTemperature(2500)))
+            .then(
+                Duration::from_secs(100),
+                SceneState::new(90, SceneColor::ColorTemperature(6500)),
+            )
+            .then(
+                Duration::from_secs(100),
+                SceneState::new(50, hue_saturation(350, 100)),
+            )
+            .then(
+                Duration::from_secs(100),
+                SceneState::new(50, hue_saturation(30, 50)),
+            );
+
+        assert_eq!(scene.duration(), Duration::from_secs(300));
+        assert_eq!(
+            scene.state_at(Duration::from_secs(25)),
+            SceneState::new(30, SceneColor::ColorTemperature(3500))
+        );
+        // The color temperature is held until the hue/saturation keyframe is reached.
+        assert_eq!(
+            scene.state_at(Duration::from_secs(150)),
+            SceneState::new(70, SceneColor::ColorTemperature(6500))
+        );
+        assert_eq!(
+            scene.state_at(Duration::from_secs(200)),
+            SceneState::new(50, hue_saturation(350, 100))
+        );
+        // The hue wraps around 360 instead of going backwards through the whole wheel.
+        assert_eq!(
+            scene.state_at(Duration::from_secs(225)),
+            SceneState::new(50, hue_saturation(0, 88))
+        );
+        assert_eq!(
+            scene.state_at(Duration::from_secs(1000)),
+            SceneState::new(50, hue_saturation(30, 50))
+        );
+    }
+
+    #[test]
+    fn keyframes_are_ordered() {
+        let scene = Scene::new(SceneState::new(1, SceneColor::ColorTemperature(2700)))
+            .keyframe(
+                Duration::from_secs(20),
+                SceneState::new(20, SceneColor::ColorTemperature(2700)),
+            )
+            .keyframe(
+                Duration::from_secs(10),
+                SceneState::new(10, SceneColor::ColorTemperature(2700)),
+            )
+            .keyframe(
+                Duration::from_secs(20),
+                SceneState::new(30, SceneColor::ColorTemperature(2700)),
+            );
+
+        let brightness: Vec<_> = scene
+            .keyframes()
+            .iter()
+            .map(|keyframe| keyframe.state.brightness)
+            .collect();
+        assert_eq!(brightness, [1, 10, 30]);
+        assert_eq!(
+            Scene::sunrise(Duration::from_secs(60)).duration(),
+            Duration::from_secs(60)
+        );
+    }
+}
@@ -0,0 +1,158 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use base64::{Engine as _, engine::general_purpose};
+use rand::RngExt as _;
+use rsa::pkcs8::DecodePublicKey as _;
+use rsa::{Pkcs1v15Encrypt, RsaPublicKey};
+use serde_json::{Value, json};
+
+use crate::api::protocol::{AesCipher, crypto};
+
+use super::http::{Request, Response};
+use super::state::MockState;
+
+const LOGIN: i64 = -1501;
+const SESSION_TIMEOUT: i64 = 9999;
+
+/// The device side of the AES protocol.
+#[derive(Debug)]
+pub(super) struct AesServer {
+    state: Arc<Mutex<MockState>>,
+    username: String,
+    password: String,
+    sessions: Mutex<HashMap<String, AesSession>>,
+}
+
+#[derive(Debug)]
+struct AesSession {
+    key: Vec<u8>,
+    iv: Vec<u8>,
+    token: Option<String>,
+}
+
+impl AesServer {
+    pub fn new(state: Arc<Mutex<MockState>>, username: &str, password: &str) -> Self {
+        let username_digest = AesCipher::sha1_digest_username(username.to_string());
+
+        Self {
+            state,
+            username: general_purpose::STANDARD.encode(username_digest),
+            password: general_purpose::STANDARD.encode(password),
+            sessions: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn expire_sessions(&self) {
+        self.sessions
+            .lock()
+            .expect("sessions lock poisoned")
+            .clear();
+    }
+
+    pub fn handle(&self, request: Request) -> Response {
+        if request.path != "/app" {
+            return Response::status(404);
+        }
+
+        let Some(body) = request.json() else {
+            return Response::status(400);
+        };
+
+        let response = match body["method"].as_str() {
+            Some("handshake") => return self.handshake(&body["params"]),
+            Some("component_nego") => json!({
+                "error_code": 0,
+                "result": { "component_list": [] },
+            }),
+            Some("securePassthrough") => self.secure_passthrough(&request, &body["params"]),
+            _ => json!({ "error_code": -1002 }),
+        };
+
+        Response::json(&response)
+    }
+
+    fn handshake(&self, params: &Value) -> Response {
+        let Some(public_key) = params["key"]
+            .as_str()
+            .and_then(|key| RsaPublicKey::from_public_key_pem(key).ok())
+        else {
+            return Response::json(&json!({ "error_code": -1010 }));
+        };
+
+        let key = rand::rng().random::<[u8; 16]>().to_vec();
+        let iv = rand::rng().random::<[u8; 16]>().to_vec();
+
+        let Ok(encrypted_key) = public_key.encrypt(
+            &mut rand::rng(),
+            Pkcs1v15Encrypt,
+            &[key.as_slice(), &iv].concat(),
+        ) else {
+            return Response::status(500);
+        };
+
+        let session_id = uuid::Uuid::new_v4().simple().to_string().to_uppercase();
+        self.sessions
+            .lock()
+            .expect("sessions lock poisoned")
+            .insert(
+                session_id.clone(),
+                AesSession {
+                    key,
+                    iv,
+                    token: None,
+                },
+            );
+
+        Response::json(&json!({
+            "error_code": 0,
+            "result": { "key": general_purpose::STANDARD.encode(encrypted_key) },
+        }))
+        .with_session_cookie(&session_id)
+    }
+
+    fn secure_passthrough(&self, request: &Request, params: &Value) -> Value {
+        let mut sessions = self.sessions.lock().expect("sessions lock poisoned");
+
+        let Some(session) = request
+            .cookie("TP_SESSIONID")
+            .and_then(|session_id| sessions.get_mut(session_id))
+        else {
+            return json!({ "error_code": SESSION_TIMEOUT });
+        };
+
+        let Some(inner_request) = params["request"]
+            .as_str()
+            .and_then(|request| crypto::aes128_cbc_decrypt(&session.key, &session.iv, request).ok())
+            .and_then(|request| serde_json::from_str::<Value>(&request).ok())
+        else {
+            return json!({ "error_code": -1003 });
+        };
+
+        let inner_response = if inner_request["method"] == "login_device" {
+            let params = &inner_request["params"];
+
+            if params["username"] == self.username.as_str()
+                && params["password"] == self.password.as_str()
+            {
+                let token = uuid::Uuid::new_v4().simple().to_string().to_uppercase();
+                session.token = Some(token.clone());
+                json!({ "error_code": 0, "result": { "token": token } })
+            } else {
+                json!({ "error_code": LOGIN })
+            }
+        } else if session.token.is_some() && session.token.as_deref() == request.query("token") {
+            self.state
+                .lock()
+                .expect("state lock poisoned")
+                .handle_request(inner_request)
+        } else {
+            json!({ "error_code": SESSION_TIMEOUT })
+        };
+
+        match crypto::aes128_cbc_encrypt(&session.key, &session.iv, &inner_response.to_string()) {
+            Ok(response) => json!({ "error_code": 0, "result": { "response": response } }),
+            Err(_) => json!({ "error_code": -1004 }),
+        }
+    }
+}
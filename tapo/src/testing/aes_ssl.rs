@@ -0,0 +1,169 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use rand::RngExt as _;
+use serde_json::{Value, json};
+
+use crate::api::protocol::{AesSslCipher, compute_password_digest, crypto, generate_nonce};
+
+use super::http::{Request, Response};
+use super::state::MockState;
+
+const LOGIN: i64 = -1501;
+const INVALID_NONCE: i64 = -40413;
+const SESSION_EXPIRED: i64 = -40401;
+
+/// The device side of the AES-SSL protocol, served over HTTPS.
+#[derive(Debug)]
+pub(super) struct AesSslServer {
+    state: Arc<Mutex<MockState>>,
+    username: String,
+    password_hash: String,
+    nonces: Mutex<HashMap<String, String>>,
+    sessions: Mutex<HashMap<String, AesSslCipher>>,
+}
+
+impl AesSslServer {
+    pub fn new(state: Arc<Mutex<MockState>>, username: &str, password: &str) -> Self {
+        Self {
+            state,
+            username: username.to_string(),
+            password_hash: crypto::sha256_hex(password.as_bytes()),
+            nonces: Mutex::new(HashMap::new()),
+            sessions: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn expire_sessions(&self) {
+        self.sessions
+            .lock()
+            .expect("sessions lock poisoned")
+            .clear();
+    }
+
+    pub fn handle(&self, request: Request) -> Response {
+        let Some(body) = request.json() else {
+            return Response::status(400);
+        };
+
+        let response = match request.path.as_str() {
+            "/" if body["method"] == "login" => self.login(&body["params"]),
+            path => match path
+                .strip_prefix("/stok=")
+                .and_then(|path| path.strip_suffix("/ds"))
+            {
+                Some(token) => self.request(token, &body),
+                None => return Response::status(404),
+            },
+        };
+
+        Response::json(&response)
+    }
+
+    fn login(&self, params: &Value) -> Value {
+        let (Some(username), Some(local_nonce)) =
+            (params["username"].as_str(), params["cnonce"].as_str())
+        else {
+            return json!({ "error_code": -40210 });
+        };
+
+        if username != self.username {
+            return json!({ "error_code": LOGIN });
+        }
+
+        match params["digest_passwd"].as_str() {
+            None => self.handshake1(local_nonce),
+            Some(password_digest) => self.handshake2(local_nonce, password_digest),
+        }
+    }
+
+    fn handshake1(&self, local_nonce: &str) -> Value {
+        let server_nonce = generate_nonce();
+
+        let confirm_hash = crypto::sha256_hex(
+            format!("{local_nonce}{}{server_nonce}", self.password_hash).as_bytes(),
+        );
+        let device_confirm = format!("{confirm_hash}{server_nonce}{local_nonce}");
+
+        self.nonces
+            .lock()
+            .expect("nonces lock poisoned")
+            .insert(local_nonce.to_string(), server_nonce.clone());
+
+        json!({
+            "error_code": INVALID_NONCE,
+            "result": {
+                "data": {
+                    "code": INVALID_NONCE,
+                    "encrypt_type": ["3"],
+                    "key": "",
+                    "nonce": server_nonce,
+                    "device_confirm": device_confirm,
+                },
+            },
+        })
+    }
+
+    fn handshake2(&self, local_nonce: &str, password_digest: &str) -> Value {
+        let Some(server_nonce) = self
+            .nonces
+            .lock()
+            .expect("nonces lock poisoned")
+            .remove(local_nonce)
+        else {
+            return json!({ "error_code": INVALID_NONCE });
+        };
+
+        if password_digest
+            != compute_password_digest(local_nonce, &server_nonce, &self.password_hash)
+        {
+            return json!({ "error_code": LOGIN });
+        }
+
+        let token = uuid::Uuid::new_v4().simple().to_string();
+        let start_sequence = rand::rng().random_range(1..1000);
+
+        self.sessions
+            .lock()
+            .expect("sessions lock poisoned")
+            .insert(
+                token.clone(),
+                AesSslCipher::new(
+                    local_nonce.to_string(),
+                    &server_nonce,
+                    self.password_hash.clone(),
+                    start_sequence,
+                ),
+            );
+
+        json!({
+            "error_code": 0,
+            "result": {
+                "stok": token,
+                "start_seq": start_sequence,
+                "user_group": "root",
+            },
+        })
+    }
+
+    fn request(&self, token: &str, body: &Value) -> Value {
+        let sessions = self.sessions.lock().expect("sessions lock poisoned");
+
+        let Some(cipher) = sessions.get(token) else {
+            return json!({ "error_code": SESSION_EXPIRED });
+        };
+
+        let Some(inner_request) = body["params"]["request"]
+            .as_str()
+            .and_then(|request| cipher.decrypt(request).ok())
+            .and_then(|request| serde_json::from_str::<Value>(&request).ok())
+        else {
+            return json!({ "error_code": -40210 });
+        };
+
+        self.state
+            .lock()
+            .expect("state lock poisoned")
+            .handle_request(inner_request)
+    }
+}
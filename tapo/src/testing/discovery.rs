@@ -0,0 +1,79 @@
+use std::net::SocketAddr;
+
+use log::debug;
+use serde_json::Value;
+use tokio::net::UdpSocket;
+
+/// The port Tapo devices listen on for discovery queries.
+pub(super) const DISCOVERY_PORT: u16 = 20002;
+
+/// Answers every discovery query received on `socket` with `message`.
+pub(super) async fn respond(socket: UdpSocket, message: Value) {
+    let packet = packet(&message);
+    let mut buf = [0; 2048];
+
+    while let Ok((_, peer)) = socket.recv_from(&mut buf).await {
+        debug!("Mock device answering discovery query from {peer}");
+        if socket.send_to(&packet, peer).await.is_err() {
+            break;
+        }
+    }
+}
+
+/// Returns the discovery response of a device that's reachable at `address`.
+pub(super) fn message(
+    device_info: &Value,
+    device_id: &str,
+    address: SocketAddr,
+    is_support_https: bool,
+    encrypt_type: &str,
+) -> Value {
+    let field = |names: &[&str]| {
+        names
+            .iter()
+            .find_map(|name| device_info[*name].as_str())
+            .unwrap_or_default()
+            .to_string()
+    };
+
+    serde_json::json!({
+        "error_code": 0,
+        "result": {
+            "device_id": device_id,
+            "device_model": field(&["model", "device_model"]),
+            "device_type": field(&["type", "device_type"]),
+            "factory_default": false,
+            "ip": address.ip().to_string(),
+            "is_support_iot_cloud": true,
+            "mac": field(&["mac"]),
+            "mgt_encrypt_schm": {
+                "encrypt_type": encrypt_type,
+                "http_port": address.port(),
+                "is_support_https": is_support_https,
+                "lv": 2,
+            },
+            "obd_src": "tplink",
+            "owner": "",
+        },
+    })
+}
+
+/// Wraps `message` in the 16-byte header that precedes the payload of discovery responses.
+fn packet(message: &Value) -> Vec<u8> {
+    let payload = message.to_string().into_bytes();
+    let size = u16::try_from(payload.len()).unwrap_or(u16::MAX);
+
+    let mut packet = Vec::with_capacity(16 + payload.len());
+    packet.extend_from_slice(&[2, 0]);
+    packet.extend_from_slice(&1u16.to_be_bytes());
+    packet.extend_from_slice(&size.to_be_bytes());
+    packet.extend_from_slice(&[17, 0]);
+    packet.extend_from_slice(&0u32.to_be_bytes());
+    packet.extend_from_slice(&0x5A6B_7C8Du32.to_be_bytes());
+    packet.extend_from_slice(&payload);
+
+    let checksum = crc32fast::hash(&packet);
+    packet[12..16].copy_from_slice(&checksum.to_be_bytes());
+
+    packet
+}
@@ -0,0 +1,360 @@
+use base64::{Engine as _, engine::general_purpose};
+use serde_json::{Value, json};
+
+use crate::DeviceType;
+
+/// Returns the `get_device_info` result of a freshly set up device of the given model.
+pub(super) fn device_info(model: &str, device_id: &str, ip: &str) -> Value {
+    let device_type = DeviceType::from_model(model);
+
+    if device_type == DeviceType::CameraPtz {
+        return camera_device_info(model, device_id);
+    }
+
+    let mut info = json!({
+        "avatar": avatar(device_type),
+        "device_id": device_id,
+        "fw_id": "00000000000000000000000000000000",
+        "fw_ver": "1.0.0 Build 240101 Rel.120000",
+        "has_set_location_info": true,
+        "hw_id": "00000000000000000000000000000000",
+        "hw_ver": "1.0",
+        "ip": ip,
+        "lang": "en_US",
+        "latitude": 0,
+        "longitude": 0,
+        "mac": mac(device_id),
+        "model": model,
+        "oem_id": "00000000000000000000000000000000",
+        "region": "Europe/London",
+        "rssi": -40,
+        "signal_level": 3,
+        "specs": "",
+        "ssid": encode("tapo-mock"),
+        "time_diff": 0,
+        "type": device_type_name(device_type),
+    });
+
+    let specific = match device_type {
+        DeviceType::Light => json!({
+            "brightness": 100,
+            "default_states": {
+                "brightness": {"type": "last_states", "value": 100},
+                "re_power_type": "always_on",
+            },
+            "device_on": false,
+            "nickname": encode(&format!("{model} Mock")),
+            "on_time": 0,
+            "overheated": false,
+        }),
+        DeviceType::ColorLight => json!({
+            "brightness": 100,
+            "color_temp_range": [2500, 6500],
+            "color_temp": 2700,
+            "default_states": {
+                "type": "last_states",
+                "state": {"brightness": 100, "hue": 0, "saturation": 100, "color_temp": 2700},
+            },
+            "device_on": false,
+            "dynamic_light_effect_enable": false,
+            "hue": 0,
+            "nickname": encode(&format!("{model} Mock")),
+            "on_time": 0,
+            "overheated": false,
+            "saturation": 100,
+        }),
+        DeviceType::RgbLightStrip | DeviceType::RgbicLightStrip => json!({
+            "brightness": 100,
+            "color_temp_range": [2500, 6500],
+            "color_temp": 2700,
+            "default_states": {
+                "type": "last_states",
+                "state": {"brightness": 100, "hue": 0, "saturation": 100, "color_temp": 2700},
+            },
+            "device_on": false,
+            "hue": 0,
+            "nickname": encode(&format!("{model} Mock")),
+            "overheated": false,
+            "saturation": 100,
+        }),
+        DeviceType::Plug => json!({
+            "default_states": {"type": "last_states"},
+            "device_on": false,
+            "nickname": encode(&format!("{model} Mock")),
+            "on_time": 0,
+        }),
+        DeviceType::PlugEnergyMonitoring => json!({
+            "charging_status": "normal",
+            "default_states": {"type": "last_states"},
+            "device_on": false,
+            "nickname": encode(&format!("{model} Mock")),
+            "on_time": 0,
+            "overcurrent_status": "normal",
+            "overheat_status": "normal",
+            "power_protection_status": "normal",
+        }),
+        DeviceType::Hub => json!({
+            "in_alarm": false,
+            "in_alarm_source": "",
+            "nickname": encode(&format!("{model} Mock")),
+            "overheated": false,
+        }),
+        DeviceType::Other => json!({
+            "nickname": encode(&format!("{model} Mock")),
+        }),
+        DeviceType::PowerStrip | DeviceType::PowerStripEnergyMonitoring | DeviceType::CameraPtz => {
+            json!({})
+        }
+    };
+
+    merge(&mut info, &specific);
+    info
+}
+
+/// Returns the device info of a freshly paired child device of the given model.
+///
+/// Power strip models return the info of one of the strip's plugs,
+/// every other model the info of a hub child device.
+pub(super) fn child_device_info(
+    model: &str,
+    device_id: &str,
+    parent_device_id: &str,
+    position: usize,
+) -> Value {
+    match DeviceType::from_model(model) {
+        DeviceType::PowerStrip => {
+            power_strip_plug_info(model, device_id, parent_device_id, position)
+        }
+        DeviceType::PowerStripEnergyMonitoring => {
+            let mut info = power_strip_plug_info(model, device_id, parent_device_id, position);
+            merge(
+                &mut info,
+                &json!({
+                    "charging_status": "normal",
+                    "is_usb": false,
+                    "overcurrent_status": "normal",
+                    "power_protection_status": "normal",
+                }),
+            );
+            info
+        }
+        _ => hub_child_info(model, device_id, parent_device_id, position),
+    }
+}
+
+/// Shallowly merges the fields of `update` into `target`.
+pub(super) fn merge(target: &mut Value, update: &Value) {
+    if let (Some(target), Some(update)) = (target.as_object_mut(), update.as_object()) {
+        for (key, value) in update {
+            target.insert(key.clone(), value.clone());
+        }
+    }
+}
+
+fn camera_device_info(model: &str, device_id: &str) -> Value {
+    json!({
+        "avatar": "camera c210",
+        "dev_id": device_id,
+        "device_alias": format!("{model} Mock"),
+        "device_info": format!("{model} 1.0 IPC"),
+        "device_model": model,
+        "device_name": format!("{model} 1.0"),
+        "device_type": "SMART.IPCAMERA",
+        "has_set_location_info": 1,
+        "hw_id": "00000000000000000000000000000000",
+        "hw_version": "1.0",
+        "latitude": 0,
+        "longitude": 0,
+        "mac": mac(device_id),
+        "no_rtsp_constrain": 1,
+        "oem_id": "00000000000000000000000000000000",
+        "region": "EU",
+        "sw_version": "1.0.0 Build 240101 Rel.12000n",
+    })
+}
+
+fn hub_child_info(model: &str, device_id: &str, parent_device_id: &str, position: usize) -> Value {
+    let mut info = json!({
+        "at_low_battery": false,
+        "avatar": "sensor",
+        "bind_count": 1,
+        "category": hub_child_category(model),
+        "device_id": device_id,
+        "fw_ver": "1.0.0 Build 240101 Rel.120000",
+        "hw_id": "00000000000000000000000000000000",
+        "hw_ver": "1.0",
+        "jamming_rssi": -110,
+        "jamming_signal_level": 1,
+        "mac": mac(device_id),
+        "model": model,
+        "nickname": encode(&format!("{model} Mock {position}")),
+        "oem_id": "00000000000000000000000000000000",
+        "parent_device_id": parent_device_id,
+        "region": "Europe/London",
+        "rssi": -60,
+        "signal_level": 3,
+        "specs": "EU",
+        "status": "online",
+        "type": hub_child_type(model),
+    });
+
+    let sensor = json!({
+        "lastOnboardingTimestamp": 1704067200,
+        "report_interval": 16,
+        "status_follow_edge": false,
+    });
+
+    let specific = match model {
+        "KE100" => json!({
+            "child_protection": false,
+            "current_temp": 20.0,
+            "frost_protection_on": false,
+            "location": "",
+            "max_control_temp": 30,
+            "min_control_temp": 5,
+            "target_temp": 21.0,
+            "temp_offset": 0,
+            "temp_unit": "celsius",
+        }),
+        "S200B" | "S200D" => sensor,
+        "S210" => {
+            let mut specific = json!({
+                "battery_percentage": 100,
+                "device_on": false,
+                "is_low": false,
+                "position": position,
+                "slot_number": 1,
+            });
+            merge(&mut specific, &sensor);
+            if let Some(info) = info.as_object_mut() {
+                info.remove("at_low_battery");
+            }
+            specific
+        }
+        "T100" => with(sensor, json!({"detected": false})),
+        "T110" => with(sensor, json!({"open": false})),
+        "T300" => with(
+            sensor,
+            json!({"in_alarm": false, "water_leak_status": "normal"}),
+        ),
+        "T310" | "T315" => with(
+            sensor,
+            json!({
+                "current_humidity_exception": 0,
+                "current_humidity": 50,
+                "current_temp_exception": 0.0,
+                "current_temp": 20.0,
+                "temp_unit": "celsius",
+            }),
+        ),
+        _ => json!({}),
+    };
+
+    merge(&mut info, &specific);
+    info
+}
+
+fn power_strip_plug_info(
+    model: &str,
+    device_id: &str,
+    parent_device_id: &str,
+    position: usize,
+) -> Value {
+    json!({
+        "auto_off_remain_time": 0,
+        "auto_off_status": "off",
+        "avatar": "plug",
+        "bind_count": 1,
+        "category": "plug.powerstrip.sub-plug",
+        "default_states": {"type": "last_states"},
+        "device_id": device_id,
+        "device_on": false,
+        "fw_id": "00000000000000000000000000000000",
+        "fw_ver": "1.0.0 Build 240101 Rel.120000",
+        "has_set_location_info": true,
+        "hw_id": "00000000000000000000000000000000",
+        "hw_ver": "1.0",
+        "latitude": 0,
+        "longitude": 0,
+        "mac": mac(parent_device_id),
+        "model": model,
+        "nickname": encode(&format!("Plug {position}")),
+        "oem_id": "00000000000000000000000000000000",
+        "on_time": 0,
+        "original_device_id": parent_device_id,
+        "overheat_status": "normal",
+        "position": position,
+        "region": "Europe/London",
+        "slot_number": 3,
+        "status_follow_edge": true,
+        "type": "SMART.TAPOPLUG",
+    })
+}
+
+fn with(mut base: Value, update: Value) -> Value {
+    merge(&mut base, &update);
+    base
+}
+
+fn avatar(device_type: DeviceType) -> &'static str {
+    match device_type {
+        DeviceType::Light
+        | DeviceType::ColorLight
+        | DeviceType::RgbLightStrip
+        | DeviceType::RgbicLightStrip => "bulb",
+        DeviceType::Hub => "hub",
+        _ => "plug",
+    }
+}
+
+fn device_type_name(device_type: DeviceType) -> &'static str {
+    match device_type {
+        DeviceType::Light
+        | DeviceType::ColorLight
+        | DeviceType::RgbLightStrip
+        | DeviceType::RgbicLightStrip => "SMART.TAPOBULB",
+        DeviceType::Hub => "SMART.TAPOHUB",
+        DeviceType::CameraPtz => "SMART.IPCAMERA",
+        _ => "SMART.TAPOPLUG",
+    }
+}
+
+fn hub_child_category(model: &str) -> &'static str {
+    match model {
+        "KE100" => "subg.trv",
+        "S200B" | "S200D" => "subg.trigger.button",
+        "S210" => "subg.plugswitch.switch",
+        "T100" => "subg.trigger.motion-sensor",
+        "T110" => "subg.trigger.contact-sensor",
+        "T300" => "subg.trigger.water-leak-sensor",
+        "T310" | "T315" => "subg.trigger.temp-hmdt-sensor",
+        _ => "subg.trigger",
+    }
+}
+
+fn hub_child_type(model: &str) -> &'static str {
+    match model {
+        "KE100" => "SMART.KASAENERGY",
+        "S200B" | "S200D" | "S210" => "SMART.TAPOSWITCH",
+        _ => "SMART.TAPOSENSOR",
+    }
+}
+
+fn mac(device_id: &str) -> String {
+    let hex: String = device_id
+        .chars()
+        .filter(char::is_ascii_hexdigit)
+        .chain(std::iter::repeat('0'))
+        .take(12)
+        .collect();
+
+    hex.as_bytes()
+        .chunks(2)
+        .map(|pair| String::from_utf8_lossy(pair).to_uppercase())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+fn encode(value: &str) -> String {
+    general_purpose::STANDARD.encode(value)
+}
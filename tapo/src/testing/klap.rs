@@ -0,0 +1,168 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use rand::RngExt as _;
+use serde_json::{Value, json};
+
+use crate::api::protocol::KlapCipher;
+use crate::api::protocol::crypto;
+
+use super::http::{Request, Response};
+use super::state::MockState;
+
+/// The device side of the KLAP protocol.
+#[derive(Debug)]
+pub(super) struct KlapServer {
+    state: Arc<Mutex<MockState>>,
+    auth_hash: Vec<u8>,
+    sessions: Mutex<HashMap<String, KlapSession>>,
+}
+
+#[derive(Debug)]
+struct KlapSession {
+    local_seed: Vec<u8>,
+    remote_seed: Vec<u8>,
+    cipher: Option<KlapCipher>,
+}
+
+impl KlapServer {
+    pub fn new(state: Arc<Mutex<MockState>>, username: &str, password: &str) -> Self {
+        let auth_hash = crypto::sha256(
+            &[
+                crypto::sha1(username.as_bytes()),
+                crypto::sha1(password.as_bytes()),
+            ]
+            .concat(),
+        )
+        .to_vec();
+
+        Self {
+            state,
+            auth_hash,
+            sessions: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn expire_sessions(&self) {
+        self.sessions
+            .lock()
+            .expect("sessions lock poisoned")
+            .clear();
+    }
+
+    pub fn handle(&self, request: Request) -> Response {
+        match request.path.as_str() {
+            "/app" => Self::handle_plain(&request),
+            "/app/handshake1" => self.handshake1(&request),
+            "/app/handshake2" => self.handshake2(&request),
+            "/app/request" => self.request(&request),
+            _ => Response::status(404),
+        }
+    }
+
+    /// KLAP devices reject plain requests, which is how clients tell them apart from AES devices.
+    fn handle_plain(request: &Request) -> Response {
+        match request.json() {
+            Some(_) => Response::json(&json!({ "error_code": 1003 })),
+            None => Response::status(400),
+        }
+    }
+
+    fn handshake1(&self, request: &Request) -> Response {
+        if request.body.len() != 16 {
+            return Response::status(400);
+        }
+
+        let local_seed = request.body.clone();
+        let remote_seed = rand::rng().random::<[u8; 16]>().to_vec();
+        let server_hash =
+            crypto::sha256(&[local_seed.as_slice(), &remote_seed, &self.auth_hash].concat());
+
+        let session_id = uuid::Uuid::new_v4().simple().to_string().to_uppercase();
+        let body = [remote_seed.as_slice(), &server_hash].concat();
+
+        self.sessions
+            .lock()
+            .expect("sessions lock poisoned")
+            .insert(
+                session_id.clone(),
+                KlapSession {
+                    local_seed,
+                    remote_seed,
+                    cipher: None,
+                },
+            );
+
+        Response::ok(body).with_session_cookie(&session_id)
+    }
+
+    fn handshake2(&self, request: &Request) -> Response {
+        let mut sessions = self.sessions.lock().expect("sessions lock poisoned");
+
+        let Some(session) = request
+            .cookie("TP_SESSIONID")
+            .and_then(|session_id| sessions.get_mut(session_id))
+        else {
+            return Response::status(403);
+        };
+
+        let expected = crypto::sha256(
+            &[
+                session.remote_seed.as_slice(),
+                &session.local_seed,
+                &self.auth_hash,
+            ]
+            .concat(),
+        );
+        if request.body != expected {
+            return Response::status(403);
+        }
+
+        match KlapCipher::new(
+            session.local_seed.clone(),
+            session.remote_seed.clone(),
+            self.auth_hash.clone(),
+        ) {
+            Ok(cipher) => {
+                session.cipher = Some(cipher);
+                Response::ok(Vec::new())
+            }
+            Err(_) => Response::status(500),
+        }
+    }
+
+    fn request(&self, request: &Request) -> Response {
+        let sessions = self.sessions.lock().expect("sessions lock poisoned");
+
+        let Some(cipher) = request
+            .cookie("TP_SESSIONID")
+            .and_then(|session_id| sessions.get(session_id))
+            .and_then(|session| session.cipher.as_ref())
+        else {
+            return Response::status(403);
+        };
+
+        let Some(seq) = request.query("seq").and_then(|seq| seq.parse().ok()) else {
+            return Response::status(400);
+        };
+
+        let Some(inner_request) = cipher
+            .decrypt(seq, request.body.clone())
+            .ok()
+            .and_then(|request| serde_json::from_str::<Value>(&request).ok())
+        else {
+            return Response::status(400);
+        };
+
+        let response = self
+            .state
+            .lock()
+            .expect("state lock poisoned")
+            .handle_request(inner_request);
+
+        match cipher.encrypt_with_seq(response.to_string(), seq) {
+            Ok(body) => Response::ok(body),
+            Err(_) => Response::status(500),
+        }
+    }
+}
@@ -0,0 +1,580 @@
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::{Arc, Mutex};
+
+use log::debug;
+use serde_json::Value;
+use tokio::net::{TcpListener, UdpSocket};
+use tokio::task::{JoinHandle, JoinSet};
+use tokio_rustls::TlsAcceptor;
+use tokio_rustls::rustls::pki_types::pem::PemObject as _;
+use tokio_rustls::rustls::{self, pki_types::CertificateDer, pki_types::PrivateKeyDer};
+
+use crate::{DeviceType, Error};
+
+use super::aes::AesServer;
+use super::aes_ssl::AesSslServer;
+use super::discovery::{self, DISCOVERY_PORT};
+use super::fixtures;
+use super::http::{self, Request, Response};
+use super::klap::KlapServer;
+use super::state::{DeviceState, FirmwareUpdate, MockState};
+
+const DEFAULT_USERNAME: &str = "tapo-username@example.com";
+const DEFAULT_PASSWORD: &str = "tapo-password";
+
+const CERTIFICATE: &[u8] = include_bytes!("mock_device_cert.pem");
+const PRIVATE_KEY: &[u8] = include_bytes!("mock_device_key.pem");
+
+/// The authentication protocol spoken by a [`MockDevice`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MockProtocol {
+    /// KLAP over HTTP. The default for every model except cameras.
+    Klap,
+    /// AES over HTTP, spoken by older firmware versions.
+    Aes,
+    /// AES over HTTPS with nonce-based authentication. The default for cameras.
+    AesSsl,
+}
+
+/// Builder for a mock Tapo device that serves the device API on localhost.
+///
+/// The device starts with the state of a freshly set up device of the given model
+/// and keeps track of the changes made through `set_device_info`.
+/// See the [module documentation](crate::testing) for an example.
+#[derive(Debug, Clone)]
+pub struct MockDevice {
+    model: String,
+    protocol: MockProtocol,
+    username: String,
+    password: String,
+    ip: IpAddr,
+    device_info: Value,
+    responses: HashMap<String, Value>,
+    children: Vec<MockChild>,
+    scannable_children: Vec<MockChild>,
+    discovery: bool,
+    firmware_update: Option<String>,
+    firmware_install_fails: bool,
+}
+
+impl MockDevice {
+    /// Creates a new mock device of the given model, e.g. `P110` or `L530`.
+    ///
+    /// Note: The device accepts the `tapo-username@example.com` / `tapo-password` credentials.
+    /// Use [`MockDevice::with_credentials`] to change them.
+    ///
+    /// # Arguments
+    ///
+    /// * `model` - the model reported by the device.
+    pub fn new(model: impl Into<String>) -> Self {
+        let model = model.into();
+        let protocol = match DeviceType::from_model(&model) {
+            DeviceType::CameraPtz => MockProtocol::AesSsl,
+            _ => MockProtocol::Klap,
+        };
+
+        Self {
+            model,
+            protocol,
+            username: DEFAULT_USERNAME.to_string(),
+            password: DEFAULT_PASSWORD.to_string(),
+            ip: IpAddr::V4(Ipv4Addr::LOCALHOST),
+            device_info: Value::Null,
+            responses: HashMap::new(),
+            children: Vec::new(),
+            scannable_children: Vec::new(),
+            discovery: false,
+            firmware_update: None,
+            firmware_install_fails: false,
+        }
+    }
+
+    /// Changes the authentication protocol from the default one of the model.
+    ///
+    /// # Arguments
+    ///
+    /// * `protocol` - the protocol spoken by the device.
+    pub fn with_protocol(mut self, protocol: MockProtocol) -> Self {
+        self.protocol = protocol;
+        self
+    }
+
+    /// Changes the credentials accepted by the device.
+    ///
+    /// # Arguments
+    ///
+    /// * `username` - the Tapo username.
+    /// * `password` - the Tapo password.
+    pub fn with_credentials(
+        mut self,
+        username: impl Into<String>,
+        password: impl Into<String>,
+    ) -> Self {
+        self.username = username.into();
+        self.password = password.into();
+        self
+    }
+
+    /// Overrides fields of the device info returned by `get_device_info`.
+    /// Fields such as `nickname` and `ssid` are base64 encoded, as they are on real devices.
+    ///
+    /// # Arguments
+    ///
+    /// * `device_info` - a JSON object with the fields to override.
+    pub fn with_device_info(mut self, device_info: Value) -> Self {
+        if self.device_info.is_null() {
+            self.device_info = device_info;
+        } else {
+            fixtures::merge(&mut self.device_info, &device_info);
+        }
+        self
+    }
+
+    /// Answers every request for `method` with the given result.
+    /// A [`Value::Null`] result answers with an empty, successful response.
+    ///
+    /// Methods without a canned response, other than the ones that
+    /// the mock device implements itself, fail with `UNKNOWN_METHOD`.
+    /// Cameras look up the canned responses of `get` requests by section, e.g. `preset`.
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - the method name, e.g. `get_energy_usage`.
+    /// * `result` - the `result` of the response.
+    pub fn with_response(mut self, method: impl Into<String>, result: Value) -> Self {
+        self.responses.insert(method.into(), result);
+        self
+    }
+
+    /// Adds a child device, reachable through `get_child_device_list` and `control_child`.
+    ///
+    /// # Arguments
+    ///
+    /// * `child` - the child device.
+    pub fn with_child(mut self, child: MockChild) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    /// Adds a child device that is in pairing mode. It's reported by `get_scan_child_device_list`
+    /// while the device is scanning, after `begin_scanning_child_device`,
+    /// and becomes a child of the device once it's added with `add_child_device_list`.
+    ///
+    /// # Arguments
+    ///
+    /// * `child` - the child device.
+    pub fn with_scannable_child(mut self, child: MockChild) -> Self {
+        self.scannable_children.push(child);
+        self
+    }
+
+    /// Changes the loopback address the device listens on from the default `127.0.0.1`.
+    /// Devices that answer discovery queries need distinct addresses.
+    ///
+    /// # Arguments
+    ///
+    /// * `ip` - the address to listen on.
+    pub fn with_ip(mut self, ip: IpAddr) -> Self {
+        self.ip = ip;
+        self
+    }
+
+    /// Answers discovery queries sent to port `20002` of the device's address,
+    /// so that it can be found by [`crate::ApiClient::discover_devices`].
+    pub fn with_discovery(mut self) -> Self {
+        self.discovery = true;
+        self
+    }
+
+    /// Offers a firmware update through `get_latest_fw`.
+    ///
+    /// Once started with `fw_download`, the update is reported by `get_fw_download_state`
+    /// as downloading and then installing, after which the device reports the new `fw_ver`.
+    ///
+    /// # Arguments
+    ///
+    /// * `fw_ver` - the version of the offered firmware.
+    pub fn with_firmware_update(mut self, fw_ver: impl Into<String>) -> Self {
+        self.firmware_update = Some(fw_ver.into());
+        self
+    }
+
+    /// Makes the installation of the update offered by [`MockDevice::with_firmware_update`] fail.
+    ///
+    /// The device goes back to idle after installing, with the update still available.
+    pub fn with_failing_firmware_install(mut self) -> Self {
+        self.firmware_install_fails = true;
+        self
+    }
+
+    /// Starts serving the device on a random port of its address.
+    /// The device stops when the returned [`MockDeviceServer`] is dropped.
+    pub async fn start(self) -> Result<MockDeviceServer, Error> {
+        let listener = TcpListener::bind(SocketAddr::new(self.ip, 0))
+            .await
+            .map_err(anyhow::Error::from)?;
+        let address = listener.local_addr().map_err(anyhow::Error::from)?;
+
+        let generated_device_id = uuid::Uuid::new_v4().simple().to_string().to_uppercase();
+        let mut device_info =
+            fixtures::device_info(&self.model, &generated_device_id, &address.to_string());
+        fixtures::merge(&mut device_info, &self.device_info);
+
+        let device_id = ["device_id", "dev_id"]
+            .iter()
+            .find_map(|field| device_info[*field].as_str())
+            .unwrap_or(&generated_device_id)
+            .to_string();
+
+        let children = self
+            .children
+            .into_iter()
+            .enumerate()
+            .map(|(index, child)| child.into_state(&device_id, index + 1))
+            .collect::<Vec<_>>();
+
+        let scannable_children = self
+            .scannable_children
+            .into_iter()
+            .enumerate()
+            .map(|(index, child)| child.into_state(&device_id, children.len() + index + 1))
+            .collect();
+
+        let state = Arc::new(Mutex::new(MockState {
+            device: DeviceState {
+                device_info: device_info.clone(),
+                responses: self.responses,
+                trigger_logs: Vec::new(),
+                firmware_update: self
+                    .firmware_update
+                    .map(|fw_ver| FirmwareUpdate::new(fw_ver, self.firmware_install_fails)),
+            },
+            children,
+            scannable_children,
+            scanning: false,
+            requests: Vec::new(),
+            camera: DeviceType::from_model(&self.model) == DeviceType::CameraPtz,
+        }));
+
+        let server = Arc::new(match self.protocol {
+            MockProtocol::Klap => ProtocolServer::Klap(KlapServer::new(
+                state.clone(),
+                &self.username,
+                &self.password,
+            )),
+            MockProtocol::Aes => ProtocolServer::Aes(AesServer::new(
+                state.clone(),
+                &self.username,
+                &self.password,
+            )),
+            MockProtocol::AesSsl => ProtocolServer::AesSsl(AesSslServer::new(
+                state.clone(),
+                &self.username,
+                &self.password,
+            )),
+        });
+
+        let tls_acceptor = match self.protocol {
+            MockProtocol::AesSsl => Some(tls_acceptor()?),
+            _ => None,
+        };
+
+        let mut tasks = vec![tokio::spawn(accept(listener, server.clone(), tls_acceptor))];
+
+        if self.discovery {
+            let socket = UdpSocket::bind(SocketAddr::new(self.ip, DISCOVERY_PORT))
+                .await
+                .map_err(anyhow::Error::from)?;
+
+            let message = discovery::message(
+                &device_info,
+                &device_id,
+                address,
+                self.protocol == MockProtocol::AesSsl,
+                match self.protocol {
+                    MockProtocol::Klap => "KLAP",
+                    MockProtocol::Aes | MockProtocol::AesSsl => "AES",
+                },
+            );
+
+            tasks.push(tokio::spawn(discovery::respond(socket, message)));
+        }
+
+        debug!("Mock {} listening on {address}", self.model);
+
+        Ok(MockDeviceServer {
+            address,
+            device_id,
+            state,
+            server,
+            tasks,
+        })
+    }
+}
+
+/// Builder for a child device of a mock hub or power strip.
+#[derive(Debug, Clone)]
+pub struct MockChild {
+    model: String,
+    device_info: Value,
+    responses: HashMap<String, Value>,
+}
+
+impl MockChild {
+    /// Creates a new child device of the given model.
+    /// Use a hub child model (e.g. `T310`) for hubs, and the strip's model (e.g. `P300`) for power strips.
+    ///
+    /// # Arguments
+    ///
+    /// * `model` - the model reported by the child device.
+    pub fn new(model: impl Into<String>) -> Self {
+        Self {
+            model: model.into(),
+            device_info: Value::Null,
+            responses: HashMap::new(),
+        }
+    }
+
+    /// Overrides fields of the child device info. See [`MockDevice::with_device_info`].
+    ///
+    /// # Arguments
+    ///
+    /// * `device_info` - a JSON object with the fields to override.
+    pub fn with_device_info(mut self, device_info: Value) -> Self {
+        if self.device_info.is_null() {
+            self.device_info = device_info;
+        } else {
+            fixtures::merge(&mut self.device_info, &device_info);
+        }
+        self
+    }
+
+    /// Answers every request for `method` sent to the child with the given result.
+    /// See [`MockDevice::with_response`].
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - the method name, e.g. `get_temp_humidity_records`.
+    /// * `result` - the `result` of the response.
+    pub fn with_response(mut self, method: impl Into<String>, result: Value) -> Self {
+        self.responses.insert(method.into(), result);
+        self
+    }
+
+    fn into_state(self, parent_device_id: &str, position: usize) -> DeviceState {
+        let device_id = format!("{parent_device_id}{position:02}");
+        let mut device_info =
+            fixtures::child_device_info(&self.model, &device_id, parent_device_id, position);
+        fixtures::merge(&mut device_info, &self.device_info);
+
+        DeviceState {
+            device_info,
+            responses: self.responses,
+            trigger_logs: Vec::new(),
+            firmware_update: None,
+        }
+    }
+}
+
+/// A running [`MockDevice`]. The device stops when this is dropped.
+#[derive(Debug)]
+pub struct MockDeviceServer {
+    address: SocketAddr,
+    device_id: String,
+    state: Arc<Mutex<MockState>>,
+    server: Arc<ProtocolServer>,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl MockDeviceServer {
+    /// Returns the address to pass to [`crate::ApiClient`], e.g. `127.0.0.1:40123`.
+    pub fn ip_address(&self) -> String {
+        self.address.to_string()
+    }
+
+    /// Returns the device ID.
+    pub fn device_id(&self) -> &str {
+        &self.device_id
+    }
+
+    /// Returns the current device info, as returned by `get_device_info`.
+    pub fn device_info(&self) -> Value {
+        self.state().device.device_info.clone()
+    }
+
+    /// Returns the current device info of every child device.
+    pub fn child_device_list(&self) -> Vec<Value> {
+        self.state()
+            .children
+            .iter()
+            .map(|child| child.device_info.clone())
+            .collect()
+    }
+
+    /// Returns every decrypted request the device has received, in order.
+    /// Handshake and login requests are not included.
+    pub fn requests(&self) -> Vec<Value> {
+        self.state().requests.clone()
+    }
+
+    /// Adds a trigger log to a hub child device, as if the sensor had been triggered.
+    /// The log is returned by `get_trigger_logs`, unless a response was set for that method.
+    ///
+    /// # Arguments
+    ///
+    /// * `device_id` - the device ID of the child device.
+    /// * `log` - the log item, e.g. `{"event": "motion"}`.
+    ///   The `id` and `timestamp` are assigned automatically when missing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the device has no child with the given `device_id`.
+    pub fn add_child_trigger_log(&self, device_id: &str, log: Value) {
+        self.state()
+            .children
+            .iter_mut()
+            .find(|child| child.device_info["device_id"] == device_id)
+            .unwrap_or_else(|| panic!("the mock device has no child {device_id}"))
+            .add_trigger_log(log);
+    }
+
+    /// Returns the params of every request for `method` the device has received, in order.
+    /// Requests batched in a `multipleRequest` are included.
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - the request method, e.g. `set_device_info`.
+    pub fn requests_for(&self, method: &str) -> Vec<Value> {
+        params_for(self.state().requests.iter().flat_map(unbatch), method)
+    }
+
+    /// Returns the params of every request for `method` that was forwarded to a child device
+    /// through `control_child`, in order. Requests batched in a `multipleRequest` are included.
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - the request method of the child device, e.g. `set_device_info`.
+    pub fn child_requests_for(&self, method: &str) -> Vec<Value> {
+        let child_requests = self
+            .state()
+            .requests
+            .iter()
+            .flat_map(unbatch)
+            .filter(|request| request["method"] == "control_child")
+            .flat_map(|request| unbatch(&request["params"]["requestData"]))
+            .collect::<Vec<_>>();
+
+        params_for(child_requests, method)
+    }
+
+    /// Expires all sessions, as devices do after a while.
+    /// Clients have to log in again before their next request succeeds.
+    pub fn expire_sessions(&self) {
+        match &*self.server {
+            ProtocolServer::Klap(server) => server.expire_sessions(),
+            ProtocolServer::Aes(server) => server.expire_sessions(),
+            ProtocolServer::AesSsl(server) => server.expire_sessions(),
+        }
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, MockState> {
+        self.state.lock().expect("state lock poisoned")
+    }
+}
+
+/// Returns the requests batched in `request` if it's a `multipleRequest`, or `request` otherwise.
+fn unbatch(request: &Value) -> Vec<Value> {
+    if request["method"] == "multipleRequest" {
+        request["params"]["requests"]
+            .as_array()
+            .cloned()
+            .unwrap_or_default()
+    } else {
+        vec![request.clone()]
+    }
+}
+
+/// Returns the params of the `requests` for `method`.
+fn params_for(requests: impl IntoIterator<Item = Value>, method: &str) -> Vec<Value> {
+    requests
+        .into_iter()
+        .filter(|request| request["method"] == method)
+        .map(|request| request["params"].clone())
+        .collect()
+}
+
+impl Drop for MockDeviceServer {
+    fn drop(&mut self) {
+        for task in &self.tasks {
+            task.abort();
+        }
+    }
+}
+
+#[derive(Debug)]
+enum ProtocolServer {
+    Klap(KlapServer),
+    Aes(AesServer),
+    AesSsl(AesSslServer),
+}
+
+impl ProtocolServer {
+    fn handle(&self, request: Request) -> Response {
+        match self {
+            ProtocolServer::Klap(server) => server.handle(request),
+            ProtocolServer::Aes(server) => server.handle(request),
+            ProtocolServer::AesSsl(server) => server.handle(request),
+        }
+    }
+}
+
+async fn accept(
+    listener: TcpListener,
+    server: Arc<ProtocolServer>,
+    tls_acceptor: Option<TlsAcceptor>,
+) {
+    // Dropping the set when the task is aborted closes the open connections as well.
+    let mut connections = JoinSet::new();
+
+    while let Ok((stream, peer)) = listener.accept().await {
+        while connections.try_join_next().is_some() {}
+
+        let server = server.clone();
+        let tls_acceptor = tls_acceptor.clone();
+
+        connections.spawn(async move {
+            let handler = |request| server.handle(request);
+
+            let result = match tls_acceptor {
+                Some(tls_acceptor) => match tls_acceptor.accept(stream).await {
+                    Ok(stream) => http::serve(stream, handler).await,
+                    Err(e) => Err(e),
+                },
+                None => http::serve(stream, handler).await,
+            };
+
+            if let Err(e) = result {
+                debug!("Mock device connection from {peer} closed: {e}");
+            }
+        });
+    }
+}
+
+fn tls_acceptor() -> Result<TlsAcceptor, Error> {
+    let certificates = CertificateDer::pem_slice_iter(CERTIFICATE)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(anyhow::Error::from)?;
+    let private_key = PrivateKeyDer::from_pem_slice(PRIVATE_KEY).map_err(anyhow::Error::from)?;
+
+    let config = rustls::ServerConfig::builder_with_provider(Arc::new(
+        rustls::crypto::aws_lc_rs::default_provider(),
+    ))
+    .with_safe_default_protocol_versions()
+    .map_err(anyhow::Error::from)?
+    .with_no_client_auth()
+    .with_single_cert(certificates, private_key)
+    .map_err(anyhow::Error::from)?;
+
+    Ok(TlsAcceptor::from(Arc::new(config)))
+}
@@ -0,0 +1,389 @@
+use std::collections::HashMap;
+
+use serde_json::{Map, Value, json};
+
+use super::fixtures;
+
+const UNKNOWN_METHOD: i64 = -1002;
+const PARAMS: i64 = -1008;
+const UNSUPPORTED_METHOD: i64 = -40106;
+
+const CHILD_DEVICE_LIST_PAGE_SIZE: usize = 10;
+
+/// The state of a mock device or one of its children.
+#[derive(Debug, Clone)]
+pub(super) struct DeviceState {
+    pub device_info: Value,
+    pub responses: HashMap<String, Value>,
+    /// Trigger logs in chronological order (oldest first).
+    pub trigger_logs: Vec<Value>,
+    /// The firmware update offered by `get_latest_fw`, if any.
+    pub firmware_update: Option<FirmwareUpdate>,
+}
+
+/// A simulated firmware update.
+#[derive(Debug, Clone)]
+pub(super) struct FirmwareUpdate {
+    pub fw_ver: String,
+    /// The number of `get_fw_download_state` requests since `fw_download`,
+    /// or `None` if the update hasn't been started.
+    pub step: Option<u8>,
+    /// Whether the device goes back to idle without installing the update.
+    pub fails: bool,
+}
+
+impl FirmwareUpdate {
+    pub fn new(fw_ver: String, fails: bool) -> Self {
+        Self {
+            fw_ver,
+            step: None,
+            fails,
+        }
+    }
+}
+
+impl DeviceState {
+    fn device_id(&self) -> Option<&str> {
+        self.device_info["device_id"].as_str()
+    }
+
+    fn handle(&mut self, method: &str, params: &Value) -> Result<Option<Value>, i64> {
+        match method {
+            "get_device_info" => Ok(Some(self.device_info.clone())),
+            "set_device_info" => {
+                fixtures::merge(&mut self.device_info, params);
+                Ok(None)
+            }
+            "get_trigger_logs" if !self.responses.contains_key(method) => {
+                Ok(Some(self.trigger_logs(params)))
+            }
+            "get_latest_fw" if !self.responses.contains_key(method) => Ok(Some(self.latest_fw())),
+            "fw_download" if !self.responses.contains_key(method) => {
+                if let Some(update) = &mut self.firmware_update {
+                    update.step.get_or_insert(0);
+                }
+                Ok(None)
+            }
+            "get_fw_download_state" if !self.responses.contains_key(method) => {
+                Ok(Some(self.fw_download_state()))
+            }
+            _ => self.canned_response(method).ok_or(UNKNOWN_METHOD),
+        }
+    }
+
+    /// Adds a trigger log, assigning it the next `id` and the current `timestamp` unless set.
+    pub fn add_trigger_log(&mut self, mut log: Value) {
+        let id = self
+            .trigger_logs
+            .last()
+            .and_then(|log| log["id"].as_u64())
+            .unwrap_or_default()
+            + 1;
+
+        if log["id"].is_null() {
+            log["id"] = json!(id);
+        }
+        if log["timestamp"].is_null() {
+            log["timestamp"] = json!(chrono::Utc::now().timestamp());
+        }
+
+        self.trigger_logs.push(log);
+    }
+
+    fn trigger_logs(&self, params: &Value) -> Value {
+        let page_size = params["page_size"].as_u64().unwrap_or(5) as usize;
+        let start_id = params["start_id"].as_u64().unwrap_or_default();
+
+        let logs = self
+            .trigger_logs
+            .iter()
+            .rev()
+            .filter(|log| start_id == 0 || log["id"].as_u64() <= Some(start_id))
+            .take(page_size)
+            .cloned()
+            .collect::<Vec<_>>();
+
+        json!({
+            "start_id": logs.first().map_or(json!(0), |log| log["id"].clone()),
+            "sum": self.trigger_logs.len(),
+            "logs": logs,
+        })
+    }
+
+    fn latest_fw(&self) -> Value {
+        match &self.firmware_update {
+            Some(update) => json!({
+                "need_to_upgrade": true,
+                "fw_ver": update.fw_ver,
+                "fw_size": 1_048_576,
+                "release_date": "2026-01-01",
+                "release_note": "Improved stability and performance.",
+            }),
+            None => json!({ "need_to_upgrade": false }),
+        }
+    }
+
+    /// Steps through downloading (50%, 100%) and installing, then installs the update
+    /// unless the update fails.
+    fn fw_download_state(&mut self) -> Value {
+        let (status, download_progress) = match &mut self.firmware_update {
+            Some(FirmwareUpdate {
+                step: Some(step), ..
+            }) if *step < 3 => {
+                *step += 1;
+                match step {
+                    1 => (2, 50),
+                    2 => (2, 100),
+                    _ => (3, 100),
+                }
+            }
+            Some(FirmwareUpdate {
+                step: step @ Some(_),
+                fails: true,
+                ..
+            }) => {
+                *step = None;
+                (0, 0)
+            }
+            Some(FirmwareUpdate {
+                fw_ver,
+                step: Some(_),
+                ..
+            }) => {
+                self.device_info["fw_ver"] = json!(fw_ver);
+                self.firmware_update = None;
+                (0, 0)
+            }
+            _ => (0, 0),
+        };
+
+        json!({
+            "status": status,
+            "download_progress": download_progress,
+            "reboot_time": 5,
+            "upgrade_time": 5,
+            "auto_upgrade": false,
+        })
+    }
+
+    fn canned_response(&self, key: &str) -> Option<Option<Value>> {
+        self.responses
+            .get(key)
+            .map(|response| (!response.is_null()).then(|| response.clone()))
+    }
+}
+
+/// The state shared by the protocol servers of a mock device.
+#[derive(Debug)]
+pub(super) struct MockState {
+    pub device: DeviceState,
+    pub children: Vec<DeviceState>,
+    /// Child devices in pairing mode, found by `begin_scanning_child_device`.
+    pub scannable_children: Vec<DeviceState>,
+    pub scanning: bool,
+    pub requests: Vec<Value>,
+    pub camera: bool,
+}
+
+impl MockState {
+    /// Handles a decrypted request and returns the response that's to be encrypted.
+    pub fn handle_request(&mut self, request: Value) -> Value {
+        self.requests.push(request.clone());
+
+        let method = request["method"].as_str().unwrap_or_default();
+
+        if self.camera {
+            return self.handle_camera_request(method, &request);
+        }
+
+        response(self.dispatch(method, &request["params"]))
+    }
+
+    fn dispatch(&mut self, method: &str, params: &Value) -> Result<Option<Value>, i64> {
+        match method {
+            "multipleRequest" if !self.device.responses.contains_key(method) => {
+                Ok(Some(multiple_request(params, |method, params| {
+                    self.dispatch(method, params)
+                })))
+            }
+            "get_child_device_list" => Ok(Some(self.child_device_list(params))),
+            "begin_scanning_child_device" => {
+                self.scanning = true;
+                Ok(None)
+            }
+            "end_scanning_child_device" => {
+                self.scanning = false;
+                Ok(None)
+            }
+            "get_scan_child_device_list" => Ok(Some(self.scan_child_device_list())),
+            "add_child_device_list" => self.add_child_device_list(params),
+            "remove_child_device_list" => self.remove_child_device_list(params),
+            "control_child" => self.control_child(params),
+            _ => self.device.handle(method, params),
+        }
+    }
+
+    fn child_device_list(&self, params: &Value) -> Value {
+        let start_index = params["start_index"].as_u64().unwrap_or_default() as usize;
+
+        let page = self
+            .children
+            .iter()
+            .skip(start_index)
+            .take(CHILD_DEVICE_LIST_PAGE_SIZE)
+            .map(|child| child.device_info.clone())
+            .collect::<Vec<_>>();
+
+        json!({
+            "child_device_list": page,
+            "start_index": start_index,
+            "sum": self.children.len(),
+        })
+    }
+
+    fn scan_child_device_list(&self) -> Value {
+        let devices = self
+            .scannable_children
+            .iter()
+            .filter(|_| self.scanning)
+            .map(|child| {
+                json!({
+                    "device_id": child.device_info["device_id"],
+                    "category": child.device_info["category"],
+                    "device_model": child.device_info["model"],
+                    "name": child.device_info["nickname"],
+                })
+            })
+            .collect::<Vec<_>>();
+
+        json!({
+            "child_device_list": devices,
+            "scan_status": if self.scanning { "scanning" } else { "idle" },
+            "scan_wait_time": if self.scanning { 28 } else { 0 },
+        })
+    }
+
+    /// Moves the listed scannable children to the children.
+    fn add_child_device_list(&mut self, params: &Value) -> Result<Option<Value>, i64> {
+        for device_id in child_device_ids(params)? {
+            let index = self
+                .scannable_children
+                .iter()
+                .position(|child| child.device_id() == Some(device_id))
+                .ok_or(PARAMS)?;
+
+            let child = self.scannable_children.remove(index);
+            self.children.push(child);
+        }
+
+        Ok(None)
+    }
+
+    fn remove_child_device_list(&mut self, params: &Value) -> Result<Option<Value>, i64> {
+        for device_id in child_device_ids(params)? {
+            let index = self
+                .children
+                .iter()
+                .position(|child| child.device_id() == Some(device_id))
+                .ok_or(PARAMS)?;
+
+            self.children.remove(index);
+        }
+
+        Ok(None)
+    }
+
+    fn control_child(&mut self, params: &Value) -> Result<Option<Value>, i64> {
+        let device_id = params["device_id"].as_str().ok_or(PARAMS)?;
+        let child = self
+            .children
+            .iter_mut()
+            .find(|child| child.device_id() == Some(device_id))
+            .ok_or(PARAMS)?;
+
+        let request = &params["requestData"];
+        let method = request["method"].as_str().unwrap_or_default();
+
+        let result = match method {
+            "multipleRequest" => Ok(Some(multiple_request(
+                &request["params"],
+                |method, params| child.handle(method, params),
+            ))),
+            _ => child.handle(method, &request["params"]),
+        };
+
+        Ok(Some(json!({ "responseData": response(result) })))
+    }
+
+    fn handle_camera_request(&mut self, method: &str, request: &Value) -> Value {
+        match method {
+            "get" => {
+                let mut response = Map::new();
+                response.insert("error_code".to_string(), json!(0));
+
+                let sections = request.as_object().into_iter().flatten();
+                for (section, params) in sections.filter(|(key, _)| *key != "method") {
+                    let names = params["name"].as_array().into_iter().flatten();
+
+                    let mut values = Map::new();
+                    for name in names.filter_map(Value::as_str) {
+                        let value = if section == "device_info" && name == "basic_info" {
+                            Some(self.device.device_info.clone())
+                        } else {
+                            self.device.responses.get(section).map(|s| s[name].clone())
+                        };
+
+                        match value {
+                            Some(value) if !value.is_null() => {
+                                values.insert(name.to_string(), value);
+                            }
+                            _ => return json!({ "error_code": UNSUPPORTED_METHOD }),
+                        }
+                    }
+
+                    response.insert(section.clone(), Value::Object(values));
+                }
+
+                Value::Object(response)
+            }
+            "do" => json!({ "error_code": 0 }),
+            _ => json!({ "error_code": UNSUPPORTED_METHOD }),
+        }
+    }
+}
+
+fn child_device_ids(params: &Value) -> Result<Vec<&str>, i64> {
+    params["child_device_list"]
+        .as_array()
+        .ok_or(PARAMS)?
+        .iter()
+        .map(|child| child["device_id"].as_str().ok_or(PARAMS))
+        .collect()
+}
+
+fn multiple_request(
+    params: &Value,
+    mut handle: impl FnMut(&str, &Value) -> Result<Option<Value>, i64>,
+) -> Value {
+    let responses = params["requests"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|request| {
+            let method = request["method"].as_str().unwrap_or_default();
+            let mut response = response(handle(method, &request["params"]));
+            response["method"] = json!(method);
+            response
+        })
+        .collect::<Vec<_>>();
+
+    json!({ "responses": responses })
+}
+
+fn response(result: Result<Option<Value>, i64>) -> Value {
+    match result {
+        Ok(Some(result)) => json!({ "error_code": 0, "result": result }),
+        Ok(None) => json!({ "error_code": 0 }),
+        Err(error_code) => json!({ "error_code": error_code }),
+    }
+}
@@ -52,3 +52,22 @@ where
         _ => Err(serde::de::Error::custom("expected bool, integer, or null")),
     }
 }
+
+/// Interpolates linearly between `from` and `to`, rounded to the nearest integer.
+pub(crate) fn lerp(from: f64, to: f64, progress: f64) -> f64 {
+    (from + (to - from) * progress).round()
+}
+
+/// Interpolates the hue the shortest way around the color wheel.
+pub(crate) fn lerp_hue(from: u16, to: u16, progress: f64) -> u16 {
+    let mut delta = f64::from(to) - f64::from(from);
+    if delta > 180.0 {
+        delta -= 360.0;
+    } else if delta < -180.0 {
+        delta += 360.0;
+    }
+
+    (f64::from(from) + delta * progress)
+        .round()
+        .rem_euclid(360.0) as u16
+}
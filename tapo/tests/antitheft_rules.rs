@@ -0,0 +1,91 @@
+use chrono::Weekday;
+use serde_json::{Value, json};
+use tapo::requests::{ScheduleRecurrence, ScheduleTime};
+use tapo::testing::MockDevice;
+
+mod common;
+
+use common::client;
+
+#[tokio::test]
+async fn antitheft_rules_are_parsed_and_managed() {
+    let server = MockDevice::new("L530")
+        .with_response(
+            "get_antitheft_rules",
+            json!({
+                "enable": true,
+                "antitheft_rule_max_count": 1,
+                "start_index": 0,
+                "sum": 1,
+                "rule_list": [{
+                    "id": "A1",
+                    "enable": false,
+                    "mode": "repeat",
+                    "week_day": 65,
+                    "s_type": "sunset",
+                    "s_min": 0,
+                    "time_offset": 15,
+                    "e_type": "normal",
+                    "e_min": 1380,
+                    "e_time_offset": 0,
+                    "year": 2026,
+                    "month": 1,
+                    "day": 1,
+                    "frequency": 5,
+                }],
+            }),
+        )
+        .with_response("add_antitheft_rule", json!({ "id": "A2" }))
+        .with_response("edit_antitheft_rule", Value::Null)
+        .with_response("remove_antitheft_rules", Value::Null)
+        .start()
+        .await
+        .unwrap();
+
+    let device = client().l530(server.ip_address()).await.unwrap();
+
+    let away_mode = device.get_antitheft_rules().await.unwrap();
+    assert!(away_mode.enabled);
+    assert_eq!(away_mode.max_count, 1);
+    assert_eq!(away_mode.rules.len(), 1);
+    let rule = &away_mode.rules[0];
+    assert!(!rule.enabled);
+    assert_eq!(rule.start, ScheduleTime::Sunset { offset_minutes: 15 });
+    assert_eq!(rule.end, ScheduleTime::at(23, 0));
+    assert_eq!(
+        rule.recurrence,
+        ScheduleRecurrence::Weekly(vec![Weekday::Sat, Weekday::Sun])
+    );
+
+    let mut rule = device
+        .add_antitheft_rule(
+            ScheduleTime::at(18, 0),
+            ScheduleTime::at(22, 30),
+            ScheduleRecurrence::daily(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(rule.id, "A2");
+
+    rule.enabled = false;
+    device.edit_antitheft_rule(&rule).await.unwrap();
+    device.remove_all_antitheft_rules().await.unwrap();
+
+    let params = |method: &str| {
+        server
+            .requests_for(method)
+            .into_iter()
+            .next()
+            .unwrap_or_else(|| panic!("{method} wasn't sent"))
+    };
+
+    let added = params("add_antitheft_rule");
+    assert_eq!(added["s_min"], 18 * 60);
+    assert_eq!(added["e_min"], 22 * 60 + 30);
+    assert_eq!(added["week_day"], 127);
+    assert_eq!(params("edit_antitheft_rule")["id"], "A2");
+    assert_eq!(
+        params("remove_antitheft_rules"),
+        json!({ "remove_all": true })
+    );
+}
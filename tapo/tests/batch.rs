@@ -0,0 +1,138 @@
+use serde_json::json;
+use tapo::testing::MockDevice;
+use tapo::{Error, TapoResponseError};
+
+mod common;
+
+use common::client;
+
+#[tokio::test]
+async fn batch_returns_typed_results_in_order() {
+    let server = MockDevice::new("P110")
+        .with_response(
+            "get_energy_usage",
+            json!({
+                "today_runtime": 60,
+                "month_runtime": 600,
+                "today_energy": 120,
+                "month_energy": 1200,
+                "local_time": "2026-01-01 12:00:00",
+                "current_power": 7000,
+            }),
+        )
+        .with_response("get_current_power", json!({ "current_power": 7 }))
+        .start()
+        .await
+        .unwrap();
+
+    let device = client().p110(server.ip_address()).await.unwrap();
+
+    let (device_info, (), energy_usage, current_power) = device
+        .batch()
+        .get_device_info()
+        .on()
+        .get_energy_usage()
+        .get_current_power()
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(device_info.device_id, server.device_id());
+    assert_eq!(device_info.nickname, "P110 Mock");
+    assert_eq!(energy_usage.today_energy, 120);
+    assert_eq!(current_power.current_power, 7);
+    assert_eq!(server.device_info()["device_on"], true);
+
+    let requests = server
+        .requests()
+        .into_iter()
+        .filter(|request| request["method"] == "multipleRequest")
+        .collect::<Vec<_>>();
+    assert_eq!(requests.len(), 1);
+
+    let batched = requests[0]["params"]["requests"].as_array().unwrap();
+    assert_eq!(batched.len(), 4);
+    assert_eq!(batched[1]["method"], "set_device_info");
+    assert_eq!(batched[1]["params"], json!({ "device_on": true }));
+    assert_eq!(batched[1]["terminalUUID"], "00-00-00-00-00-00");
+    assert!(batched[1]["requestTimeMilis"].as_u64().unwrap() > 0);
+}
+
+#[tokio::test]
+async fn batch_is_split_according_to_the_max_requests() {
+    let server = MockDevice::new("L530").start().await.unwrap();
+    let device = client().l530(server.ip_address()).await.unwrap();
+
+    let (first, (), second) = device
+        .batch()
+        .with_max_requests(2)
+        .get_device_info()
+        .on()
+        .get_device_info()
+        .send()
+        .await
+        .unwrap();
+
+    assert!(!first.device_on);
+    assert!(second.device_on);
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn batch_is_split_in_chunks_of_five_by_default() {
+    let server = MockDevice::new("L530").start().await.unwrap();
+    let device = client().l530(server.ip_address()).await.unwrap();
+
+    device
+        .batch()
+        .get_device_info()
+        .get_device_info()
+        .get_device_info()
+        .get_device_info()
+        .get_device_info()
+        .get_device_info()
+        .send()
+        .await
+        .unwrap();
+
+    let chunks = server
+        .requests()
+        .into_iter()
+        .filter(|request| request["method"] == "multipleRequest")
+        .map(|request| request["params"]["requests"].as_array().unwrap().len())
+        .collect::<Vec<_>>();
+    assert_eq!(chunks, [5, 1]);
+}
+
+#[tokio::test]
+async fn batch_fails_when_the_response_count_mismatches() {
+    let server = MockDevice::new("L530")
+        .with_response("multipleRequest", json!({ "responses": [] }))
+        .start()
+        .await
+        .unwrap();
+    let device = client().l530(server.ip_address()).await.unwrap();
+
+    let result = device.batch().get_device_info().send().await;
+
+    assert!(matches!(
+        result,
+        Err(Error::Tapo(TapoResponseError::ResponseError { description }))
+            if description.contains("Expected 1 responses")
+    ));
+}
+
+#[tokio::test]
+async fn batch_fails_when_a_request_fails() {
+    let server = MockDevice::new("P110").start().await.unwrap();
+    let device = client().p110(server.ip_address()).await.unwrap();
+
+    let result = device
+        .batch()
+        .get_device_info()
+        .get_current_power()
+        .send()
+        .await;
+
+    assert!(result.is_err());
+}
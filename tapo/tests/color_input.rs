@@ -0,0 +1,54 @@
+use serde_json::json;
+use tapo::ColorControl;
+use tapo::testing::MockDevice;
+
+mod common;
+
+use common::client;
+
+#[tokio::test]
+async fn hex_colors_are_sent_as_hue_and_saturation() {
+    let server = MockDevice::new("L530").start().await.unwrap();
+    let device = client().l530(server.ip_address()).await.unwrap();
+
+    device.set().hex("#804400").send(&device).await.unwrap();
+
+    let set_device_info = server.requests_for("set_device_info").pop().unwrap();
+    assert_eq!(
+        set_device_info,
+        json!({ "brightness": 50, "hue": 32, "saturation": 100, "color_temp": 0 })
+    );
+}
+
+#[tokio::test]
+async fn kelvin_is_clamped_to_the_device_range() {
+    let server = MockDevice::new("L920")
+        .with_device_info(json!({ "color_temp_range": [2700, 6500] }))
+        .start()
+        .await
+        .unwrap();
+    let device = client().l920(server.ip_address()).await.unwrap();
+
+    let range = ColorControl::get_color_temperature_range(&device)
+        .await
+        .unwrap();
+    assert_eq!(range, 2700..=6500);
+
+    device
+        .set()
+        .color_temperature_range(range)
+        .kelvin(2200)
+        .send(&device)
+        .await
+        .unwrap();
+
+    let set_device_info = server.requests_for("set_device_info").pop().unwrap();
+    assert_eq!(set_device_info, json!({ "color_temp": 2700 }));
+
+    // Without an explicit range, the range of the device is used.
+    device.set().kelvin(2000).send(&device).await.unwrap();
+
+    let set_device_info = server.requests_for("set_device_info").pop().unwrap();
+    assert_eq!(set_device_info, json!({ "color_temp": 2700 }));
+    assert_eq!(server.requests_for("get_device_info").len(), 1);
+}
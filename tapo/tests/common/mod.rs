@@ -0,0 +1,6 @@
+use tapo::ApiClient;
+
+/// Returns a client with the credentials the mock devices accept.
+pub fn client() -> ApiClient {
+    ApiClient::new("tapo-username@example.com", "tapo-password")
+}
@@ -0,0 +1,99 @@
+use serde_json::{Value, json};
+use tapo::responses::{
+    ColorLightState, DefaultColorLightState, DefaultPlugState, DefaultStateType, LedNightMode,
+    LedRule, PlugState,
+};
+use tapo::testing::MockDevice;
+
+mod common;
+
+use common::client;
+
+#[tokio::test]
+async fn default_state_is_set() {
+    let server = MockDevice::new("P110").start().await.unwrap();
+    let device = client().p110(server.ip_address()).await.unwrap();
+
+    device
+        .set_default_state(DefaultPlugState::Custom {
+            state: PlugState { on: true },
+        })
+        .await
+        .unwrap();
+
+    let device_info = device.get_device_info().await.unwrap();
+    assert!(matches!(
+        device_info.default_states,
+        DefaultPlugState::Custom {
+            state: PlugState { on: true }
+        }
+    ));
+
+    let server = MockDevice::new("L530").start().await.unwrap();
+    let device = client().l530(server.ip_address()).await.unwrap();
+
+    device
+        .set_default_state(DefaultColorLightState {
+            r#type: DefaultStateType::Custom,
+            state: ColorLightState {
+                brightness: 80,
+                hue: None,
+                saturation: None,
+                color_temp: 4000,
+            },
+        })
+        .await
+        .unwrap();
+
+    let set_device_info = server.requests_for("set_device_info");
+    assert_eq!(
+        set_device_info[0]["default_states"],
+        json!({ "type": "custom", "state": { "brightness": 80, "color_temp": 4000 } })
+    );
+}
+
+#[tokio::test]
+async fn led_night_mode_is_set() {
+    let server = MockDevice::new("P100")
+        .with_response(
+            "get_led_info",
+            json!({
+                "led_rule": "always",
+                "led_status": true,
+                "night_mode": {
+                    "night_mode_type": "sunrise_sunset",
+                    "sunrise_offset": 0,
+                    "sunset_offset": 0,
+                    "start_time": 1320,
+                    "end_time": 360,
+                },
+            }),
+        )
+        .with_response("set_led_info", Value::Null)
+        .start()
+        .await
+        .unwrap();
+
+    let device = client().p100(server.ip_address()).await.unwrap();
+
+    let mut led_info = device.get_led_info().await.unwrap();
+    assert_eq!(led_info.led_rule, LedRule::Always);
+    assert!(led_info.led_status);
+
+    led_info.led_rule = LedRule::NightMode;
+    led_info.night_mode = LedNightMode::custom(22 * 60, 7 * 60);
+    device.set_led_info(led_info).await.unwrap();
+
+    let set_led_info = &server.requests_for("set_led_info")[0];
+    assert_eq!(set_led_info["led_rule"], "auto");
+    assert_eq!(
+        set_led_info["night_mode"],
+        json!({
+            "night_mode_type": "custom",
+            "sunset_offset": 0,
+            "sunrise_offset": 0,
+            "start_time": 1320,
+            "end_time": 420,
+        })
+    );
+}
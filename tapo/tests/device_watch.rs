@@ -0,0 +1,72 @@
+use std::time::Duration;
+
+use serde_json::json;
+use tapo::testing::{MockChild, MockDevice};
+use tapo::{Plug, StreamExt as _};
+
+mod common;
+
+use common::client;
+
+const INTERVAL: Duration = Duration::from_millis(20);
+
+#[tokio::test]
+async fn watch_reports_only_the_changed_fields() {
+    let server = MockDevice::new("L530").start().await.unwrap();
+    let device = client().l530(server.ip_address()).await.unwrap();
+
+    let mut changes = device.watch(INTERVAL);
+    // Let the first poll set the baseline.
+    tokio::time::sleep(INTERVAL * 3).await;
+
+    device
+        .set()
+        .on()
+        .brightness(30)
+        .send(&device)
+        .await
+        .unwrap();
+
+    let diff = changes.next().await.unwrap().unwrap();
+    let fields = diff
+        .changes
+        .iter()
+        .map(|change| change.field.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(fields, ["brightness", "device_on"]);
+    assert_eq!(diff.get("device_on").unwrap().old, json!(false));
+    assert_eq!(diff.get("device_on").unwrap().new, json!(true));
+    assert_eq!(diff.get("brightness").unwrap().new, json!(30));
+
+    assert!(
+        tokio::time::timeout(INTERVAL * 5, changes.next())
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn watch_includes_the_current_power_of_energy_monitoring_plugs() {
+    let server = MockDevice::new("P304M")
+        .with_child(
+            MockChild::new("P304M")
+                .with_response("get_current_power", json!({ "current_power": 5 })),
+        )
+        .start()
+        .await
+        .unwrap();
+
+    let strip = client().p304(server.ip_address()).await.unwrap();
+    let plug = strip.plug(Plug::ByPosition(1)).await.unwrap();
+
+    let mut changes = plug.watch(INTERVAL);
+    tokio::time::sleep(INTERVAL * 3).await;
+
+    plug.on().await.unwrap();
+
+    let diff = changes.next().await.unwrap().unwrap();
+    assert_eq!(diff.changes.len(), 1);
+    assert_eq!(diff.get("device_on").unwrap().new, json!(true));
+
+    assert!(!server.child_requests_for("get_current_power").is_empty());
+}
@@ -0,0 +1,131 @@
+use std::time::Duration;
+
+use tapo::responses::FirmwareDownloadStatus;
+use tapo::testing::MockDevice;
+use tapo::{FirmwareRollout, FirmwareUpdateProgress, StreamExt as _};
+
+mod common;
+
+use common::client;
+
+const FW_VER: &str = "1.2.0 Build 260101 Rel.120000";
+
+#[tokio::test]
+async fn firmware_updates_are_downloaded_and_installed() {
+    let server = MockDevice::new("P110")
+        .with_firmware_update(FW_VER)
+        .start()
+        .await
+        .unwrap();
+
+    let device = client().p110(server.ip_address()).await.unwrap();
+
+    let latest_firmware = device.get_latest_firmware().await.unwrap();
+    assert!(latest_firmware.need_to_upgrade);
+    assert_eq!(latest_firmware.fw_ver, FW_VER);
+
+    let state = device.get_fw_download_state().await.unwrap();
+    assert_eq!(state.status, FirmwareDownloadStatus::Idle);
+
+    device.fw_download().await.unwrap();
+
+    let mut statuses = Vec::new();
+    for _ in 0..4 {
+        let state = device.get_fw_download_state().await.unwrap();
+        statuses.push((state.status, state.download_progress));
+    }
+    assert_eq!(
+        statuses,
+        vec![
+            (FirmwareDownloadStatus::Downloading, 50),
+            (FirmwareDownloadStatus::Downloading, 100),
+            (FirmwareDownloadStatus::Installing, 100),
+            (FirmwareDownloadStatus::Idle, 0),
+        ]
+    );
+
+    assert!(!device.get_latest_firmware().await.unwrap().need_to_upgrade);
+    assert_eq!(device.get_device_info().await.unwrap().fw_ver, FW_VER);
+}
+
+#[tokio::test]
+async fn rollout_checks_and_updates_outdated_devices() {
+    let outdated = MockDevice::new("L530")
+        .with_firmware_update(FW_VER)
+        .start()
+        .await
+        .unwrap();
+    let up_to_date = MockDevice::new("P100").start().await.unwrap();
+
+    let mut devices = Vec::new();
+    for server in [&outdated, &up_to_date] {
+        devices.push(Ok(client().connect(server.ip_address()).await.unwrap()));
+    }
+
+    let rollout = FirmwareRollout::new()
+        .with_max_concurrent(1)
+        .with_poll_interval(Duration::from_millis(10));
+
+    let report = rollout.check(tokio_stream::iter(devices)).await;
+    assert_eq!(report.devices.len(), 2);
+    assert!(report.errors.is_empty());
+
+    let outdated_devices = report.outdated().cloned().collect::<Vec<_>>();
+    assert_eq!(outdated_devices.len(), 1);
+    assert_eq!(outdated_devices[0].device.ip(), outdated.ip_address());
+
+    let updates = rollout.update(outdated_devices).collect::<Vec<_>>().await;
+    let progress = updates
+        .iter()
+        .map(|update| {
+            assert_eq!(update.ip, outdated.ip_address());
+            format!("{:?}", update.progress)
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        progress,
+        vec![
+            "Started".to_string(),
+            "Downloading { progress: 50 }".to_string(),
+            "Downloading { progress: 100 }".to_string(),
+            "Installing".to_string(),
+            format!("Completed {{ fw_ver: {FW_VER:?} }}"),
+        ]
+    );
+
+    assert_eq!(outdated.device_info()["fw_ver"], FW_VER);
+    assert_ne!(up_to_date.device_info()["fw_ver"], FW_VER);
+}
+
+#[tokio::test]
+async fn rollout_reports_failed_installs_without_waiting_for_the_timeout() {
+    let server = MockDevice::new("P110")
+        .with_firmware_update(FW_VER)
+        .with_failing_firmware_install()
+        .start()
+        .await
+        .unwrap();
+
+    let device = client().connect(server.ip_address()).await.unwrap();
+    let rollout = FirmwareRollout::new()
+        .with_poll_interval(Duration::from_millis(10))
+        .with_timeout(Duration::from_secs(60));
+
+    let report = rollout.check(tokio_stream::iter([Ok(device)])).await;
+    let outdated_devices = report.outdated().cloned().collect::<Vec<_>>();
+
+    let updates = tokio::time::timeout(
+        Duration::from_secs(5),
+        rollout.update(outdated_devices).collect::<Vec<_>>(),
+    )
+    .await
+    .unwrap();
+
+    let last = updates.last().unwrap();
+    assert!(
+        matches!(&last.progress, FirmwareUpdateProgress::Failed(error) if error.to_string().contains("wasn't installed")),
+        "{:?}",
+        last.progress
+    );
+    assert_ne!(server.device_info()["fw_ver"], FW_VER);
+}
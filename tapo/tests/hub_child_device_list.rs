@@ -0,0 +1,95 @@
+use tapo::StreamExt as _;
+use tapo::responses::ChildDeviceHubResult;
+use tapo::testing::{MockChild, MockDevice, MockDeviceServer};
+
+mod common;
+
+use common::client;
+
+fn start_indexes(server: &MockDeviceServer) -> Vec<u64> {
+    server
+        .requests_for("get_child_device_list")
+        .iter()
+        .map(|params| params["start_index"].as_u64().unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn child_device_list_stream_pages_lazily() {
+    let mut device = MockDevice::new("H100");
+    for _ in 0..25 {
+        device = device.with_child(MockChild::new("T100"));
+    }
+    let server = device.start().await.unwrap();
+
+    let hub = client().h100(server.ip_address()).await.unwrap();
+
+    let mut children = hub.get_child_device_list_stream();
+    for _ in 0..10 {
+        children.next().await.unwrap().unwrap();
+    }
+    assert_eq!(start_indexes(&server), vec![0]);
+
+    children.next().await.unwrap().unwrap();
+    assert_eq!(start_indexes(&server), vec![0, 10]);
+
+    let mut remaining = 0;
+    while let Some(child) = children.next().await {
+        child.unwrap();
+        remaining += 1;
+    }
+    assert_eq!(remaining, 14);
+    assert_eq!(start_indexes(&server), vec![0, 10, 20]);
+}
+
+#[tokio::test]
+async fn child_device_list_diff_reports_added_removed_and_renamed_children() {
+    let server = MockDevice::new("H100")
+        .with_child(MockChild::new("T100"))
+        .with_child(MockChild::new("T110"))
+        .with_scannable_child(MockChild::new("T310"))
+        .start()
+        .await
+        .unwrap();
+
+    let hub = client().h100(server.ip_address()).await.unwrap();
+
+    let mut previous = hub.get_child_device_list().await.unwrap();
+    let diff = hub.child_device_list_diff(&previous).await.unwrap();
+    assert!(diff.is_empty());
+
+    let t100_id = previous[0].device_id().to_string();
+    let t110_id = previous[1].device_id().to_string();
+    if let ChildDeviceHubResult::T100(t100) = &mut previous[0] {
+        t100.nickname = "Hallway".to_string();
+    }
+
+    hub.begin_scanning_child_device().await.unwrap();
+    let scan = hub.get_scan_child_device_list().await.unwrap();
+    hub.add_child_device_list(&scan.devices).await.unwrap();
+    hub.remove_child_device_list(vec![t110_id.clone()])
+        .await
+        .unwrap();
+
+    let diff = hub.child_device_list_diff(&previous).await.unwrap();
+    assert!(!diff.is_empty());
+
+    assert_eq!(diff.added.len(), 1);
+    assert_eq!(diff.added[0].model(), "T310");
+
+    assert_eq!(diff.removed.len(), 1);
+    assert_eq!(diff.removed[0].device_id(), t110_id);
+
+    assert_eq!(diff.renamed.len(), 1);
+    assert_eq!(diff.renamed[0].device_id, t100_id);
+    assert_eq!(diff.renamed[0].old_nickname, "Hallway");
+    assert_eq!(diff.renamed[0].new_nickname, "T100 Mock 1");
+
+    assert_eq!(diff.current.len(), 2);
+    assert!(
+        hub.child_device_list_diff(&diff.current)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
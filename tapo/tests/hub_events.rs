@@ -0,0 +1,143 @@
+use std::time::Duration;
+
+use serde_json::json;
+use tapo::testing::{MockChild, MockDevice, MockDeviceServer};
+use tapo::{HubEventKind, HubEvents, HubHandler, StreamExt as _};
+
+mod common;
+
+use common::client;
+
+const INTERVAL: Duration = Duration::from_millis(20);
+
+async fn hub() -> (MockDeviceServer, HubHandler, String, String) {
+    let server = MockDevice::new("H100")
+        .with_child(MockChild::new("T100"))
+        .with_child(MockChild::new("S200B"))
+        .with_child(MockChild::new("T310"))
+        .start()
+        .await
+        .unwrap();
+
+    let hub = client().h100(server.ip_address()).await.unwrap();
+
+    let children = server.child_device_list();
+    let t100_id = children[0]["device_id"].as_str().unwrap().to_string();
+    let s200_id = children[1]["device_id"].as_str().unwrap().to_string();
+
+    (server, hub, t100_id, s200_id)
+}
+
+async fn wait_for_first_poll(events: &HubEvents) {
+    while events.last_seen_ids().len() < 2 {
+        tokio::time::sleep(INTERVAL).await;
+    }
+}
+
+#[tokio::test]
+async fn events_are_reported_once_in_chronological_order() {
+    let (server, hub, t100_id, s200_id) = hub().await;
+    server.add_child_trigger_log(&t100_id, json!({ "event": "motion" }));
+
+    let mut events = hub.events(INTERVAL);
+    wait_for_first_poll(&events).await;
+
+    for _ in 0..25 {
+        server.add_child_trigger_log(&t100_id, json!({ "event": "motion" }));
+    }
+    server.add_child_trigger_log(
+        &s200_id,
+        json!({ "event": "rotation", "params": { "rotate_deg": -30 } }),
+    );
+    server.add_child_trigger_log(&s200_id, json!({ "event": "doubleClick" }));
+
+    let mut t100_ids = Vec::new();
+    let mut s200_kinds = Vec::new();
+    while t100_ids.len() + s200_kinds.len() < 27 {
+        let event = events.next().await.unwrap().unwrap();
+        if event.device_id == t100_id {
+            assert_eq!(event.kind, HubEventKind::Motion);
+            t100_ids.push(event.id);
+        } else {
+            assert_eq!(event.model, "S200B");
+            s200_kinds.push(event.kind);
+        }
+    }
+
+    assert_eq!(t100_ids, (2..=26).collect::<Vec<_>>());
+    assert_eq!(
+        s200_kinds,
+        vec![
+            HubEventKind::Rotation {
+                rotation_degrees: -30
+            },
+            HubEventKind::DoubleClick,
+        ]
+    );
+
+    tokio::time::sleep(INTERVAL * 5).await;
+    assert!(
+        tokio::time::timeout(INTERVAL * 5, events.next())
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn events_resume_from_the_last_seen_ids() {
+    let (server, hub, t100_id, _) = hub().await;
+
+    let events = hub.events(INTERVAL);
+    wait_for_first_poll(&events).await;
+    let last_seen_ids = events.last_seen_ids();
+    drop(events);
+
+    server.add_child_trigger_log(&t100_id, json!({ "event": "motion" }));
+
+    let mut events = hub.events_from(INTERVAL, last_seen_ids);
+    let event = events.next().await.unwrap().unwrap();
+    assert_eq!(event.device_id, t100_id);
+    assert_eq!(event.id, 1);
+}
+
+#[tokio::test]
+async fn events_survive_expired_sessions() {
+    let (server, hub, t100_id, _) = hub().await;
+
+    let mut events = hub.events(INTERVAL);
+    wait_for_first_poll(&events).await;
+
+    server.expire_sessions();
+    server.add_child_trigger_log(&t100_id, json!({ "event": "motion" }));
+
+    let event = events.next().await.unwrap().unwrap();
+    assert_eq!(event.kind, HubEventKind::Motion);
+}
+
+#[tokio::test]
+async fn events_resume_after_the_stream_is_dropped_mid_batch() {
+    let (server, hub, t100_id, _) = hub().await;
+
+    let mut events = hub.events(INTERVAL);
+    wait_for_first_poll(&events).await;
+
+    for _ in 0..5 {
+        server.add_child_trigger_log(&t100_id, json!({ "event": "motion" }));
+    }
+
+    for id in 1..=2 {
+        assert_eq!(events.next().await.unwrap().unwrap().id, id);
+    }
+    // Let the poller send the rest of the batch to the stream.
+    tokio::time::sleep(INTERVAL * 3).await;
+    let last_seen_ids = events.last_seen_ids();
+    assert_eq!(last_seen_ids[&t100_id], 2);
+    drop(events);
+
+    let mut events = hub.events_from(INTERVAL, last_seen_ids);
+    for id in 3..=5 {
+        let event = events.next().await.unwrap().unwrap();
+        assert_eq!(event.device_id, t100_id);
+        assert_eq!(event.id, id);
+    }
+}
@@ -0,0 +1,71 @@
+use serde_json::json;
+use tapo::HubDevice;
+use tapo::responses::ScanStatus;
+use tapo::testing::{MockChild, MockDevice};
+
+mod common;
+
+use common::client;
+
+#[tokio::test]
+async fn scanned_children_are_paired_and_unpaired() {
+    let server = MockDevice::new("H100")
+        .with_child(MockChild::new("T100"))
+        .with_scannable_child(MockChild::new("T310"))
+        .with_scannable_child(MockChild::new("T110"))
+        .start()
+        .await
+        .unwrap();
+
+    let hub = client().h100(server.ip_address()).await.unwrap();
+
+    let scan = hub.get_scan_child_device_list().await.unwrap();
+    assert_eq!(scan.scan_status, ScanStatus::Idle);
+    assert!(scan.devices.is_empty());
+
+    hub.begin_scanning_child_device().await.unwrap();
+
+    let scan = hub.get_scan_child_device_list().await.unwrap();
+    assert_eq!(scan.scan_status, ScanStatus::Scanning);
+    assert_eq!(scan.devices.len(), 2);
+    assert_eq!(scan.devices[0].model, "T310");
+    assert_eq!(scan.devices[0].nickname, "T310 Mock 2");
+    assert_eq!(scan.devices[0].category, "subg.trigger.temp-hmdt-sensor");
+
+    let sensors = scan
+        .devices
+        .into_iter()
+        .filter(|device| device.model == "T310")
+        .collect::<Vec<_>>();
+    hub.add_child_device_list(&sensors).await.unwrap();
+    hub.end_scanning_child_device().await.unwrap();
+
+    let add_requests = server.requests_for("add_child_device_list");
+    assert_eq!(
+        add_requests[0],
+        json!({
+            "child_device_list": [{
+                "device_id": sensors[0].device_id,
+                "category": "subg.trigger.temp-hmdt-sensor",
+            }],
+        })
+    );
+
+    let t310 = hub
+        .t31x(HubDevice::ByDeviceId(sensors[0].device_id.clone()))
+        .await
+        .unwrap();
+    assert_eq!(
+        t310.get_device_info().await.unwrap().nickname,
+        "T310 Mock 2"
+    );
+    assert_eq!(hub.get_child_device_list().await.unwrap().len(), 2);
+
+    hub.remove_child_device_list(vec![sensors[0].device_id.clone()])
+        .await
+        .unwrap();
+
+    let children = hub.get_child_device_list().await.unwrap();
+    assert_eq!(children.len(), 1);
+    assert_eq!(children[0].model(), "T100");
+}
@@ -0,0 +1,155 @@
+use chrono::Weekday;
+use serde_json::{Value, json};
+use tapo::requests::{ScheduleRecurrence, ScheduleTime, TemperatureUnitKE100};
+use tapo::testing::{MockChild, MockDevice, MockDeviceServer};
+use tapo::{HubDevice, KE100Handler};
+
+mod common;
+
+use common::client;
+
+async fn ke100(child: MockChild) -> (MockDeviceServer, KE100Handler) {
+    let server = MockDevice::new("H100")
+        .with_child(child)
+        .start()
+        .await
+        .unwrap();
+
+    let hub = client().h100(server.ip_address()).await.unwrap();
+    let device_id = server.child_device_list()[0]["device_id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let ke100 = hub.ke100(HubDevice::ByDeviceId(device_id)).await.unwrap();
+
+    (server, ke100)
+}
+
+#[tokio::test]
+async fn heating_schedule_is_parsed_and_managed() {
+    let child = MockChild::new("KE100")
+        .with_response(
+            "get_schedule_rules",
+            json!({
+                "enable": true,
+                "schedule_rule_max_count": 32,
+                "start_index": 0,
+                "sum": 3,
+                "rule_list": [
+                    {
+                        "id": "S1",
+                        "enable": true,
+                        "mode": "repeat",
+                        "week_day": 62,
+                        "s_type": "normal",
+                        "s_min": 390,
+                        "time_offset": 0,
+                        "e_type": "normal",
+                        "e_min": 0,
+                        "e_action": "none",
+                        "year": 2026,
+                        "month": 1,
+                        "day": 1,
+                        "desired_states": { "target_temp": 21.5, "temp_unit": "celsius" },
+                    },
+                    {
+                        "id": "S2",
+                        "enable": true,
+                        "mode": "repeat",
+                        "week_day": 62,
+                        "s_type": "normal",
+                        "s_min": 1320,
+                        "time_offset": 0,
+                        "e_type": "normal",
+                        "e_min": 0,
+                        "e_action": "none",
+                        "year": 2026,
+                        "month": 1,
+                        "day": 1,
+                        "desired_states": { "target_temp": 17.0 },
+                    },
+                    {
+                        "id": "S4",
+                        "enable": true,
+                        "mode": "repeat",
+                        "week_day": 65,
+                        "s_type": "normal",
+                        "s_min": 600,
+                        "desired_states": { "frost_protection_on": true },
+                    },
+                ],
+            }),
+        )
+        .with_response("add_schedule_rule", json!({ "id": "S3" }))
+        .with_response("edit_schedule_rule", Value::Null)
+        .with_response("remove_schedule_rules", Value::Null);
+    let (server, ke100) = ke100(child).await;
+
+    let schedule = ke100.get_schedule_rules().await.unwrap();
+    assert!(schedule.enabled);
+    assert_eq!(schedule.rules.len(), 2);
+    assert_eq!(schedule.rules[0].time, ScheduleTime::at(6, 30));
+    assert_eq!(schedule.rules[0].target_temperature, 21.5);
+    assert_eq!(schedule.rules[1].target_temperature, 17.0);
+    assert_eq!(schedule.unsupported_rules.len(), 1);
+    assert_eq!(schedule.unsupported_rules[0]["id"], "S4");
+
+    let weekend = ScheduleRecurrence::Weekly(vec![Weekday::Sat, Weekday::Sun]);
+    assert!(
+        ke100
+            .add_schedule_rule(
+                ScheduleTime::at(8, 0),
+                weekend.clone(),
+                40.0,
+                TemperatureUnitKE100::Celsius
+            )
+            .await
+            .is_err()
+    );
+
+    let rule = ke100
+        .add_schedule_rule(
+            ScheduleTime::at(8, 0),
+            weekend,
+            22.5,
+            TemperatureUnitKE100::Celsius,
+        )
+        .await
+        .unwrap();
+    assert_eq!(rule.id, "S3");
+
+    let mut rule = schedule.rules[0].clone();
+    rule.enabled = false;
+    ke100.edit_schedule_rule(&rule).await.unwrap();
+
+    let mut rule = schedule.rules[1].clone();
+    rule.target_temperature = 16.0;
+    ke100.edit_schedule_rule(&rule).await.unwrap();
+    ke100
+        .remove_schedule_rules(vec!["S1".to_string()])
+        .await
+        .unwrap();
+
+    let added = &server.child_requests_for("add_schedule_rule")[0];
+    assert_eq!(added["week_day"], 0b100_0001);
+    assert_eq!(added["s_min"], 480);
+    assert_eq!(
+        added["desired_states"],
+        json!({ "target_temp": 22.5, "temp_unit": "celsius" })
+    );
+
+    let edited = server.child_requests_for("edit_schedule_rule");
+    assert_eq!(edited[0]["id"], "S1");
+    assert_eq!(edited[0]["enable"], false);
+    assert_eq!(
+        edited[0]["desired_states"],
+        json!({ "target_temp": 21.5, "temp_unit": "celsius" })
+    );
+    assert_eq!(edited[1]["id"], "S2");
+    assert_eq!(edited[1]["desired_states"]["target_temp"], 16.0);
+
+    assert_eq!(
+        server.child_requests_for("remove_schedule_rules"),
+        vec![json!({ "remove_all": false, "rule_list": [{ "id": "S1" }] })]
+    );
+}
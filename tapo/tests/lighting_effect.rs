@@ -0,0 +1,68 @@
+use serde_json::json;
+use tapo::Error;
+use tapo::requests::{LightingEffect, LightingEffectPreset, LightingEffectType};
+use tapo::testing::MockDevice;
+
+mod common;
+
+use common::client;
+
+#[tokio::test]
+async fn running_lighting_effect_is_returned() {
+    let aurora = LightingEffect::from(LightingEffectPreset::Aurora);
+    let server = MockDevice::new("L930")
+        .with_device_info(json!({
+            "lighting_effect": {
+                "brightness": 60,
+                "custom": 0,
+                "display_colors": aurora.display_colors,
+                "enable": 1,
+                "id": aurora.id,
+                "name": "Aurora",
+            },
+        }))
+        .start()
+        .await
+        .unwrap();
+    let device = client().l930(server.ip_address()).await.unwrap();
+
+    let lighting_effect = device.get_lighting_effect().await.unwrap().unwrap();
+    assert_eq!(lighting_effect.brightness, 60);
+    assert!(!lighting_effect.is_custom);
+    assert_eq!(lighting_effect.preset(), Some(LightingEffectPreset::Aurora));
+
+    let server = MockDevice::new("L930")
+        .with_device_info(json!({
+            "lighting_effect": { "custom": 0, "enable": 0, "id": "", "name": "" },
+        }))
+        .start()
+        .await
+        .unwrap();
+    let device = client().l930(server.ip_address()).await.unwrap();
+
+    assert_eq!(device.get_lighting_effect().await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn invalid_lighting_effects_are_not_sent() {
+    let server = MockDevice::new("L930").start().await.unwrap();
+    let device = client().l930(server.ip_address()).await.unwrap();
+
+    let lighting_effect = LightingEffect::new(
+        "Invalid",
+        LightingEffectType::Random,
+        true,
+        true,
+        100,
+        vec![[0, 100, 100]],
+    )
+    .with_segments(vec![0])
+    .with_hue_range([300, 200]);
+
+    let result = device.set_lighting_effect(lighting_effect).await;
+    assert!(matches!(
+        result,
+        Err(Error::Validation { field, .. }) if field == "hue_range"
+    ));
+    assert!(server.requests_for("set_lighting_effect").is_empty());
+}
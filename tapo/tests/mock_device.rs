@@ -0,0 +1,262 @@
+use std::net::{IpAddr, Ipv4Addr};
+
+use serde_json::json;
+use tapo::responses::{ChildDeviceHubResult, TemperatureUnitKE100};
+use tapo::testing::{MockChild, MockDevice, MockProtocol};
+use tapo::{DeviceType, HubDevice, Plug, StreamExt as _};
+
+mod common;
+
+use common::client;
+
+const MODELS: [&str; 22] = [
+    "L510", "L520", "L530", "L535", "L610", "L630", "L900", "L920", "L930", "P100", "P105", "P110",
+    "P110M", "P115", "P300", "P304M", "P306", "P316M", "H100", "C210", "C520WS", "TC70",
+];
+
+const HUB_CHILD_MODELS: [&str; 9] = [
+    "KE100", "S200B", "S200D", "S210", "T100", "T110", "T300", "T310", "T315",
+];
+
+#[tokio::test]
+async fn every_model_connects_to_its_handler() {
+    for model in MODELS {
+        let server = MockDevice::new(model).start().await.unwrap();
+
+        let device = client()
+            .connect(server.ip_address())
+            .await
+            .unwrap_or_else(|e| panic!("{model}: {e}"));
+
+        assert_eq!(
+            device.device_type(),
+            DeviceType::from_model(model),
+            "{model}"
+        );
+        assert_eq!(device.device_id(), server.device_id(), "{model}");
+        assert_eq!(device.model(), model);
+    }
+}
+
+#[tokio::test]
+async fn every_protocol_logs_in_and_refreshes_expired_sessions() {
+    for protocol in [MockProtocol::Klap, MockProtocol::Aes] {
+        let server = MockDevice::new("P100")
+            .with_protocol(protocol)
+            .start()
+            .await
+            .unwrap();
+
+        let device = client().p100(server.ip_address()).await.unwrap();
+        device.on().await.unwrap();
+
+        server.expire_sessions();
+        device.off().await.unwrap();
+
+        assert_eq!(server.device_info()["device_on"], false, "{protocol:?}");
+        assert_eq!(server.requests().len(), 2, "{protocol:?}");
+    }
+
+    let server = MockDevice::new("C210").start().await.unwrap();
+    let device = client().c210(server.ip_address()).await.unwrap();
+
+    server.expire_sessions();
+    let device_info = device.get_device_info().await.unwrap();
+    assert_eq!(device_info.device_id, server.device_id());
+}
+
+#[tokio::test]
+async fn wrong_credentials_are_rejected() {
+    for protocol in [MockProtocol::Klap, MockProtocol::Aes, MockProtocol::AesSsl] {
+        let server = MockDevice::new("P100")
+            .with_protocol(protocol)
+            .with_credentials("someone-else@example.com", "tapo-password")
+            .start()
+            .await
+            .unwrap();
+
+        let result = client().p100(server.ip_address()).await;
+        assert!(result.is_err(), "{protocol:?}");
+    }
+}
+
+#[tokio::test]
+async fn set_device_info_updates_the_device_state() {
+    let server = MockDevice::new("L530")
+        .with_protocol(MockProtocol::Aes)
+        .start()
+        .await
+        .unwrap();
+
+    let device = client().l530(server.ip_address()).await.unwrap();
+    device
+        .set()
+        .on()
+        .brightness(42)
+        .hue_saturation(120, 80)
+        .send(&device)
+        .await
+        .unwrap();
+
+    let device_info = device.get_device_info().await.unwrap();
+    assert!(device_info.device_on);
+    assert_eq!(device_info.brightness, 42);
+    assert_eq!(device_info.hue, Some(120));
+    assert_eq!(device_info.saturation, Some(80));
+    assert_eq!(device_info.nickname, "L530 Mock");
+}
+
+#[tokio::test]
+async fn canned_responses_and_unknown_methods() {
+    let server = MockDevice::new("P110")
+        .with_response("get_current_power", json!({ "current_power": 7 }))
+        .start()
+        .await
+        .unwrap();
+
+    let device = client().p110(server.ip_address()).await.unwrap();
+    assert_eq!(device.get_current_power().await.unwrap().current_power, 7);
+    assert!(device.get_energy_usage().await.is_err());
+}
+
+#[tokio::test]
+async fn hub_children_are_paginated_and_controllable() {
+    let mut device = MockDevice::new("H100");
+    for model in HUB_CHILD_MODELS {
+        device = device.with_child(MockChild::new(model));
+    }
+    let server = device
+        .with_child(MockChild::new("T100").with_device_info(json!({ "detected": true })))
+        .with_child(MockChild::new("T310").with_device_info(json!({ "current_temp": 23.5 })))
+        .with_child(MockChild::new("KE100"))
+        .start()
+        .await
+        .unwrap();
+
+    let hub = client().h100(server.ip_address()).await.unwrap();
+
+    let children = hub.get_child_device_list().await.unwrap();
+    assert_eq!(children.len(), HUB_CHILD_MODELS.len() + 3);
+    assert!(
+        !children
+            .iter()
+            .any(|child| matches!(child, ChildDeviceHubResult::Other(_)))
+    );
+
+    let t310_id = children[10].device_id().to_string();
+    let t310 = hub.t31x(HubDevice::ByDeviceId(t310_id)).await.unwrap();
+    assert_eq!(
+        t310.get_device_info().await.unwrap().current_temperature,
+        23.5
+    );
+
+    let ke100_id = children[11].device_id().to_string();
+    let ke100 = hub
+        .ke100(HubDevice::ByDeviceId(ke100_id.clone()))
+        .await
+        .unwrap();
+    ke100
+        .set_target_temperature(24, TemperatureUnitKE100::Celsius)
+        .await
+        .unwrap();
+
+    let ke100_info = server
+        .child_device_list()
+        .into_iter()
+        .find(|child| child["device_id"] == ke100_id.as_str())
+        .unwrap();
+    assert_eq!(ke100_info["target_temp"], 24);
+}
+
+#[tokio::test]
+async fn power_strip_plugs_are_controllable() {
+    for model in ["P300", "P304M"] {
+        let server = MockDevice::new(model)
+            .with_child(MockChild::new(model))
+            .with_child(MockChild::new(model))
+            .with_child(MockChild::new(model))
+            .start()
+            .await
+            .unwrap();
+
+        let strip = client().p300(server.ip_address()).await.unwrap();
+        assert_eq!(strip.get_child_device_list().await.unwrap().len(), 3);
+
+        let plug = strip.plug(Plug::ByPosition(2)).await.unwrap();
+        plug.on().await.unwrap();
+
+        let plugs = server.child_device_list();
+        assert_eq!(plugs[0]["device_on"], false, "{model}");
+        assert_eq!(plugs[1]["device_on"], true, "{model}");
+    }
+}
+
+#[tokio::test]
+async fn camera_requests_are_recorded() {
+    let server = MockDevice::new("C210")
+        .with_response(
+            "preset",
+            json!({
+                "preset": {
+                    "id": ["1"],
+                    "name": ["Door"],
+                    "position_pan": ["0.5"],
+                    "position_tilt": ["-0.25"],
+                    "read_only": ["0"],
+                },
+            }),
+        )
+        .start()
+        .await
+        .unwrap();
+
+    let camera = client().c210(server.ip_address()).await.unwrap();
+    camera.pan_tilt(10, -10).await.unwrap();
+
+    let presets = camera.get_presets().await.unwrap();
+    assert_eq!(presets.len(), 1);
+    assert_eq!(presets[0].name, "Door");
+
+    let requests = server.requests();
+    assert!(
+        requests
+            .iter()
+            .any(|request| request["method"] == "do" && request["motor"]["move"]["x_coord"] == "10")
+    );
+}
+
+#[tokio::test]
+async fn discovery_finds_the_device() {
+    let ip = IpAddr::V4(Ipv4Addr::new(127, 0, 0, 2));
+    let server = MockDevice::new("L900")
+        .with_ip(ip)
+        .with_discovery()
+        .start()
+        .await
+        .unwrap();
+
+    let mut discovery = client().discover_devices(ip.to_string(), 3).await.unwrap();
+    let device = discovery.next().await.unwrap().unwrap();
+
+    assert_eq!(device.device_type(), DeviceType::RgbLightStrip);
+    assert_eq!(device.device_id(), server.device_id());
+    assert_eq!(device.ip(), server.ip_address());
+}
+
+#[tokio::test]
+async fn discovery_reports_the_ip_without_the_port() {
+    let ip = IpAddr::V4(Ipv4Addr::new(127, 0, 0, 3));
+    let server = MockDevice::new("X100")
+        .with_ip(ip)
+        .with_discovery()
+        .start()
+        .await
+        .unwrap();
+
+    let mut discovery = client().discover_devices(ip.to_string(), 3).await.unwrap();
+    let device = discovery.next().await.unwrap().unwrap();
+
+    assert_eq!(device.device_type(), DeviceType::Other);
+    assert_eq!(device.device_id(), server.device_id());
+    assert_eq!(device.ip(), ip.to_string());
+}
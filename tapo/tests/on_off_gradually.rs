@@ -0,0 +1,71 @@
+use serde_json::json;
+use tapo::Error;
+use tapo::responses::GradualOnOffState;
+use tapo::testing::MockDevice;
+
+mod common;
+
+use common::client;
+
+#[tokio::test]
+async fn on_off_gradually_info_is_changed() {
+    let server = MockDevice::new("L530")
+        .with_response(
+            "get_on_off_gradually_info",
+            json!({
+                "on_state": { "enable": false, "duration": 1, "max_duration": 60 },
+                "off_state": { "enable": true, "duration": 1, "max_duration": 60 },
+            }),
+        )
+        .with_response("set_on_off_gradually_info", json!({}))
+        .start()
+        .await
+        .unwrap();
+    let device = client().l530(server.ip_address()).await.unwrap();
+
+    let mut info = device.get_on_off_gradually_info().await.unwrap();
+    assert_eq!(info.enable, None);
+    assert_eq!(
+        info.on_state,
+        Some(GradualOnOffState {
+            enable: false,
+            duration: 1,
+            max_duration: Some(60),
+        })
+    );
+
+    info.on_state = Some(GradualOnOffState::enabled(30));
+    device.set_on_off_gradually_info(info).await.unwrap();
+
+    let set_info = server
+        .requests_for("set_on_off_gradually_info")
+        .pop()
+        .unwrap();
+    assert_eq!(
+        set_info,
+        json!({
+            "on_state": { "enable": true, "duration": 30 },
+            "off_state": { "enable": true, "duration": 1 },
+        })
+    );
+
+    let mut info = device.get_on_off_gradually_info().await.unwrap();
+    info.off_state = Some(GradualOnOffState {
+        duration: 61,
+        ..info.off_state.unwrap()
+    });
+    let result = device.set_on_off_gradually_info(info.clone()).await;
+    assert!(matches!(
+        result,
+        Err(Error::Validation { ref field, .. }) if field == "off_state.duration"
+    ));
+
+    // The max duration of states built from scratch is read from the device.
+    info.off_state = Some(GradualOnOffState::enabled(90));
+    let result = device.set_on_off_gradually_info(info).await;
+    assert!(matches!(
+        result,
+        Err(Error::Validation { ref field, .. }) if field == "off_state.duration"
+    ));
+    assert_eq!(server.requests_for("set_on_off_gradually_info").len(), 1);
+}
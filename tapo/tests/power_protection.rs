@@ -0,0 +1,68 @@
+use serde_json::{Value, json};
+use tapo::Plug;
+use tapo::testing::{MockChild, MockDevice};
+
+mod common;
+
+use common::client;
+
+#[tokio::test]
+async fn power_protection_is_configured_and_cleared() {
+    let server = MockDevice::new("P110")
+        .with_response(
+            "get_protection_power",
+            json!({ "enabled": true, "protection_power": 1500 }),
+        )
+        .with_response("get_max_power", json!({ "max_power": 3680 }))
+        .with_response("set_protection_power", Value::Null)
+        .start()
+        .await
+        .unwrap();
+
+    let device = client().p110(server.ip_address()).await.unwrap();
+
+    let power_protection = device.get_power_protection().await.unwrap();
+    assert!(power_protection.enabled);
+    assert_eq!(power_protection.protection_power, 1500);
+
+    assert!(device.set_power_protection(4000).await.is_err());
+    device.set_power_protection(2000).await.unwrap();
+    device.disable_power_protection().await.unwrap();
+
+    let set_requests = server.requests_for("set_protection_power");
+    assert_eq!(
+        set_requests,
+        vec![
+            json!({ "enabled": true, "protection_power": 2000 }),
+            json!({ "enabled": false }),
+        ]
+    );
+
+    device.clear_power_protection_status().await.unwrap();
+    assert_eq!(server.device_info()["power_protection_status"], "normal");
+}
+
+#[tokio::test]
+async fn power_strip_plugs_are_configured_through_the_strip() {
+    let server = MockDevice::new("P304M")
+        .with_child(
+            MockChild::new("P304M")
+                .with_device_info(json!({ "power_protection_status": "overloaded" }))
+                .with_response("get_max_power", json!({ "max_power": 3680 }))
+                .with_response("set_protection_power", Value::Null),
+        )
+        .start()
+        .await
+        .unwrap();
+
+    let strip = client().p304(server.ip_address()).await.unwrap();
+    let plug = strip.plug(Plug::ByPosition(1)).await.unwrap();
+
+    plug.set_power_protection(1000).await.unwrap();
+    plug.clear_power_protection_status().await.unwrap();
+
+    assert_eq!(
+        server.child_device_list()[0]["power_protection_status"],
+        "normal"
+    );
+}
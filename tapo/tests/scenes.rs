@@ -0,0 +1,158 @@
+use std::time::Duration;
+
+use tapo::Error;
+use tapo::StreamExt as _;
+use tapo::scenes::{Scene, SceneColor, SceneState};
+use tapo::testing::MockDevice;
+
+mod common;
+
+use common::client;
+
+#[tokio::test]
+async fn scene_is_played_until_the_last_keyframe() {
+    let server = MockDevice::new("L530").start().await.unwrap();
+    let device = client().l530(server.ip_address()).await.unwrap();
+
+    let scene = Scene::new(SceneState::new(10, SceneColor::ColorTemperature(2700)))
+        .then(
+            Duration::from_millis(400),
+            SceneState::new(50, SceneColor::ColorTemperature(4000)),
+        )
+        .with_min_interval(Duration::from_millis(100));
+
+    let steps: Vec<_> = scene
+        .play(&device)
+        .map(|step| step.unwrap())
+        .collect()
+        .await;
+
+    assert!(steps.len() >= 2, "{steps:?}");
+    assert_eq!(
+        steps[0].state,
+        SceneState::new(10, SceneColor::ColorTemperature(2700))
+    );
+    assert_eq!(
+        steps.last().unwrap().state,
+        SceneState::new(50, SceneColor::ColorTemperature(4000))
+    );
+    assert!(steps.windows(2).all(|w| w[0].elapsed < w[1].elapsed));
+
+    let requests = server.requests_for("set_device_info");
+    assert_eq!(requests.len(), steps.len());
+
+    let last = requests.last().unwrap();
+    assert_eq!(last["brightness"], 50);
+    assert_eq!(last["color_temp"], 4000);
+}
+
+#[tokio::test]
+async fn scene_keeps_playing_while_the_playback_is_not_read() {
+    let server = MockDevice::new("L530").start().await.unwrap();
+    let device = client().l530(server.ip_address()).await.unwrap();
+
+    let playback = Scene::new(SceneState::new(1, SceneColor::ColorTemperature(2700)))
+        .then(
+            Duration::from_millis(2000),
+            SceneState::new(100, SceneColor::ColorTemperature(2700)),
+        )
+        .with_min_interval(Duration::from_millis(10))
+        .play(&device);
+
+    let finished = async {
+        loop {
+            let requests = server.requests_for("set_device_info");
+            if requests
+                .last()
+                .is_some_and(|last| last["brightness"] == 100)
+            {
+                return requests.len();
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    };
+    let sent = tokio::time::timeout(Duration::from_secs(10), finished)
+        .await
+        .expect("the scene didn't finish while the playback wasn't read");
+
+    // The steps sent while the buffer was full are left out, the stream still ends.
+    let steps: Vec<_> = playback.map(|step| step.unwrap()).collect().await;
+    assert!(steps.len() < sent, "{} of {sent}", steps.len());
+    assert_eq!(steps[0].state.brightness, 1);
+}
+
+#[tokio::test]
+async fn scene_with_an_invalid_keyframe_ends_after_the_error() {
+    let server = MockDevice::new("L530").start().await.unwrap();
+    let device = client().l530(server.ip_address()).await.unwrap();
+
+    let scene = Scene::new(SceneState::new(0, SceneColor::ColorTemperature(2700)))
+        .then(
+            Duration::from_millis(100),
+            SceneState::new(0, SceneColor::ColorTemperature(2700)),
+        )
+        .with_min_interval(Duration::from_millis(10));
+
+    let steps = tokio::time::timeout(
+        Duration::from_secs(5),
+        scene.play(&device).collect::<Vec<_>>(),
+    )
+    .await
+    .expect("the playback of an invalid scene didn't end");
+
+    assert_eq!(steps.len(), 1, "{steps:?}");
+    assert!(matches!(steps[0], Err(Error::Validation { .. })));
+    assert!(server.requests_for("set_device_info").is_empty());
+}
+
+#[tokio::test]
+async fn scene_ends_when_the_device_is_unreachable() {
+    let server = MockDevice::new("L530").start().await.unwrap();
+    let device = client().l530(server.ip_address()).await.unwrap();
+    drop(server);
+
+    let scene = Scene::new(SceneState::new(10, SceneColor::ColorTemperature(2700)))
+        .then(
+            Duration::from_millis(50),
+            SceneState::new(50, SceneColor::ColorTemperature(2700)),
+        )
+        .with_min_interval(Duration::from_millis(10));
+
+    let steps = tokio::time::timeout(
+        Duration::from_secs(10),
+        scene.play(&device).collect::<Vec<_>>(),
+    )
+    .await
+    .expect("the playback on an unreachable device didn't end");
+
+    assert!(!steps.is_empty());
+    assert!(steps.iter().all(Result::is_err));
+}
+
+#[tokio::test]
+async fn dropping_the_playback_stops_the_scene() {
+    let server = MockDevice::new("L930").start().await.unwrap();
+    let device = client().l930(server.ip_address()).await.unwrap();
+
+    let mut playback = Scene::sunrise(Duration::from_secs(60))
+        .with_min_interval(Duration::from_millis(50))
+        .play(&device);
+
+    let step = playback.next().await.unwrap().unwrap();
+    assert_eq!(
+        step.state,
+        SceneState::new(
+            1,
+            SceneColor::HueSaturation {
+                hue: 0,
+                saturation: 100
+            }
+        )
+    );
+
+    drop(playback);
+    let sent = server.requests_for("set_device_info").len();
+    tokio::time::sleep(Duration::from_millis(300)).await;
+
+    assert!(server.requests_for("set_device_info").len() <= sent + 1);
+}
@@ -0,0 +1,198 @@
+use chrono::Weekday;
+use serde_json::{Value, json};
+use tapo::Plug;
+use tapo::requests::{ScheduleRecurrence, ScheduleTime};
+use tapo::responses::PowerState;
+use tapo::testing::{MockChild, MockDevice};
+
+mod common;
+
+use common::client;
+
+fn rule_list() -> Value {
+    json!({
+        "enable": true,
+        "schedule_rule_max_count": 32,
+        "start_index": 0,
+        "sum": 3,
+        "rule_list": [
+            {
+                "id": "S1",
+                "enable": true,
+                "mode": "repeat",
+                "week_day": 62,
+                "s_type": "normal",
+                "s_min": 420,
+                "time_offset": 0,
+                "e_type": "normal",
+                "e_min": 540,
+                "e_action": "off",
+                "year": 2026,
+                "month": 1,
+                "day": 1,
+                "desired_states": { "on": true },
+            },
+            {
+                "id": "S2",
+                "enable": false,
+                "mode": "repeat",
+                "week_day": 127,
+                "s_type": "sunset",
+                "s_min": 0,
+                "time_offset": -30,
+                "e_type": "normal",
+                "e_min": 0,
+                "e_action": "none",
+                "year": 2026,
+                "month": 1,
+                "day": 1,
+                "desired_states": { "on": false },
+            },
+            {
+                "id": "S3",
+                "enable": true,
+                "mode": "interval",
+                "s_type": "normal",
+                "s_min": 0,
+                "desired_states": { "on": true },
+            },
+        ],
+    })
+}
+
+#[tokio::test]
+async fn schedule_rules_are_parsed_and_managed() {
+    let server = MockDevice::new("P110")
+        .with_response("get_schedule_rules", rule_list())
+        .with_response("add_schedule_rule", json!({ "id": "S4" }))
+        .with_response("edit_schedule_rule", Value::Null)
+        .with_response("remove_schedule_rules", Value::Null)
+        .start()
+        .await
+        .unwrap();
+
+    let device = client().p110(server.ip_address()).await.unwrap();
+
+    let schedule = device.get_schedule_rules().await.unwrap();
+    assert!(schedule.enabled);
+    assert_eq!(schedule.max_count, 32);
+    assert_eq!(schedule.rules.len(), 2);
+    assert_eq!(schedule.rules[0].time, ScheduleTime::at(7, 0));
+    assert_eq!(
+        schedule.rules[0].recurrence,
+        ScheduleRecurrence::Weekly(vec![
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+        ])
+    );
+    assert_eq!(
+        schedule.rules[1].time,
+        ScheduleTime::Sunset {
+            offset_minutes: -30
+        }
+    );
+    assert_eq!(schedule.rules[1].recurrence, ScheduleRecurrence::daily());
+    assert_eq!(schedule.rules[1].desired_state, PowerState::Off);
+    assert_eq!(schedule.unsupported_rules.len(), 1);
+    assert_eq!(schedule.unsupported_rules[0]["id"], "S3");
+
+    let mut first = schedule.rules[0].clone();
+    first.time = ScheduleTime::at(6, 30);
+    device.edit_schedule_rule(&first).await.unwrap();
+
+    let mut rule = device
+        .add_schedule_rule(
+            ScheduleTime::at(22, 30),
+            ScheduleRecurrence::Weekly(vec![Weekday::Sat, Weekday::Sun]),
+            PowerState::Off,
+        )
+        .await
+        .unwrap();
+    assert_eq!(rule.id, "S4");
+    assert!(rule.enabled);
+
+    rule.enabled = false;
+    device.edit_schedule_rule(&rule).await.unwrap();
+    device
+        .remove_schedule_rules(vec!["S1".to_string()])
+        .await
+        .unwrap();
+
+    let params = |method: &str| {
+        server
+            .requests_for(method)
+            .into_iter()
+            .next()
+            .unwrap_or_else(|| panic!("{method} wasn't sent"))
+    };
+
+    let added = params("add_schedule_rule");
+    assert_eq!(added["enable"], true);
+    assert_eq!(added["mode"], "repeat");
+    assert_eq!(added["week_day"], 0b100_0001);
+    assert_eq!(added["s_type"], "normal");
+    assert_eq!(added["s_min"], 22 * 60 + 30);
+    assert_eq!(added["desired_states"], json!({ "on": false }));
+    assert!(added.get("id").is_none());
+
+    let edited = server.requests_for("edit_schedule_rule");
+    assert_eq!(edited[0]["id"], "S1");
+    assert_eq!(edited[0]["s_min"], 6 * 60 + 30);
+    assert_eq!(edited[0]["e_min"], 540);
+    assert_eq!(edited[0]["e_action"], "off");
+    assert_eq!(edited[1]["id"], "S4");
+    assert_eq!(edited[1]["enable"], false);
+
+    assert_eq!(
+        params("remove_schedule_rules"),
+        json!({ "remove_all": false, "rule_list": [{ "id": "S1" }] })
+    );
+}
+
+#[tokio::test]
+async fn invalid_schedule_rules_are_not_sent() {
+    let server = MockDevice::new("L530").start().await.unwrap();
+    let device = client().l530(server.ip_address()).await.unwrap();
+
+    let result = device
+        .add_schedule_rule(
+            ScheduleTime::Sunrise {
+                offset_minutes: 12 * 60,
+            },
+            ScheduleRecurrence::daily(),
+            PowerState::On,
+        )
+        .await;
+
+    assert!(result.is_err());
+    assert!(server.requests().is_empty());
+}
+
+#[tokio::test]
+async fn power_strip_plugs_manage_their_own_schedule_rules() {
+    let server = MockDevice::new("P300")
+        .with_child(MockChild::new("P300"))
+        .with_child(
+            MockChild::new("P300")
+                .with_response("get_schedule_rules", rule_list())
+                .with_response("remove_schedule_rules", Value::Null),
+        )
+        .start()
+        .await
+        .unwrap();
+
+    let strip = client().p300(server.ip_address()).await.unwrap();
+    let plug = strip.plug(Plug::ByPosition(2)).await.unwrap();
+
+    let schedule = plug.get_schedule_rules().await.unwrap();
+    assert_eq!(schedule.rules.len(), 2);
+
+    plug.remove_all_schedule_rules().await.unwrap();
+
+    let removed = server.child_requests_for("remove_schedule_rules");
+    assert_eq!(removed.len(), 1);
+    assert_eq!(removed[0]["remove_all"], true);
+}
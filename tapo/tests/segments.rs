@@ -0,0 +1,92 @@
+use serde_json::json;
+use tapo::Error;
+use tapo::testing::MockDevice;
+
+mod common;
+
+use common::client;
+
+#[tokio::test]
+async fn segments_are_painted_with_a_static_lighting_effect() {
+    let server = MockDevice::new("L930")
+        .with_response("set_lighting_effect", json!({ "response": "" }))
+        .start()
+        .await
+        .unwrap();
+    let device = client().l930(server.ip_address()).await.unwrap();
+
+    device
+        .set_segments(&[(0, [0, 100, 100]), (4, [240, 100, 30])])
+        .await
+        .unwrap();
+
+    let params = server.requests_for("set_lighting_effect").pop().unwrap();
+    assert_eq!(params["type"], "static");
+    assert_eq!(params["custom"], 1);
+    assert_eq!(params["segments"], json!([0, 4]));
+    assert_eq!(params["sequence"], json!([[0, 100, 100], [240, 100, 30]]));
+}
+
+#[tokio::test]
+async fn gradients_are_spread_over_the_segment_count() {
+    let server = MockDevice::new("L930")
+        .with_device_info(json!({ "segment_count": 3 }))
+        .with_response("set_lighting_effect", json!({ "response": "" }))
+        .start()
+        .await
+        .unwrap();
+    let device = client().l930(server.ip_address()).await.unwrap();
+
+    assert_eq!(device.get_segment_count().await.unwrap(), Some(3));
+
+    let result = device.set_segments(&[(3, [0, 100, 100])]).await;
+    assert!(matches!(
+        result,
+        Err(Error::Validation { ref field, .. }) if field == "segments"
+    ));
+    assert!(server.requests_for("set_lighting_effect").is_empty());
+
+    device
+        .set_segment_gradient(&[[0, 100, 100], [120, 100, 50]])
+        .await
+        .unwrap();
+
+    let params = server.requests_for("set_lighting_effect").pop().unwrap();
+    assert_eq!(params["segments"], json!([0, 1, 2]));
+    assert_eq!(
+        params["sequence"],
+        json!([[0, 100, 100], [60, 100, 75], [120, 100, 50]])
+    );
+
+    let server = MockDevice::new("L920").start().await.unwrap();
+    let device = client().l920(server.ip_address()).await.unwrap();
+
+    assert_eq!(device.get_segment_count().await.unwrap(), None);
+    assert!(
+        device
+            .set_segment_gradient(&[[0, 100, 100], [120, 100, 50]])
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn segment_count_is_read_once_per_session() {
+    let server = MockDevice::new("L930")
+        .with_device_info(json!({ "segment_count": 3 }))
+        .with_response("set_lighting_effect", json!({ "response": "" }))
+        .start()
+        .await
+        .unwrap();
+    let device = client().l930(server.ip_address()).await.unwrap();
+    let before = server.requests_for("get_device_info").len();
+
+    device
+        .set_segment_gradient(&[[0, 100, 100], [120, 100, 50]])
+        .await
+        .unwrap();
+    device.set_segments(&[(2, [0, 100, 100])]).await.unwrap();
+    assert_eq!(device.get_segment_count().await.unwrap(), Some(3));
+
+    assert_eq!(server.requests_for("get_device_info").len(), before + 1);
+}
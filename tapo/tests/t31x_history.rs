@@ -0,0 +1,63 @@
+use serde_json::json;
+use tapo::responses::{TemperatureHumidityPeriod, TemperatureUnit};
+use tapo::testing::{MockChild, MockDevice, MockDeviceServer};
+use tapo::{HubDevice, T31XHandler};
+
+mod common;
+
+use common::client;
+
+async fn t310(child: MockChild) -> (MockDeviceServer, T31XHandler) {
+    let server = MockDevice::new("H100")
+        .with_child(child)
+        .start()
+        .await
+        .unwrap();
+
+    let hub = client().h100(server.ip_address()).await.unwrap();
+    let device_id = server.child_device_list()[0]["device_id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let t310 = hub.t31x(HubDevice::ByDeviceId(device_id)).await.unwrap();
+
+    (server, t310)
+}
+
+#[tokio::test]
+async fn records_are_aggregated_per_hour_and_day() {
+    let (_server, t310) = t310(MockChild::new("T310").with_response(
+        "get_temp_humidity_records",
+        json!({
+            "local_time": 1773110400,
+            "past24h_humidity_exception": [0, 0, 0, 0, 0],
+            "past24h_humidity": [50, 52, 54, 56, -1000],
+            "past24h_temp_exception": [0, 0, 0, 0, 0],
+            "past24h_temp": [190, 200, 210, 220, -1000],
+            "temp_unit": "celsius",
+        }),
+    ))
+    .await;
+
+    let records = t310.get_temperature_humidity_records().await.unwrap();
+    let hourly = records.aggregate(TemperatureHumidityPeriod::Hour);
+
+    assert_eq!(hourly.stats.len(), 2);
+    assert_eq!(hourly.stats[0].temperature_min, 19.0);
+    assert_eq!(hourly.stats[0].temperature_max, 20.0);
+    assert_eq!(hourly.stats[1].temperature_avg, 21.5);
+    assert_eq!(hourly.stats[1].humidity_avg, 55.0);
+
+    let daily = records.aggregate(TemperatureHumidityPeriod::Day);
+    assert_eq!(daily.stats.len(), 1);
+    assert_eq!(daily.stats[0].temperature_avg, 20.5);
+    assert_eq!(daily.stats[0].humidity_min, 50);
+    assert_eq!(daily.stats[0].humidity_max, 56);
+
+    let fahrenheit = daily.to_unit(TemperatureUnit::Fahrenheit);
+    assert_eq!(fahrenheit.stats[0].temperature_max, 71.6);
+    assert_eq!(
+        fahrenheit.to_csv().lines().nth(1),
+        Some("2026-03-10T00:00:00Z,day,66.2,71.6,68.9,50,56,53,fahrenheit")
+    );
+}